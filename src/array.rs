@@ -0,0 +1,5318 @@
+//! A heap-backed array of bits, spanning any number of bytes.
+
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt::Write;
+use core::hash::{Hash, Hasher};
+use core::iter::{
+    self, DoubleEndedIterator, ExactSizeIterator, FromIterator, FusedIterator, IntoIterator,
+    Iterator,
+};
+use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
+use core::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Deref, DerefMut, Index, Not,
+    Range, Shl, ShlAssign, Shr, ShrAssign,
+};
+use core::{fmt, slice};
+#[cfg(feature = "std")]
+use std::io::{self, Read};
+
+use crate::{
+    reverse_index_bits, Bit, BitError, BitOrder, BitSlice, BitSliceExt, BitVec, Byte, ByteIndices,
+    SliceBitIter,
+};
+
+/// A fixed-length sequence of bits, packed into 64-bit blocks.
+///
+/// Indices follow the crate convention: index 0 is the left-most bit of the first byte,
+/// index 8 is the left-most bit of the second byte, and so on.
+/// Any unused bits of the final block are kept at zero.
+///
+/// The `&`, `|` and `^` operators treat arrays as sets of indices. Arrays of different lengths are
+/// combined by zero-extending the shorter one, so the result has the longer length.
+///
+/// Arrays order lexicographically by bit, like slices, so an array sorts before any longer array
+/// that it is a prefix of. An array hashes the same as a [`BitSlice`] holding the same bits, and
+/// compares equal to one.
+#[derive(Clone, Default, Eq, PartialEq)]
+pub struct BitArray {
+    /// The blocks holding the bits of the array, 64 to a block.
+    ///
+    /// Each block is stored big-endian, so that in memory the blocks are the bytes of the array in order.
+    /// This lets bulk operations work on whole blocks while the bytes can still be borrowed directly.
+    pub(crate) blocks: Vec<u64>,
+    /// The number of bits in the array.
+    pub(crate) len: usize,
+}
+
+impl BitArray {
+    /// Creates an array of `len` bits, all of which are `0`.
+    pub fn new(len: usize) -> Self {
+        Self {
+            blocks: vec![0; len.div_ceil(64)],
+            len,
+        }
+    }
+
+    /// The bytes holding the bits of the array, from the start of the blocks.
+    pub(crate) fn bytes(&self) -> &[Byte] {
+        // SAFETY: `Byte` is a `repr(transparent)` wrapper around `u8`, so it has no alignment
+        // requirement, and the blocks hold at least `len.div_ceil(8)` bytes.
+        unsafe { slice::from_raw_parts(self.blocks.as_ptr().cast(), self.len.div_ceil(8)) }
+    }
+
+    /// The bytes holding the bits of the array, from the start of the blocks.
+    pub(crate) fn bytes_mut(&mut self) -> &mut [Byte] {
+        // SAFETY: as for `bytes`, and the borrow of the blocks is unique.
+        unsafe { slice::from_raw_parts_mut(self.blocks.as_mut_ptr().cast(), self.len.div_ceil(8)) }
+    }
+
+    /// Changes the length of the array to `len` bits, adding `0` bits at the end or dropping bits
+    /// from the end.
+    pub(crate) fn resize_len(&mut self, len: usize) {
+        // The unused bits are zero, so growing needs no more than new blocks.
+        self.blocks.resize(len.div_ceil(64), 0);
+        self.len = len;
+        self.clear_padding();
+    }
+
+    /// The mask selecting bit `idx` within its block, as stored.
+    #[inline]
+    fn block_mask(idx: usize) -> u64 {
+        (0x8000_0000_0000_0000_u64 >> (idx & 63)).to_be()
+    }
+
+    /// The mask selecting the bits of the final block that are part of the array, in logical order.
+    fn last_block_mask(&self) -> u64 {
+        match self.len % 64 {
+            0 => u64::MAX,
+            n => !(u64::MAX >> n),
+        }
+    }
+
+    /// Creates an array holding every bit of `bytes`, reading the bits of each byte in the given order.
+    ///
+    /// With [`BitOrder::Msb0`] the bits keep their positions; with [`BitOrder::Lsb0`] the right-most bit
+    /// of each byte becomes the first bit of that byte in the array.
+    pub fn from_bytes_ordered(bytes: &[u8], order: BitOrder) -> Self {
+        let mut arr = Self::new(bytes.len() * 8);
+        for (dst, &b) in arr.bytes_mut().iter_mut().zip(bytes) {
+            *dst = match order {
+                BitOrder::Msb0 => Byte::from(b),
+                BitOrder::Lsb0 => Byte::from(b.reverse_bits()),
+            };
+        }
+        arr
+    }
+
+    /// Creates an array of `len` bits, where the bit at index `i` is `f(i)`.
+    ///
+    /// `f` is called exactly once for each index, in ascending order, and the bits are packed a
+    /// byte at a time.
+    ///
+    /// ```
+    /// use bit_array_rs::BitArray;
+    ///
+    /// let alternating = BitArray::from_fn(12, |i| i % 2 == 0);
+    /// assert_eq!(alternating.as_bytes(), [0b1010_1010, 0b1010_0000]);
+    /// ```
+    pub fn from_fn<F: FnMut(usize) -> bool>(len: usize, mut f: F) -> Self {
+        let mut arr = Self::new(len);
+        for (idx, byte) in arr.bytes_mut().iter_mut().enumerate() {
+            *byte = Byte::from_fn(|i| {
+                let pos = idx * 8 + i;
+                pos < len && f(pos)
+            });
+        }
+        arr.debug_check_padding();
+        arr
+    }
+
+    /// The largest order accepted by [`BitArray::de_bruijn`].
+    pub const DE_BRUIJN_MAX_ORDER: usize = 24;
+
+    /// The binary de Bruijn sequence B(2, n): `2^n` bits in which, read cyclically, every `n`-bit
+    /// pattern starts at exactly one index.
+    ///
+    /// The sequence is the lexicographically least one, built by concatenating the Lyndon words
+    /// whose length divides `n`, so it starts with `n` zeros. `n = 0` gives the single bit `0`.
+    /// Returns an error if `n` is greater than [`BitArray::DE_BRUIJN_MAX_ORDER`].
+    ///
+    /// ```
+    /// use bit_array_rs::BitArray;
+    ///
+    /// let seq = BitArray::de_bruijn(3)?;
+    /// assert_eq!(seq.as_bytes(), [0b0001_0111]);
+    /// # Ok::<(), bit_array_rs::BitError>(())
+    /// ```
+    pub fn de_bruijn(n: usize) -> Result<BitArray, BitError> {
+        if n > Self::DE_BRUIJN_MAX_ORDER {
+            return Err(BitError::InvalidLength {
+                len: n,
+                max: Self::DE_BRUIJN_MAX_ORDER,
+            });
+        }
+        let mut seq = BitArray::new(1 << n);
+        if n == 0 {
+            return Ok(seq);
+        }
+        let mut pos = 0;
+        // Each Lyndon word in turn, with `true` for a `1`.
+        let mut word = vec![false];
+        loop {
+            if n.is_multiple_of(word.len()) {
+                for &bit in &word {
+                    seq.set(pos, bit);
+                    pos += 1;
+                }
+            }
+            // The next Lyndon word: repeat the word out to `n` bits, drop the trailing ones,
+            // and change the last zero to a one.
+            let period = word.len();
+            while word.len() < n {
+                word.push(word[word.len() - period]);
+            }
+            while word.last() == Some(&true) {
+                word.pop();
+            }
+            match word.last_mut() {
+                Some(last) => *last = true,
+                None => break,
+            }
+        }
+        debug_assert_eq!(pos, seq.len);
+        Ok(seq)
+    }
+
+    /// Creates an array holding every bit of `bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        Self::from_bytes_ordered(bytes, BitOrder::Msb0)
+    }
+
+    /// Creates an array of `bit_len` bits from the start of `bytes`.
+    ///
+    /// Bytes past those needed for `bit_len` bits are dropped, and the unused bits of the final byte
+    /// are cleared. Returns an error if `bit_len` is greater than the number of bits in `bytes`.
+    ///
+    /// The bytes are copied into newly allocated storage, however they are aligned: the array keeps
+    /// its bits in 64-bit blocks, and a `Vec<u8>` buffer cannot be reused as a `Vec<u64>`, since it
+    /// was allocated with a different alignment.
+    pub fn from_vec(bytes: Vec<u8>, bit_len: usize) -> Result<Self, BitError> {
+        if bit_len > bytes.len() * 8 {
+            return Err(BitError::InvalidLength {
+                len: bit_len,
+                max: bytes.len() * 8,
+            });
+        }
+        let mut arr = Self::new(bit_len);
+        for (dst, &b) in arr.bytes_mut().iter_mut().zip(&bytes) {
+            *dst = Byte(b);
+        }
+        arr.clear_padding();
+        Ok(arr)
+    }
+
+    /// Creates an array of `bit_len` bits from exactly the bytes needed to hold them, checking that
+    /// the unused bits of the final byte are zero.
+    ///
+    /// Unlike [`BitArray::from_vec`], which drops extra bytes and clears the padding, this returns a
+    /// [`BitError::LengthMismatch`] of the byte counts if `bytes` is not `bit_len.div_ceil(8)` long,
+    /// and a [`BitError::NonZeroPadding`] if any bit past `bit_len` is set.
+    ///
+    /// ```
+    /// use bit_array_rs::{BitArray, BitError};
+    ///
+    /// let arr = BitArray::try_from_bytes_strict(&[0xFF, 0b1110_0000], 11).unwrap();
+    /// assert_eq!(arr.count_ones(), 11);
+    /// assert_eq!(
+    ///     BitArray::try_from_bytes_strict(&[0xFF, 0b1111_0000], 11),
+    ///     Err(BitError::NonZeroPadding { len: 11 })
+    /// );
+    /// ```
+    pub fn try_from_bytes_strict(bytes: &[u8], bit_len: usize) -> Result<Self, BitError> {
+        if bytes.len() != bit_len.div_ceil(8) {
+            return Err(BitError::LengthMismatch {
+                left: bytes.len(),
+                right: bit_len.div_ceil(8),
+            });
+        }
+        if let Some(&last) = bytes.last() {
+            let used = bit_len % 8;
+            if used != 0 && last & (0xFF >> used) != 0 {
+                return Err(BitError::NonZeroPadding { len: bit_len });
+            }
+        }
+        let mut arr = Self::new(bit_len);
+        for (dst, &b) in arr.bytes_mut().iter_mut().zip(bytes) {
+            *dst = Byte(b);
+        }
+        Ok(arr)
+    }
+
+    /// The bytes holding the bits of the array, without copying; any unused bits of the final byte are zero.
+    pub fn as_bytes(&self) -> &[u8] {
+        // SAFETY: `Byte` is a `repr(transparent)` wrapper around `u8`.
+        unsafe { slice::from_raw_parts(self.bytes().as_ptr().cast(), self.bytes().len()) }
+    }
+
+    /// Consumes the array, returning the bytes holding its bits.
+    ///
+    /// The bytes are copied into a new `Vec<u8>`, for the same reason as in [`BitArray::from_vec`].
+    /// Use [`BitArray::as_bytes`] to borrow them, or [`BitArray::into_raw_parts`] to take the
+    /// buffer itself.
+    pub fn into_vec(self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+
+    /// Consumes the array, returning the bytes holding its bits with any unused bits of the final
+    /// byte set to `pad`.
+    ///
+    /// With [`Bit::ZERO`] this is the same as [`BitArray::into_vec`].
+    pub fn into_bytes(self, pad: Bit) -> Vec<u8> {
+        let used = self.len % 8;
+        let mut bytes = self.into_vec();
+        if let Some(last) = bytes.last_mut() {
+            if used != 0 && pad.is_one() {
+                *last |= 0xFF >> used;
+            }
+        }
+        bytes
+    }
+
+    /// Consumes the array, returning a pointer to its bytes, its length in bits and the capacity
+    /// of its buffer in bytes.
+    ///
+    /// The first `bit_len.div_ceil(8)` bytes behind the pointer hold the bits as for
+    /// [`BitArray::as_bytes`]. The buffer is aligned to 8 bytes and its capacity is a multiple of 8.
+    /// The caller takes ownership of the buffer, and must pass all three parts back to
+    /// [`BitArray::from_raw_parts`] to free it; freeing it any other way is undefined behavior.
+    pub fn into_raw_parts(self) -> (*mut u8, usize, usize) {
+        let mut arr = ManuallyDrop::new(self);
+        (
+            arr.blocks.as_mut_ptr().cast(),
+            arr.len,
+            arr.blocks.capacity() * 8,
+        )
+    }
+
+    /// Rebuilds an array from the parts returned by [`BitArray::into_raw_parts`].
+    ///
+    /// The bytes may have been changed in between; any bits past `bit_len` are cleared.
+    ///
+    /// # Safety
+    ///
+    /// `ptr`, `bit_len` and `byte_cap` must be exactly the parts returned by a single call to
+    /// [`BitArray::into_raw_parts`], and the buffer must not have been freed or rebuilt since. As for
+    /// `Vec::from_raw_parts`, the buffer is owned by the returned array, and is freed when it drops.
+    pub unsafe fn from_raw_parts(ptr: *mut u8, bit_len: usize, byte_cap: usize) -> Self {
+        debug_assert!(
+            ptr.align_offset(8) == 0 && byte_cap.is_multiple_of(8),
+            "raw parts were not returned by into_raw_parts"
+        );
+        // SAFETY: the caller guarantees that the parts came from the blocks of an array of
+        // `bit_len` bits, which were allocated as `u64`s with a capacity of `byte_cap / 8`.
+        let blocks = unsafe { Vec::from_raw_parts(ptr.cast(), bit_len.div_ceil(64), byte_cap / 8) };
+        let mut arr = BitArray {
+            blocks,
+            len: bit_len,
+        };
+        arr.clear_padding();
+        arr
+    }
+
+    /// A borrowed, `#[repr(C)]` descriptor of the array's bytes, for passing across an FFI boundary.
+    pub fn as_view(&self) -> BitArrayView<'_> {
+        BitArrayView::from(self)
+    }
+
+    /// Sets the unused bits of the final block to zero.
+    pub(crate) fn clear_padding(&mut self) {
+        let mask = self.last_block_mask();
+        if let Some(last) = self.blocks.last_mut() {
+            *last = (u64::from_be(*last) & mask).to_be();
+        }
+        self.debug_check_padding();
+    }
+
+    /// Checks, in debug builds, that the storage holds exactly the blocks needed and that no bit
+    /// past the end of the array is set.
+    ///
+    /// Equality, hashing and counting all rely on this, so every mutation ends by checking it.
+    pub(crate) fn debug_check_padding(&self) {
+        debug_assert_eq!(self.blocks.len(), self.len.div_ceil(64));
+        debug_assert!(
+            self.blocks
+                .last()
+                .is_none_or(|&last| u64::from_be(last) & !self.last_block_mask() == 0),
+            "bits past the end of a bit array of length {} are set",
+            self.len
+        );
+    }
+
+    /// The number of bits in the array.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// True if the array holds no bits.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Panics if `idx` is not a valid index into the array.
+    fn check_index(&self, idx: usize) {
+        assert!(
+            idx < self.len,
+            "index {} out of range for bit array of length {}",
+            idx,
+            self.len
+        );
+    }
+
+    /// Getter for the bit at index `idx`.
+    ///
+    /// Panics if `idx` is not less than the length of the array.
+    #[inline]
+    pub fn get(&self, idx: usize) -> Bit {
+        self.check_index(idx);
+        // SAFETY: `idx` was just checked against the length.
+        unsafe { self.get_unchecked(idx) }
+    }
+
+    /// Setter for the bit at index `idx`.
+    ///
+    /// Panics if `idx` is not less than the length of the array.
+    #[inline]
+    pub fn set(&mut self, idx: usize, val: bool) {
+        self.check_index(idx);
+        // SAFETY: `idx` was just checked against the length.
+        unsafe { self.set_unchecked(idx, val) }
+    }
+
+    /// Getter for the bit at index `idx`, without checking that it is in range.
+    ///
+    /// # Safety
+    ///
+    /// `idx` must be less than the length of the array. Debug builds assert this; in release
+    /// builds an index past the end of the storage is undefined behavior.
+    #[inline]
+    pub unsafe fn get_unchecked(&self, idx: usize) -> Bit {
+        debug_assert!(
+            idx < self.len,
+            "index {} out of range for bit array of length {}",
+            idx,
+            self.len
+        );
+        // SAFETY: the caller guarantees `idx < len`, and the blocks hold at least `len` bits.
+        let block = unsafe { *self.blocks.get_unchecked(idx >> 6) };
+        Bit::from(block & Self::block_mask(idx) != 0)
+    }
+
+    /// Setter for the bit at index `idx`, without checking that it is in range.
+    ///
+    /// # Safety
+    ///
+    /// `idx` must be less than the length of the array. Debug builds assert this; in release
+    /// builds an index past the end of the storage is undefined behavior, and an index into the
+    /// padding of the final block would break the invariant that the padding is zero.
+    #[inline]
+    pub unsafe fn set_unchecked(&mut self, idx: usize, val: bool) {
+        debug_assert!(
+            idx < self.len,
+            "index {} out of range for bit array of length {}",
+            idx,
+            self.len
+        );
+        let mask = Self::block_mask(idx);
+        // SAFETY: the caller guarantees `idx < len`, and the blocks hold at least `len` bits.
+        let block = unsafe { self.blocks.get_unchecked_mut(idx >> 6) };
+        if val {
+            *block |= mask;
+        } else {
+            *block &= !mask;
+        }
+        self.debug_check_padding();
+    }
+
+    /// Flips the bit at index `idx`, returning its new value.
+    ///
+    /// Panics if `idx` is not less than the length of the array.
+    pub fn toggle(&mut self, idx: usize) -> Bit {
+        self.check_index(idx);
+        self.blocks[idx / 64] ^= Self::block_mask(idx);
+        self.debug_check_padding();
+        self.get(idx)
+    }
+
+    /// Sets the bit at index `idx` to `1`, returning its previous value.
+    ///
+    /// Panics if `idx` is not less than the length of the array.
+    pub fn test_and_set(&mut self, idx: usize) -> Bit {
+        self.check_index(idx);
+        self.test_and_update(idx, |block, mask| block | mask)
+    }
+
+    /// Sets the bit at index `idx` to `0`, returning its previous value.
+    ///
+    /// Panics if `idx` is not less than the length of the array.
+    pub fn test_and_clear(&mut self, idx: usize) -> Bit {
+        self.check_index(idx);
+        self.test_and_update(idx, |block, mask| block & !mask)
+    }
+
+    /// Flips the bit at index `idx`, returning its previous value.
+    ///
+    /// Panics if `idx` is not less than the length of the array.
+    pub fn test_and_toggle(&mut self, idx: usize) -> Bit {
+        self.check_index(idx);
+        self.test_and_update(idx, |block, mask| block ^ mask)
+    }
+
+    /// As for [`BitArray::test_and_set`], or an error if `idx` is not less than the length of the array.
+    pub fn try_test_and_set(&mut self, idx: usize) -> Result<Bit, BitError> {
+        self.try_check_index(idx)?;
+        Ok(self.test_and_set(idx))
+    }
+
+    /// As for [`BitArray::test_and_clear`], or an error if `idx` is not less than the length of the array.
+    pub fn try_test_and_clear(&mut self, idx: usize) -> Result<Bit, BitError> {
+        self.try_check_index(idx)?;
+        Ok(self.test_and_clear(idx))
+    }
+
+    /// As for [`BitArray::test_and_toggle`], or an error if `idx` is not less than the length of the array.
+    pub fn try_test_and_toggle(&mut self, idx: usize) -> Result<Bit, BitError> {
+        self.try_check_index(idx)?;
+        Ok(self.test_and_toggle(idx))
+    }
+
+    /// Replaces the block holding the in-range bit `idx` with `op(block, mask)`, where `mask`
+    /// selects the bit, returning the bit's previous value.
+    fn test_and_update(&mut self, idx: usize, op: impl FnOnce(u64, u64) -> u64) -> Bit {
+        let mask = Self::block_mask(idx);
+        let block = &mut self.blocks[idx / 64];
+        let old = *block;
+        *block = op(old, mask);
+        self.debug_check_padding();
+        Bit(old & mask != 0)
+    }
+
+    /// Sets every bit of the array to `1`, a block at a time.
+    pub fn set_all(&mut self) {
+        self.blocks.fill(u64::MAX);
+        self.clear_padding();
+    }
+
+    /// Sets every bit of the array to `0`, a block at a time.
+    pub fn clear_all(&mut self) {
+        self.blocks.fill(0);
+        self.debug_check_padding();
+    }
+
+    /// Flips every bit of the array, a block at a time.
+    pub fn toggle_all(&mut self) {
+        self.blocks.iter_mut().for_each(|block| *block = !*block);
+        self.clear_padding();
+    }
+
+    /// Exchanges the bits at indices `i` and `j`.
+    ///
+    /// Panics if either index is not less than the length of the array.
+    pub fn swap(&mut self, i: usize, j: usize) {
+        if self.get(i) != self.get(j) {
+            self.blocks[i / 64] ^= Self::block_mask(i);
+            self.blocks[j / 64] ^= Self::block_mask(j);
+        }
+        self.debug_check_padding();
+    }
+
+    /// Exchanges the bits in range `a` with those in range `b`, up to 64 bits at a time.
+    ///
+    /// Returns an error if either range is decreasing or extends past the end of the array, if the
+    /// ranges differ in length, or if they overlap.
+    pub fn swap_ranges(&mut self, a: Range<usize>, b: Range<usize>) -> Result<(), BitError> {
+        self.check_range(&a)?;
+        self.check_range(&b)?;
+        let len = a.end - a.start;
+        if len != b.end - b.start {
+            return Err(BitError::LengthMismatch {
+                left: len,
+                right: b.end - b.start,
+            });
+        }
+        if len > 0 && a.start < b.end && b.start < a.end {
+            return Err(BitError::Overlap {
+                first: a.start,
+                second: b.start,
+                len,
+            });
+        }
+        for offset in (0..len).step_by(64) {
+            let width = (len - offset).min(64);
+            let x = a.start + offset..a.start + offset + width;
+            let y = b.start + offset..b.start + offset + width;
+            let (x_bits, y_bits) = (self.get_bits(x.clone()), self.get_bits(y.clone()));
+            self.set_bits(x, y_bits);
+            self.set_bits(y, x_bits);
+        }
+        Ok(())
+    }
+
+    /// Copies the bits of `src` in `src_range` into the array starting at index `dst_offset`.
+    ///
+    /// The same as [`BitArray::try_copy_bits_from`], returning its errors.
+    pub fn copy_bits_from(
+        &mut self,
+        dst_offset: usize,
+        src: &BitArray,
+        src_range: Range<usize>,
+    ) -> Result<(), BitError> {
+        self.try_copy_bits_from(dst_offset, src, src_range)
+    }
+
+    /// Copies the bits of `src` in `src_range` into the array starting at index `dst_offset`, up to
+    /// 64 bits at a time.
+    ///
+    /// Returns a [`BitError::DecreasingRange`] if `src_range` is decreasing, and a
+    /// [`BitError::OutOfBounds`] holding the end of the offending range if `src_range` extends past
+    /// the end of `src` or the copied bits would extend past the end of the array. Nothing is
+    /// copied on error.
+    pub fn try_copy_bits_from(
+        &mut self,
+        dst_offset: usize,
+        src: &BitArray,
+        src_range: Range<usize>,
+    ) -> Result<(), BitError> {
+        src.check_range(&src_range)?;
+        let len = src_range.end - src_range.start;
+        self.check_range(&(dst_offset..dst_offset.saturating_add(len)))?;
+        for offset in (0..len).step_by(64) {
+            let width = (len - offset).min(64);
+            let from = src_range.start + offset;
+            let to = dst_offset + offset;
+            self.set_bits(to..to + width, src.get_bits(from..from + width));
+        }
+        Ok(())
+    }
+
+    /// Copies the bits in `src_range` to the array starting at index `dst_offset`, up to 64 bits at
+    /// a time.
+    ///
+    /// The ranges may overlap; as for `slice::copy_within`, the copy runs backwards when the
+    /// destination is after the source, so every bit is read before it is overwritten. Returns an
+    /// error if `src_range` is decreasing or either range extends past the end of the array.
+    pub fn copy_within(
+        &mut self,
+        src_range: Range<usize>,
+        dst_offset: usize,
+    ) -> Result<(), BitError> {
+        self.check_range(&src_range)?;
+        let len = src_range.end - src_range.start;
+        self.check_range(&(dst_offset..dst_offset.saturating_add(len)))?;
+        let mut copy = |offset: usize| {
+            let width = (len - offset).min(64);
+            let from = src_range.start + offset;
+            let to = dst_offset + offset;
+            let bits = self.get_bits(from..from + width);
+            self.set_bits(to..to + width, bits);
+        };
+        if dst_offset > src_range.start {
+            (0..len).step_by(64).rev().for_each(&mut copy);
+        } else {
+            (0..len).step_by(64).for_each(&mut copy);
+        }
+        Ok(())
+    }
+
+    /// Reverses the order of the bits in the array, so the first bit becomes the last.
+    pub fn reverse(&mut self) {
+        let pad = self.len.div_ceil(8) * 8 - self.len;
+        let bytes = self.bytes_mut();
+        bytes.reverse();
+        for byte in bytes.iter_mut() {
+            *byte = byte.reverse_bits();
+        }
+        // The padding bits are now at the front; shift them back out to the end.
+        if pad > 0 {
+            for i in 0..bytes.len() {
+                let next = bytes.get(i + 1).map_or(0, |b| **b);
+                bytes[i] = Byte((*bytes[i] << pad) | (next >> (8 - pad)));
+            }
+        }
+        self.debug_check_padding();
+    }
+
+    /// A new array with the bit at each index `i` moved to index [`reverse_index_bits`]`(i, log2(len))`,
+    /// the reordering applied to FFT inputs.
+    ///
+    /// Applying the permutation twice gives the original array. Returns an error if the length is
+    /// not a power of two.
+    pub fn bit_reverse_permutation(&self) -> Result<BitArray, BitError> {
+        if !self.len.is_power_of_two() {
+            return Err(BitError::NotPowerOfTwo { len: self.len });
+        }
+        let width = self.len.trailing_zeros();
+        Ok(BitArray::from_fn(self.len, |idx| {
+            *self.get(reverse_index_bits(idx, width))
+        }))
+    }
+
+    /// A guard for changing the bit at index `idx`, or `None` if it is past the end of the array.
+    ///
+    /// The guard dereferences to the bit's value, and writes any change back when it is dropped.
+    /// It borrows the array mutably, so only one guard can exist at a time.
+    pub fn get_mut(&mut self, idx: usize) -> Option<BitRefMut<'_>> {
+        if idx >= self.len {
+            return None;
+        }
+        let val = *self.get(idx);
+        Some(BitRefMut {
+            arr: self,
+            idx,
+            val,
+            orig: val,
+        })
+    }
+
+    /// The number of bits in the array with a value of `1`.
+    pub fn count_ones(&self) -> usize {
+        // The unused bits of the final block are always zero.
+        self.blocks.iter().map(|b| b.count_ones() as usize).sum()
+    }
+
+    /// The number of bits in the array with a value of `0`.
+    pub fn count_zeros(&self) -> usize {
+        self.len - self.count_ones()
+    }
+
+    /// The XOR of every bit of the array: `1` if an odd number of bits are set.
+    pub fn parity(&self) -> Bit {
+        // The unused bits of the final block are always zero, so they never change the parity.
+        let folded = self.blocks.iter().fold(0, |acc, block| acc ^ block);
+        Bit(folded.count_ones() % 2 == 1)
+    }
+
+    /// True if every bit of the array is `1`, including when the array is empty.
+    pub fn all(&self) -> bool {
+        match self.blocks.split_last() {
+            Some((&last, full)) => {
+                full.iter().all(|&block| block == u64::MAX)
+                    && u64::from_be(last) == self.last_block_mask()
+            }
+            None => true,
+        }
+    }
+
+    /// True if any bit of the array is `1`; false when the array is empty.
+    pub fn any(&self) -> bool {
+        // The unused bits of the final block are always zero.
+        self.blocks.iter().any(|&block| block != 0)
+    }
+
+    /// True if every bit of the array is `0`, including when the array is empty.
+    pub fn none(&self) -> bool {
+        !self.any()
+    }
+
+    /// The mask selecting the bits of the final byte that are part of the array.
+    fn last_byte_mask(&self) -> u8 {
+        match self.len % 8 {
+            0 => u8::MAX,
+            n => !(u8::MAX >> n),
+        }
+    }
+
+    /// The number of `0` bits before the first `1`, or the length of the array if there is none.
+    pub fn leading_zeros(&self) -> usize {
+        self.first_one().unwrap_or(self.len)
+    }
+
+    /// The number of `0` bits after the last `1`, or the length of the array if there is none.
+    pub fn trailing_zeros(&self) -> usize {
+        self.last_one().map_or(self.len, |idx| self.len - 1 - idx)
+    }
+
+    /// The index of the first `1`, or `None` if all bits are `0`.
+    pub fn first_one(&self) -> Option<usize> {
+        self.next_equal(0, true)
+    }
+
+    /// The index of the first `0`, or `None` if all bits are `1`.
+    pub fn first_zero(&self) -> Option<usize> {
+        self.next_equal(0, false)
+    }
+
+    /// The index of the last `1`, or `None` if all bits are `0`.
+    pub fn last_one(&self) -> Option<usize> {
+        self.blocks
+            .iter()
+            .rposition(|&block| block != 0)
+            .map(|i| i * 64 + 63 - u64::from_be(self.blocks[i]).trailing_zeros() as usize)
+    }
+
+    /// The number of `1` bits before index `idx`.
+    ///
+    /// This adds up the popcount of each 64-bit block before `idx`, masking the block that `idx`
+    /// falls in, so it runs in time linear in `idx / 64`.
+    /// Panics if `idx` is greater than the length of the array.
+    pub fn rank1(&self, idx: usize) -> usize {
+        assert!(
+            idx <= self.len,
+            "rank index {} out of range for bit array of length {}",
+            idx,
+            self.len
+        );
+        self.count_ones_in(0..idx)
+    }
+
+    /// The number of `1` bits in `range`, which must be increasing and lie within the array.
+    ///
+    /// The popcounts of the blocks the range spans are added up, masking the first and last.
+    fn count_ones_in(&self, range: Range<usize>) -> usize {
+        if range.is_empty() {
+            return 0;
+        }
+        let (first, last) = (range.start / 64, (range.end - 1) / 64);
+        (first..=last)
+            .map(|i| {
+                let mut block = u64::from_be(self.blocks[i]);
+                if i == first {
+                    block &= u64::MAX >> (range.start % 64);
+                }
+                if i == last {
+                    block &= u64::MAX << (63 - (range.end - 1) % 64);
+                }
+                block.count_ones() as usize
+            })
+            .sum()
+    }
+
+    /// The number of `1` bits before every multiple of `block`, up to the length of the array.
+    ///
+    /// Entry `k` is [`BitArray::rank1`]`(k * block)`, so the index has `len / block + 1` entries and
+    /// starts with `0`. It is built in one pass, and lets [`BitArray::rank1_with_index`] count only
+    /// the bits after the nearest boundary.
+    ///
+    /// Panics if `block` is zero, or if the array holds more than `u32::MAX` `1` bits.
+    pub fn prefix_counts(&self, block: usize) -> Vec<u32> {
+        assert!(block > 0, "block size must be non-zero");
+        let mut total = 0;
+        (0..=self.len / block)
+            .map(|k| {
+                if k > 0 {
+                    total += self.count_ones_in((k - 1) * block..k * block);
+                }
+                u32::try_from(total).expect("more than u32::MAX ones to count")
+            })
+            .collect()
+    }
+
+    /// The number of `1` bits before index `idx`, using an `index` built by
+    /// [`BitArray::prefix_counts`] with the same `block` size.
+    ///
+    /// Only the bits between the boundary before `idx` and `idx` itself are counted. The index must
+    /// have been built from the array as it is now; a stale index gives a wrong count.
+    /// Panics if `idx` is greater than the length of the array, or if `index` is too short for `block`.
+    pub fn rank1_with_index(&self, index: &[u32], block: usize, idx: usize) -> usize {
+        assert!(
+            idx <= self.len,
+            "rank index {} out of range for bit array of length {}",
+            idx,
+            self.len
+        );
+        let k = idx / block;
+        index[k] as usize + self.count_ones_in(k * block..idx)
+    }
+
+    /// The number of `0` bits before index `idx`.
+    ///
+    /// Panics if `idx` is greater than the length of the array.
+    pub fn rank0(&self, idx: usize) -> usize {
+        idx - self.rank1(idx)
+    }
+
+    /// The index of the `1` bit preceded by exactly `k` other `1` bits, or `None` if there are not
+    /// enough of them.
+    pub fn select1(&self, k: usize) -> Option<usize> {
+        self.select(k, 0)
+    }
+
+    /// The index of the `0` bit preceded by exactly `k` other `0` bits, or `None` if there are not
+    /// enough of them.
+    pub fn select0(&self, k: usize) -> Option<usize> {
+        self.select(k, u64::MAX)
+    }
+
+    /// The wanted bits of every block, in logical order: those that are one once XORed with `flip`.
+    fn logical_blocks(&self, flip: u64) -> impl Iterator<Item = u64> + '_ {
+        let last = self.blocks.len().wrapping_sub(1);
+        let mask = self.last_block_mask();
+        self.blocks.iter().enumerate().map(move |(i, &block)| {
+            let bits = u64::from_be(block) ^ flip;
+            if i == last {
+                bits & mask
+            } else {
+                bits
+            }
+        })
+    }
+
+    /// The index of the `k`-th bit (counting from zero) that is a one once XORed with `flip`.
+    fn select(&self, mut k: usize, flip: u64) -> Option<usize> {
+        for (i, mut bits) in self.logical_blocks(flip).enumerate() {
+            let ones = bits.count_ones() as usize;
+            if k < ones {
+                // Clear the first `k` wanted bits, leaving the one sought as the left-most.
+                for _ in 0..k {
+                    bits &= !(0x8000_0000_0000_0000 >> bits.leading_zeros());
+                }
+                return Some(i * 64 + bits.leading_zeros() as usize);
+            }
+            k -= ones;
+        }
+        None
+    }
+
+    /// A read-only view of the bits in `range`, without copying.
+    ///
+    /// Panics if the range is decreasing or extends past the end of the array.
+    pub fn slice(&self, range: Range<usize>) -> BitSlice<'_> {
+        BitSlice::new(self, range)
+    }
+
+    /// Iterates over every bit of the array, in index order.
+    pub fn iter(&self) -> iter::Take<SliceBitIter<'_>> {
+        self.as_bytes().iter_bits().take(self.len)
+    }
+
+    /// Iterates over the indices of the `1` bits, in ascending order.
+    ///
+    /// Bytes with no `1` bits are skipped whole.
+    pub fn iter_ones(&self) -> ArrayIndices<'_> {
+        ArrayIndices::new(self, 0)
+    }
+
+    /// Iterates over the indices of the `0` bits, in ascending order.
+    ///
+    /// Bytes with no `0` bits are skipped whole.
+    pub fn iter_zeros(&self) -> ArrayIndices<'_> {
+        ArrayIndices::new(self, u8::MAX)
+    }
+
+    /// Combines `other` into the array a block at a time.
+    ///
+    /// The array is first zero-extended to the length of `other` if that is longer, and a shorter
+    /// `other` is treated as zero-extended. `op` must map two zero blocks to zero, so padding stays clear.
+    fn combine_with<F: Fn(u64, u64) -> u64>(&mut self, other: &BitArray, op: F) {
+        if other.len > self.len {
+            self.resize_len(other.len);
+        }
+        for (i, block) in self.blocks.iter_mut().enumerate() {
+            *block = op(*block, other.blocks.get(i).copied().unwrap_or(0));
+        }
+        self.debug_check_padding();
+    }
+
+    /// Sets every bit that is `1` in `other`, growing the array to the longer of the two lengths.
+    pub fn union_with(&mut self, other: &BitArray) {
+        self.combine_with(other, |a, b| a | b);
+    }
+
+    /// Clears every bit that is `0` in `other`, growing the array to the longer of the two lengths.
+    ///
+    /// Bits past the end of the shorter array count as `0`, so they are cleared.
+    pub fn intersect_with(&mut self, other: &BitArray) {
+        self.combine_with(other, |a, b| a & b);
+    }
+
+    /// Clears every bit that is `1` in `other`, growing the array to the longer of the two lengths.
+    pub fn difference_with(&mut self, other: &BitArray) {
+        self.combine_with(other, |a, b| a & !b);
+    }
+
+    /// Toggles every bit that is `1` in `other`, growing the array to the longer of the two lengths.
+    pub fn symmetric_difference_with(&mut self, other: &BitArray) {
+        self.combine_with(other, |a, b| a ^ b);
+    }
+
+    /// The bits that are `1` in this array but not in `other`.
+    ///
+    /// The result has the longer of the two lengths, with the shorter array treated as zero-extended.
+    pub fn difference(&self, other: &BitArray) -> BitArray {
+        let mut res = self.clone();
+        res.difference_with(other);
+        res
+    }
+
+    /// The number of positions at which the bits of the two arrays differ.
+    ///
+    /// Returns an error if the arrays are not the same length.
+    pub fn hamming_distance(&self, other: &BitArray) -> Result<usize, BitError> {
+        if self.len != other.len {
+            return Err(BitError::LengthMismatch {
+                left: self.len,
+                right: other.len,
+            });
+        }
+        // The unused bits of both final blocks are zero, so they never differ.
+        Ok(self
+            .blocks
+            .iter()
+            .zip(&other.blocks)
+            .map(|(a, b)| (a ^ b).count_ones() as usize)
+            .sum())
+    }
+
+    /// A new array combining the corresponding 64-bit words of two equal-length arrays with `f`.
+    ///
+    /// Each word is in logical order, the bit with the lowest index as the most significant, and
+    /// the final word is padded on the right with zeros. Whatever `f` returns for the final word,
+    /// the bits past the end of the array are cleared. Returns an error if the arrays are not the
+    /// same length.
+    ///
+    /// ```
+    /// use bit_array_rs::BitArray;
+    ///
+    /// let a = BitArray::from_bytes(&[0b1100_1100]);
+    /// let b = BitArray::from_bytes(&[0b1010_1010]);
+    /// let and_not = a.zip_words(&b, |x, y| x & !y)?;
+    /// assert_eq!(and_not.as_bytes(), [0b0100_0100]);
+    /// # Ok::<(), bit_array_rs::BitError>(())
+    /// ```
+    pub fn zip_words<F: FnMut(u64, u64) -> u64>(
+        &self,
+        other: &BitArray,
+        mut f: F,
+    ) -> Result<BitArray, BitError> {
+        if self.len != other.len {
+            return Err(BitError::LengthMismatch {
+                left: self.len,
+                right: other.len,
+            });
+        }
+        let mut res = BitArray::new(self.len);
+        for (block, (&a, &b)) in res
+            .blocks
+            .iter_mut()
+            .zip(self.blocks.iter().zip(&other.blocks))
+        {
+            *block = f(u64::from_be(a), u64::from_be(b)).to_be();
+        }
+        res.clear_padding();
+        Ok(res)
+    }
+
+    /// The reflected binary Gray code of the array, read as an unsigned integer with the first bit
+    /// most significant: each bit is XORed with the bit before it.
+    pub fn to_gray(&self) -> BitArray {
+        let mut res = self.clone();
+        let mut carry = 0;
+        for block in res.blocks.iter_mut() {
+            let value = u64::from_be(*block);
+            *block = (value ^ ((value >> 1) | (carry << 63))).to_be();
+            carry = value & 1;
+        }
+        // The last bit was shifted into the padding.
+        res.clear_padding();
+        res
+    }
+
+    /// Decodes a Gray code back to the array it encodes, the inverse of [`BitArray::to_gray`].
+    ///
+    /// Each bit of the result is the XOR of every bit of the code up to and including it.
+    pub fn from_gray(gray: &BitArray) -> BitArray {
+        let mut res = gray.clone();
+        let mut carry = 0;
+        for block in res.blocks.iter_mut() {
+            let mut value = u64::from_be(*block);
+            value ^= value >> 1;
+            value ^= value >> 2;
+            value ^= value >> 4;
+            value ^= value >> 8;
+            value ^= value >> 16;
+            value ^= value >> 32;
+            // Continue the running XOR from the last bit of the previous block.
+            value ^= 0u64.wrapping_sub(carry);
+            *block = value.to_be();
+            carry = value & 1;
+        }
+        res.clear_padding();
+        res
+    }
+
+    /// The running XOR of the array: bit `i` of the result is the XOR of bits `0..=i`.
+    ///
+    /// This is the same transform as [`BitArray::from_gray`], done a block at a time with
+    /// parallel-prefix shifts; [`BitArray::undo_prefix_xor`] is its inverse.
+    pub fn prefix_xor(&self) -> BitArray {
+        BitArray::from_gray(self)
+    }
+
+    /// The inverse of [`BitArray::prefix_xor`]: bit `i` of the result is the XOR of bits `i - 1`
+    /// and `i`, and the first bit is kept.
+    ///
+    /// This is the same transform as [`BitArray::to_gray`].
+    pub fn undo_prefix_xor(&self) -> BitArray {
+        self.to_gray()
+    }
+
+    /// Interleaves the bits of two equal-length arrays, a byte at a time.
+    ///
+    /// Bit `i` of `self` becomes bit `2 * i` of the result and bit `i` of `other` becomes bit
+    /// `2 * i + 1`, so the result starts with the first bit of `self`. Returns an error if the arrays
+    /// are not the same length.
+    pub fn interleave(&self, other: &BitArray) -> Result<BitArray, BitError> {
+        if self.len != other.len {
+            return Err(BitError::LengthMismatch {
+                left: self.len,
+                right: other.len,
+            });
+        }
+        let mut res = BitArray::new(self.len * 2);
+        for start in (0..self.len).step_by(8) {
+            let end = (start + 8).min(self.len);
+            let pad = 8 - (end - start);
+            let a = Byte((self.get_bits(start..end) << pad) as u8);
+            let b = Byte((other.get_bits(start..end) << pad) as u8);
+            res.set_bits(2 * start..2 * end, u64::from(a.interleave(b) >> (2 * pad)));
+        }
+        Ok(res)
+    }
+
+    /// Carry-less multiplication, treating both arrays as polynomials over GF(2).
+    ///
+    /// Bit `i` is the coefficient of `x^i`, so index 0 is the constant term; note this is the
+    /// reverse of the integer reading used by [`BitArray::overflowing_add`]. The product has
+    /// `self.len() + other.len() - 1` bits, or none if either array is empty. For each `1` in
+    /// `self`, `other` is XORed into the product up to 64 bits at a time.
+    pub fn clmul(&self, other: &BitArray) -> BitArray {
+        if self.len == 0 || other.len == 0 {
+            return BitArray::new(0);
+        }
+        let mut res = BitArray::new(self.len + other.len - 1);
+        for shift in self.iter_ones() {
+            for start in (0..other.len).step_by(64) {
+                let end = (start + 64).min(other.len);
+                let range = shift + start..shift + end;
+                let bits = res.get_bits(range.clone()) ^ other.get_bits(start..end);
+                res.set_bits(range, bits);
+            }
+        }
+        res
+    }
+
+    /// Long division of polynomials over GF(2), as for [`BitArray::clmul`], returning the quotient
+    /// and remainder.
+    ///
+    /// Index 0 is the constant term. If the divisor has degree `d` (its last `1` is at index `d`),
+    /// the quotient has `self.len() - d` bits, or none if the array is shorter than that, and the
+    /// remainder has `divisor.len() - 1` bits, so both hold any degree the result can have. Zeros
+    /// past the degree of either input are allowed. Each step XORs the divisor into the running
+    /// remainder up to 64 bits at a time. Returns an error if the divisor has no `1` bits.
+    ///
+    /// ```
+    /// use bit_array_rs::BitArray;
+    ///
+    /// // x^3 + 1 = (x + 1)(x^2 + x + 1), with no remainder.
+    /// let a: BitArray = [true, false, false, true].iter().copied().collect();
+    /// let d: BitArray = [true, true].iter().copied().collect();
+    /// let (q, r) = a.polynomial_div_rem(&d)?;
+    /// assert_eq!(q, [true, true, true].iter().copied().collect::<BitArray>());
+    /// assert!(r.none());
+    /// # Ok::<(), bit_array_rs::BitError>(())
+    /// ```
+    pub fn polynomial_div_rem(&self, divisor: &BitArray) -> Result<(BitArray, BitArray), BitError> {
+        let degree = divisor.last_one().ok_or(BitError::DivideByZero)?;
+        let mut rem = self.clone();
+        let mut quotient = BitArray::new(self.len.saturating_sub(degree));
+        while let Some(top) = rem.last_one().filter(|&top| top >= degree) {
+            let shift = top - degree;
+            quotient.set(shift, true);
+            for start in (0..=degree).step_by(64) {
+                let end = (start + 64).min(degree + 1);
+                let range = shift + start..shift + end;
+                let bits = rem.get_bits(range.clone()) ^ divisor.get_bits(start..end);
+                rem.set_bits(range, bits);
+            }
+        }
+        let mut remainder = BitArray::new(divisor.len - 1);
+        remainder.copy_bits_from(0, &rem, 0..degree.min(self.len))?;
+        Ok((quotient, remainder))
+    }
+
+    /// Adds `other` to the array, both read as unsigned integers with the first bit most significant.
+    ///
+    /// Returns the sum, truncated to the length of the arrays, and whether a carry came out of the
+    /// first bit. Returns an error if the arrays are not the same length.
+    pub fn overflowing_add(&self, other: &BitArray) -> Result<(BitArray, bool), BitError> {
+        self.overflowing_op(other, false)
+    }
+
+    /// Subtracts `other` from the array, both read as unsigned integers with the first bit most significant.
+    ///
+    /// Returns the difference, wrapped to the length of the arrays, and whether a borrow was needed
+    /// past the first bit. Returns an error if the arrays are not the same length.
+    pub fn overflowing_sub(&self, other: &BitArray) -> Result<(BitArray, bool), BitError> {
+        self.overflowing_op(other, true)
+    }
+
+    /// Adds one to the array, read as an unsigned integer, returning true if it wrapped around to zero.
+    pub fn increment(&mut self) -> bool {
+        // The lowest bit sits just above the padding of the final block.
+        let mut carry = match self.len % 64 {
+            0 => 1,
+            n => 1 << (64 - n),
+        };
+        for block in self.blocks.iter_mut().rev() {
+            let (sum, overflow) = u64::from_be(*block).overflowing_add(carry);
+            *block = sum.to_be();
+            if !overflow {
+                self.debug_check_padding();
+                return false;
+            }
+            carry = 1;
+        }
+        true
+    }
+
+    /// Adds or subtracts the blocks of two equal-length arrays, from the last block to the first.
+    ///
+    /// Both arrays are shifted left by the same padding within their final block, so the padding
+    /// of the result stays zero and the carry out of the first block is the carry out of the array.
+    fn overflowing_op(
+        &self,
+        other: &BitArray,
+        subtract: bool,
+    ) -> Result<(BitArray, bool), BitError> {
+        if self.len != other.len {
+            return Err(BitError::LengthMismatch {
+                left: self.len,
+                right: other.len,
+            });
+        }
+        let mut res = self.clone();
+        let mut carry = false;
+        for (block, &rhs) in res.blocks.iter_mut().zip(&other.blocks).rev() {
+            let (lhs, rhs) = (u64::from_be(*block), u64::from_be(rhs));
+            let (value, first, second) = if subtract {
+                let (diff, borrow) = lhs.overflowing_sub(rhs);
+                let (diff, borrow_in) = diff.overflowing_sub(u64::from(carry));
+                (diff, borrow, borrow_in)
+            } else {
+                let (sum, carry_out) = lhs.overflowing_add(rhs);
+                let (sum, carry_in) = sum.overflowing_add(u64::from(carry));
+                (sum, carry_out, carry_in)
+            };
+            *block = value.to_be();
+            carry = first || second;
+        }
+        res.debug_check_padding();
+        Ok((res, carry))
+    }
+
+    /// Divides the array, read as an unsigned integer with the first bit most significant, by
+    /// `divisor`, returning the quotient at the same length and the remainder.
+    ///
+    /// The division runs a 64-bit block at a time, from the most significant end. Returns an error
+    /// if `divisor` is zero.
+    pub fn div_rem_u32(&self, divisor: u32) -> Result<(BitArray, u32), BitError> {
+        if divisor == 0 {
+            return Err(BitError::DivideByZero);
+        }
+        let divisor = u128::from(divisor);
+        let mut quotient = BitArray::new(self.len);
+        let mut rem = 0u128;
+        let last = self.blocks.len().wrapping_sub(1);
+        for (i, block) in quotient.blocks.iter_mut().enumerate() {
+            let value = u64::from_be(self.blocks[i]);
+            // The final block holds its bits at the top, above the padding.
+            let (width, pad) = match (i == last, self.len % 64) {
+                (true, n) if n != 0 => (n, 64 - n),
+                _ => (64, 0),
+            };
+            let dividend = (rem << width) | u128::from(value >> pad);
+            rem = dividend % divisor;
+            *block = (((dividend / divisor) as u64) << pad).to_be();
+        }
+        quotient.debug_check_padding();
+        Ok((quotient, rem as u32))
+    }
+
+    /// The array, read as an unsigned integer with the first bit most significant, in decimal.
+    ///
+    /// The digits come from repeated division by 10^19; an empty array gives `"0"`.
+    pub fn to_decimal_string(&self) -> String {
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+        let mut chunks = Vec::new();
+        let mut value = self.clone();
+        while value.any() {
+            let (quotient, rem) = value.div_rem_u64(CHUNK);
+            chunks.push(rem);
+            value = quotient;
+        }
+        let mut digits = chunks.pop().unwrap_or(0).to_string();
+        for chunk in chunks.iter().rev() {
+            write!(digits, "{:019}", chunk).expect("writing to a string cannot fail");
+        }
+        digits
+    }
+
+    /// As for [`BitArray::div_rem_u32`], by a non-zero 64-bit divisor.
+    fn div_rem_u64(&self, divisor: u64) -> (BitArray, u64) {
+        let divisor = u128::from(divisor);
+        let mut quotient = BitArray::new(self.len);
+        let mut rem = 0u128;
+        for start in (0..self.len).step_by(32) {
+            let end = (start + 32).min(self.len);
+            let dividend = (rem << (end - start)) | u128::from(self.get_bits(start..end));
+            rem = dividend % divisor;
+            quotient.set_bits(start..end, (dividend / divisor) as u64);
+        }
+        (quotient, rem as u64)
+    }
+
+    /// Panics if `range` is decreasing, extends past the end of the array, or spans more than 64 bits.
+    fn check_field(&self, range: &Range<usize>) {
+        assert!(
+            range.start <= range.end && range.end <= self.len,
+            "range {}..{} out of range for bit array of length {}",
+            range.start,
+            range.end,
+            self.len
+        );
+        assert!(
+            range.end - range.start <= 64,
+            "range {}..{} is wider than 64 bits",
+            range.start,
+            range.end
+        );
+    }
+
+    /// The bytes spanned by the non-empty `range`, as an integer, with the offset of the end of
+    /// the range from its least significant bit.
+    fn field_window(&self, range: &Range<usize>) -> (u128, usize) {
+        let (first, last) = (range.start / 8, (range.end - 1) / 8);
+        let window = self.bytes()[first..=last]
+            .iter()
+            .fold(0, |acc, byte| (acc << 8) | u128::from(**byte));
+        (window, (last + 1) * 8 - range.end)
+    }
+
+    /// The bits in `range` as an integer, whose most significant bit is the first bit of the range.
+    ///
+    /// Panics if `range` is decreasing, extends past the end of the array, or spans more than 64 bits.
+    pub fn get_bits(&self, range: Range<usize>) -> u64 {
+        self.check_field(&range);
+        if range.is_empty() {
+            return 0;
+        }
+        let width = range.end - range.start;
+        let (window, shift) = self.field_window(&range);
+        ((window >> shift) & (u128::MAX >> (128 - width))) as u64
+    }
+
+    /// Overwrites the bits in `range` with the bits of `value`, the most significant bit of `value`
+    /// going to the first bit of the range.
+    ///
+    /// Panics if `range` is decreasing, extends past the end of the array, or spans more than 64 bits,
+    /// or if `value` does not fit in the width of the range.
+    pub fn set_bits(&mut self, range: Range<usize>, value: u64) {
+        self.check_field(&range);
+        let width = range.end - range.start;
+        assert!(
+            u128::from(value) >> width == 0,
+            "value {:#x} does not fit in {} bits",
+            value,
+            width
+        );
+        if range.is_empty() {
+            return;
+        }
+        let (window, shift) = self.field_window(&range);
+        let mask = (u128::MAX >> (128 - width)) << shift;
+        let window = (window & !mask) | (u128::from(value) << shift);
+        let first = range.start / 8;
+        let last = (range.end - 1) / 8;
+        for (i, byte) in self.bytes_mut()[first..=last].iter_mut().enumerate() {
+            *byte = Byte((window >> ((last - first - i) * 8)) as u8);
+        }
+        self.debug_check_padding();
+    }
+
+    /// Checks that `range` is increasing and lies within the array.
+    fn check_range(&self, range: &Range<usize>) -> Result<(), BitError> {
+        if range.start > range.end {
+            return Err(BitError::DecreasingRange {
+                start: range.start,
+                end: range.end,
+            });
+        }
+        if range.end > self.len {
+            return Err(BitError::OutOfBounds {
+                index: range.end,
+                len: self.len,
+            });
+        }
+        Ok(())
+    }
+
+    /// Sets every bit in `range` to `val`, writing whole bytes where the range covers them.
+    ///
+    /// Returns an error if `range` is decreasing or extends past the end of the array.
+    pub fn fill(&mut self, range: Range<usize>, val: bool) -> Result<(), BitError> {
+        self.check_range(&range)?;
+        if range.is_empty() {
+            return Ok(());
+        }
+        let (first, last) = (range.start / 8, (range.end - 1) / 8);
+        let apply = |byte: &mut Byte, mask: u8| {
+            if val {
+                *byte |= Byte(mask);
+            } else {
+                *byte &= !Byte(mask);
+            }
+        };
+        let bytes = self.bytes_mut();
+        if first == last {
+            let mask = Byte::range_mask(&(range.start % 8..range.end - first * 8));
+            apply(&mut bytes[first], mask);
+        } else {
+            apply(&mut bytes[first], Byte::range_mask(&(range.start % 8..8)));
+            bytes[first + 1..last].fill(Byte(if val { u8::MAX } else { 0 }));
+            apply(
+                &mut bytes[last],
+                Byte::range_mask(&(0..range.end - last * 8)),
+            );
+        }
+        self.debug_check_padding();
+        Ok(())
+    }
+
+    /// Sets every bit in `range` to `1`.
+    ///
+    /// The same as [`BitArray::try_set_range`], returning its errors.
+    pub fn set_range(&mut self, range: Range<usize>) -> Result<(), BitError> {
+        self.try_set_range(range)
+    }
+
+    /// Sets every bit in `range` to `1`.
+    ///
+    /// Returns a [`BitError::DecreasingRange`] if `range` is decreasing, or a
+    /// [`BitError::OutOfBounds`] holding its end if it extends past the end of the array. Nothing
+    /// is changed on error.
+    pub fn try_set_range(&mut self, range: Range<usize>) -> Result<(), BitError> {
+        self.fill(range, true)
+    }
+
+    /// Sets every bit in `range` to `0`.
+    ///
+    /// Returns an error if `range` is decreasing or extends past the end of the array.
+    pub fn clear_range(&mut self, range: Range<usize>) -> Result<(), BitError> {
+        self.fill(range, false)
+    }
+
+    /// Combines the `len` bits starting at `offset` with the first `len` bits of `mask` using `op`,
+    /// leaving every other bit alone.
+    fn apply_mask(
+        &mut self,
+        offset: usize,
+        mask: &[u8],
+        len: usize,
+        op: impl Fn(u8, u8) -> u8,
+    ) -> Result<(), BitError> {
+        if len > mask.len() * 8 {
+            return Err(BitError::InvalidLength {
+                len,
+                max: mask.len() * 8,
+            });
+        }
+        self.check_range(&(offset..offset.saturating_add(len)))?;
+        let shift = offset % 8;
+        let bytes = self.bytes_mut();
+        for (i, &m) in mask.iter().enumerate().take(len.div_ceil(8)) {
+            let width = (len - i * 8).min(8);
+            // Line the mask byte up with the two storage bytes it may straddle.
+            let value = u16::from(m) << (8 - shift);
+            let affected = u16::from(Byte::range_mask(&(0..width))) << (8 - shift);
+            let first = offset / 8 + i;
+            for (byte, value, affected) in [
+                (first, (value >> 8) as u8, (affected >> 8) as u8),
+                (first + 1, value as u8, affected as u8),
+            ] {
+                if affected != 0 {
+                    let old = bytes[byte].0;
+                    bytes[byte] = Byte((old & !affected) | (op(old, value) & affected));
+                }
+            }
+        }
+        self.debug_check_padding();
+        Ok(())
+    }
+
+    /// ORs `mask` into the 8 bits starting at index `offset`, which may straddle two bytes.
+    ///
+    /// Returns an error if the 8 bits extend past the end of the array.
+    pub fn mask_or(&mut self, offset: usize, mask: Byte) -> Result<(), BitError> {
+        self.mask_or_bytes(offset, &[mask.0], 8)
+    }
+
+    /// ANDs `mask` into the 8 bits starting at index `offset`, which may straddle two bytes.
+    ///
+    /// Returns an error if the 8 bits extend past the end of the array.
+    pub fn mask_and(&mut self, offset: usize, mask: Byte) -> Result<(), BitError> {
+        self.mask_and_bytes(offset, &[mask.0], 8)
+    }
+
+    /// XORs `mask` into the 8 bits starting at index `offset`, which may straddle two bytes.
+    ///
+    /// Returns an error if the 8 bits extend past the end of the array.
+    pub fn mask_xor(&mut self, offset: usize, mask: Byte) -> Result<(), BitError> {
+        self.mask_xor_bytes(offset, &[mask.0], 8)
+    }
+
+    /// ORs the first `len` bits of `mask` into the `len` bits starting at index `offset`.
+    ///
+    /// Returns an error if `mask` holds fewer than `len` bits or the bits extend past the end of
+    /// the array.
+    pub fn mask_or_bytes(
+        &mut self,
+        offset: usize,
+        mask: &[u8],
+        len: usize,
+    ) -> Result<(), BitError> {
+        self.apply_mask(offset, mask, len, |old, m| old | m)
+    }
+
+    /// ANDs the first `len` bits of `mask` into the `len` bits starting at index `offset`.
+    ///
+    /// Returns an error if `mask` holds fewer than `len` bits or the bits extend past the end of
+    /// the array.
+    pub fn mask_and_bytes(
+        &mut self,
+        offset: usize,
+        mask: &[u8],
+        len: usize,
+    ) -> Result<(), BitError> {
+        self.apply_mask(offset, mask, len, |old, m| old & m)
+    }
+
+    /// XORs the first `len` bits of `mask` into the `len` bits starting at index `offset`.
+    ///
+    /// Returns an error if `mask` holds fewer than `len` bits or the bits extend past the end of
+    /// the array.
+    pub fn mask_xor_bytes(
+        &mut self,
+        offset: usize,
+        mask: &[u8],
+        len: usize,
+    ) -> Result<(), BitError> {
+        self.apply_mask(offset, mask, len, |old, m| old ^ m)
+    }
+
+    /// True if `pattern` occurs in the array starting at index `pos`, which must leave room for it.
+    fn matches_at(&self, pattern: &BitArray, pos: usize) -> bool {
+        // Compare up to 64 bits at a time, whatever the alignment of `pos`.
+        (0..pattern.len).step_by(64).all(|start| {
+            let end = (start + 64).min(pattern.len);
+            self.get_bits(pos + start..pos + end) == pattern.get_bits(start..end)
+        })
+    }
+
+    /// The index of the first occurrence of `pattern`, at any bit offset, or `None` if there is none.
+    ///
+    /// An empty pattern matches at index 0.
+    pub fn find(&self, pattern: &BitArray) -> Option<usize> {
+        self.find_from(pattern, 0)
+    }
+
+    /// The index of the first occurrence of `pattern` starting at or after `start`, or `None` if
+    /// there is none.
+    pub fn find_from(&self, pattern: &BitArray, start: usize) -> Option<usize> {
+        let last = self.len.checked_sub(pattern.len)?;
+        (start..=last).find(|&pos| self.matches_at(pattern, pos))
+    }
+
+    /// Iterates over the index of every occurrence of `pattern`, including overlapping ones.
+    pub fn matches<'a>(&'a self, pattern: &'a BitArray) -> Matches<'a> {
+        Matches {
+            haystack: self,
+            pattern,
+            pos: 0,
+        }
+    }
+
+    /// True if the arrays hold the same bits once the shorter is extended with zeros on the right,
+    /// so `101` matches `10100`.
+    ///
+    /// Arrays of the same length match only if they are equal. Unlike `==`, this ignores the
+    /// lengths; the bits are compared up to 64 at a time.
+    pub fn eq_ignore_trailing_zeros(&self, other: &BitArray) -> bool {
+        let (short, long) = if self.len <= other.len {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        long.matches_at(short, 0) && long.next_equal(short.len, true).is_none()
+    }
+
+    /// True if the arrays hold the same bits once the shorter is extended with zeros on the left,
+    /// so `101` matches `00101`, as when comparing them as unsigned integers.
+    ///
+    /// Arrays of the same length match only if they are equal. Unlike `==`, this ignores the
+    /// lengths; the bits are compared up to 64 at a time.
+    pub fn eq_ignore_leading_zeros(&self, other: &BitArray) -> bool {
+        let (short, long) = if self.len <= other.len {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let extra = long.len - short.len;
+        long.next_equal(0, true).is_none_or(|idx| idx >= extra) && long.matches_at(short, extra)
+    }
+
+    /// Iterates over the pieces of the array between non-overlapping occurrences of `delim`, as
+    /// with [`str::split`].
+    ///
+    /// Occurrences are found left to right, each search starting after the end of the last match.
+    /// A delimiter at the start or end of the array, or two delimiters next to each other, give
+    /// empty pieces, and an array without the delimiter gives a single piece holding all of it.
+    /// Returns an error if `delim` is empty.
+    ///
+    /// ```
+    /// use bit_array_rs::BitArray;
+    ///
+    /// let arr = BitArray::from_bytes(&[0b0010_1101]);
+    /// let one = BitArray::from_fn(1, |_| true);
+    /// let pieces: Vec<_> = arr.split(&one).unwrap().map(|piece| piece.len()).collect();
+    /// assert_eq!(pieces, [2, 1, 0, 1, 0]);
+    /// ```
+    pub fn split<'a>(&'a self, delim: &'a BitArray) -> Result<Split<'a>, BitError> {
+        self.splitn(usize::MAX, delim)
+    }
+
+    /// Iterates over at most `n` pieces of the array between occurrences of `delim`, as with
+    /// [`str::splitn`].
+    ///
+    /// The last piece holds the rest of the array, delimiters and all. Returns an error if `delim`
+    /// is empty.
+    pub fn splitn<'a>(&'a self, n: usize, delim: &'a BitArray) -> Result<Split<'a>, BitError> {
+        if delim.is_empty() {
+            return Err(BitError::EmptyPattern);
+        }
+        Ok(Split {
+            haystack: self,
+            delim,
+            pos: Some(0),
+            remaining: n,
+        })
+    }
+
+    /// The index of the first bit equal to `val` at or after `from`, or `None` if there is none.
+    ///
+    /// Whole blocks holding none of the wanted bits are skipped.
+    fn next_equal(&self, from: usize, val: bool) -> Option<usize> {
+        let flip = if val { 0 } else { u64::MAX };
+        let first_mask = u64::MAX >> (from % 64);
+        self.logical_blocks(flip)
+            .enumerate()
+            .skip(from / 64)
+            .find_map(|(i, bits)| {
+                let bits = if i == from / 64 {
+                    bits & first_mask
+                } else {
+                    bits
+                };
+                (bits != 0).then(|| i * 64 + bits.leading_zeros() as usize)
+            })
+    }
+
+    /// The longest run of bits that appears contiguously in both arrays, as its offset in `self`,
+    /// its offset in `other` and its length, or `None` if the arrays share no bit at all.
+    ///
+    /// Ties go to the earliest offset in `self`, then the earliest offset in `other`. Each pairing
+    /// of offsets is a diagonal, compared 64 bits at a time by XOR, so the search takes time
+    /// proportional to `self.len() * other.len() / 64`.
+    pub fn longest_common_substring(&self, other: &BitArray) -> Option<(usize, usize, usize)> {
+        let mut best = (0, 0, 0);
+        let mut consider = |i: usize, j: usize, len: usize| {
+            let (bi, bj, blen) = best;
+            if len > blen || (len == blen && len > 0 && (i, j) < (bi, bj)) {
+                best = (i, j, len);
+            }
+        };
+        let starts = (0..other.len)
+            .map(|j| (0, j))
+            .chain((1..self.len).map(|i| (i, 0)));
+        for (i0, j0) in starts {
+            let overlap = (self.len - i0).min(other.len - j0);
+            // The start, relative to the diagonal, and length of the current run of equal bits.
+            let (mut run_start, mut run_len) = (0, 0);
+            for k in (0..overlap).step_by(64) {
+                let width = (overlap - k).min(64);
+                let diff =
+                    self.get_bits(i0 + k..i0 + k + width) ^ other.get_bits(j0 + k..j0 + k + width);
+                // Line the equal bits up from the top of the word, the first bit highest.
+                let mut equal = !diff << (64 - width);
+                let mut pos = 0;
+                while pos < width {
+                    let step = if equal >> 63 == 1 {
+                        let ones = (equal.leading_ones() as usize).min(width - pos);
+                        run_len += ones;
+                        ones
+                    } else {
+                        let zeros = (equal.leading_zeros() as usize).min(width - pos);
+                        consider(i0 + run_start, j0 + run_start, run_len);
+                        run_start = k + pos + zeros;
+                        run_len = 0;
+                        zeros
+                    };
+                    pos += step;
+                    equal = equal.checked_shl(step as u32).unwrap_or(0);
+                }
+            }
+            consider(i0 + run_start, j0 + run_start, run_len);
+        }
+        Some(best).filter(|&(_, _, len)| len > 0)
+    }
+
+    /// The start of the first run of at least `n` consecutive `0` bits, or `None` if there is none.
+    ///
+    /// A run of zero bits is found at index 0, and `None` is returned if `n` exceeds the length.
+    /// Whole blocks of `1` bits are skipped, and runs may span blocks.
+    pub fn find_zero_run(&self, n: usize) -> Option<usize> {
+        self.find_run(0, n, false)
+    }
+
+    /// The start of the first run of at least `n` consecutive `0` bits starting at or after `from`,
+    /// or `None` if there is none, as for next-fit allocation.
+    ///
+    /// A run of zero bits is found at `from` if it is at most the length.
+    pub fn find_zero_run_at_or_after(&self, from: usize, n: usize) -> Option<usize> {
+        self.find_run(from, n, false)
+    }
+
+    /// The start of the first run of at least `n` consecutive `1` bits, or `None` if there is none.
+    ///
+    /// A run of zero bits is found at index 0, and `None` is returned if `n` exceeds the length.
+    pub fn find_one_run(&self, n: usize) -> Option<usize> {
+        self.find_run(0, n, true)
+    }
+
+    /// The start of the first run of at least `n` bits equal to `val` starting at or after `from`.
+    fn find_run(&self, from: usize, n: usize, val: bool) -> Option<usize> {
+        if n == 0 {
+            return Some(from).filter(|&from| from <= self.len);
+        }
+        let mut pos = from;
+        loop {
+            let start = self.next_equal(pos, val)?;
+            if start + n > self.len {
+                return None;
+            }
+            let end = self.next_equal(start, !val).unwrap_or(self.len);
+            if end - start >= n {
+                return Some(start);
+            }
+            pos = end;
+        }
+    }
+
+    /// Iterates over the maximal runs of equal bits, in order, as each run's value and length.
+    ///
+    /// Runs continue across byte boundaries.
+    pub fn runs(&self) -> Runs<'_> {
+        Runs { arr: self, pos: 0 }
+    }
+
+    /// Iterates over the bits in groups of `width`, each given as its value and the number of bits it holds.
+    ///
+    /// The first bit of a group is its most significant. The final group may hold fewer than `width`
+    /// bits, in which case its value is padded on the right with zeros.
+    ///
+    /// Panics if `width` is not between 1 and 64.
+    pub fn chunks(&self, width: usize) -> Chunks<'_> {
+        assert!(
+            (1..=64).contains(&width),
+            "chunk width {} is not between 1 and 64",
+            width
+        );
+        Chunks {
+            arr: self,
+            width,
+            pos: 0,
+        }
+    }
+
+    /// Iterates over the bits from index `bit_offset` on, eight at a time, as bytes whose most
+    /// significant bit is the first of the eight.
+    ///
+    /// Only whole groups of eight are yielded; any bits left over at the end are available from
+    /// [`BytesAt::remainder`]. Each byte is formed from the two stored bytes it straddles.
+    ///
+    /// Panics if `bit_offset` is greater than the length of the array.
+    pub fn iter_bytes_at(&self, bit_offset: usize) -> BytesAt<'_> {
+        assert!(
+            bit_offset <= self.len,
+            "offset {} out of range for bit array of length {}",
+            bit_offset,
+            self.len
+        );
+        BytesAt {
+            bytes: self.as_bytes(),
+            pos: bit_offset,
+            end: self.len,
+        }
+    }
+
+    /// Iterates over every window of `width` consecutive bits, each given as its value.
+    ///
+    /// Window `k` holds bits `k..k + width`, its first bit as the most significant, so there are
+    /// `len - width + 1` windows, or none if `width` exceeds the length. Each window is formed from
+    /// the one before by shifting in a single bit.
+    ///
+    /// Panics if `width` is not between 1 and 64.
+    pub fn windows(&self, width: usize) -> Windows<'_> {
+        assert!(
+            (1..=64).contains(&width),
+            "window width {} is not between 1 and 64",
+            width
+        );
+        Windows {
+            arr: self,
+            width,
+            pos: 0,
+            value: 0,
+        }
+    }
+
+    /// Creates an array from runs of equal bits, each given as its value and length.
+    ///
+    /// This is the inverse of [`BitArray::runs`]; runs of length zero are allowed.
+    pub fn from_runs<I: IntoIterator<Item = (Bit, usize)>>(runs: I) -> Self {
+        let mut arr = BitArray::new(0);
+        for (bit, len) in runs {
+            let start = arr.len;
+            arr.resize_len(start + len);
+            arr.fill(start..arr.len, *bit)
+                .expect("the range was just added");
+        }
+        arr
+    }
+
+    /// A new array holding the bits of `self` followed directly by the bits of `other`.
+    pub fn concat(&self, other: &BitArray) -> BitArray {
+        let mut res = self.clone();
+        res.append_bits(other);
+        res
+    }
+
+    /// A new array holding `n` copies of the array, one after another.
+    ///
+    /// `repeat(0)` gives an empty array. Panics if the total length overflows a `usize`.
+    pub fn repeat(&self, n: usize) -> BitArray {
+        let len = self
+            .len
+            .checked_mul(n)
+            .expect("repeated length overflows usize");
+        Self::tiled(self, len)
+    }
+
+    /// Creates an array of `total_len` bits holding copies of `pattern` one after another, the last
+    /// cut short if needed.
+    ///
+    /// After the first copy, the bits filled so far are copied onto the end in one go, doubling
+    /// them each time, so only about `log2(total_len / pattern.len())` copies are made. Panics if
+    /// `pattern` is empty and `total_len` is not zero.
+    ///
+    /// ```
+    /// use bit_array_rs::BitArray;
+    ///
+    /// let pattern = BitArray::from_fn(3, |i| i == 0);
+    /// assert_eq!(BitArray::tiled(&pattern, 8).as_bytes(), [0b1001_0010]);
+    /// assert_eq!(pattern.repeat(2).len(), 6);
+    /// ```
+    pub fn tiled(pattern: &BitArray, total_len: usize) -> BitArray {
+        let mut res = BitArray::new(total_len);
+        if total_len == 0 {
+            return res;
+        }
+        assert!(!pattern.is_empty(), "cannot tile an empty pattern");
+        let mut filled = pattern.len.min(total_len);
+        res.copy_bits_from(0, pattern, 0..filled)
+            .expect("the first copy fits");
+        while filled < total_len {
+            let n = filled.min(total_len - filled);
+            res.copy_within(0..n, filled)
+                .expect("the copy fits after the bits filled so far");
+            filled += n;
+        }
+        res
+    }
+
+    /// Creates an array holding the bits of each of `arrays` in turn, with no gaps between them.
+    pub fn concat_all<'a, I: IntoIterator<Item = &'a BitArray>>(arrays: I) -> Self {
+        let mut res = BitArray::new(0);
+        for arr in arrays {
+            res.append_bits(arr);
+        }
+        res
+    }
+
+    /// Copies the bits of `other` onto the end of the array.
+    fn append_bits(&mut self, other: &BitArray) {
+        self.append_range(other, 0..other.len);
+    }
+
+    /// Copies the bits of `src` in `range` onto the end of the array, up to 64 bits at a time.
+    fn append_range(&mut self, src: &BitArray, range: Range<usize>) {
+        let mut dest = self.len;
+        self.resize_len(self.len + range.end - range.start);
+        for from in range.clone().step_by(64) {
+            let to = (from + 64).min(range.end);
+            self.set_bits(dest..dest + to - from, src.get_bits(from..to));
+            dest += to - from;
+        }
+    }
+
+    /// Rotates the bits of the array `n` places to the left, so the bit at index `n` becomes the first.
+    ///
+    /// `n` is taken modulo the length of the array.
+    pub fn rotate_left(&mut self, n: usize) {
+        if self.len == 0 || n.is_multiple_of(self.len) {
+            return;
+        }
+        let n = n % self.len;
+        let mut res = BitArray {
+            blocks: Vec::with_capacity(self.blocks.len()),
+            len: 0,
+        };
+        res.append_range(self, n..self.len);
+        res.append_range(self, 0..n);
+        *self = res;
+    }
+
+    /// Rotates the bits of the array `n` places to the right, so the last `n` bits move to the front.
+    ///
+    /// `n` is taken modulo the length of the array.
+    pub fn rotate_right(&mut self, n: usize) {
+        if self.len > 0 {
+            self.rotate_left(self.len - n % self.len);
+        }
+    }
+
+    /// Shifts the bits of the array `n` places to the left, so the bit at index `n` becomes the
+    /// first, filling the end with `0` bits.
+    ///
+    /// Bits shifted past the start are lost, and shifting by the length or more clears the array.
+    pub fn shift_left(&mut self, n: usize) {
+        if n >= self.len {
+            self.clear_all();
+            return;
+        }
+        let (skip, shift) = (n / 8, n % 8);
+        let bytes = self.bytes_mut();
+        let at = |bytes: &[Byte], idx: usize| bytes.get(idx).map_or(0, |byte| byte.0);
+        // Each byte only reads bytes at or after it, so ascending order never reads a written byte.
+        for idx in 0..bytes.len() {
+            let (hi, lo) = (at(bytes, idx + skip), at(bytes, idx + skip + 1));
+            bytes[idx] = Byte(if shift == 0 {
+                hi
+            } else {
+                (hi << shift) | (lo >> (8 - shift))
+            });
+        }
+        // The bits shifted in came from the padding, so it is still clear.
+        self.debug_check_padding();
+    }
+
+    /// Shifts the bits of the array `n` places to the right, so the first bit moves to index `n`,
+    /// filling the start with `0` bits.
+    ///
+    /// Bits shifted past the end are lost, and shifting by the length or more clears the array.
+    pub fn shift_right(&mut self, n: usize) {
+        if n >= self.len {
+            self.clear_all();
+            return;
+        }
+        let (skip, shift) = (n / 8, n % 8);
+        let bytes = self.bytes_mut();
+        // Each byte only reads bytes at or before it, so descending order never reads a written byte.
+        for idx in (0..bytes.len()).rev() {
+            let src = idx.checked_sub(skip);
+            let cur = src.map_or(0, |src| bytes[src].0);
+            let prev = src
+                .and_then(|src| src.checked_sub(1))
+                .map_or(0, |src| bytes[src].0);
+            bytes[idx] = Byte(if shift == 0 {
+                cur
+            } else {
+                (prev << (8 - shift)) | (cur >> shift)
+            });
+        }
+        self.clear_padding();
+    }
+
+    /// A copy of the array shifted `n` places to the right, filling the start with `0` bits; the
+    /// same as `&arr >> n`.
+    pub fn logical_shr(&self, n: usize) -> BitArray {
+        self >> n
+    }
+
+    /// A copy of the array shifted `n` places to the right as a two's complement value, filling the
+    /// start with copies of the sign bit at index 0.
+    ///
+    /// Shifting by the length or more leaves every bit equal to the sign bit.
+    ///
+    /// ```
+    /// use bit_array_rs::BitArray;
+    ///
+    /// let minus_six = BitArray::from_bytes(&[0b1111_1010]);
+    /// assert_eq!(minus_six.arithmetic_shr(1).as_bytes(), [0b1111_1101]);
+    /// ```
+    pub fn arithmetic_shr(&self, n: usize) -> BitArray {
+        let mut res = self.clone();
+        res.arithmetic_shr_assign(n);
+        res
+    }
+
+    /// Shifts the bits of the array `n` places to the right as a two's complement value, filling
+    /// the start with copies of the sign bit at index 0.
+    ///
+    /// Shifting by the length or more leaves every bit equal to the sign bit. An empty array is
+    /// left alone.
+    pub fn arithmetic_shr_assign(&mut self, n: usize) {
+        if self.len == 0 {
+            return;
+        }
+        let negative = self.get(0).is_one();
+        self.shift_right(n);
+        if negative {
+            self.fill(0..n.min(self.len), true)
+                .expect("the range lies within the array");
+        }
+    }
+
+    /// Returns an error if `idx` is not a valid index into the array.
+    fn try_check_index(&self, idx: usize) -> Result<(), BitError> {
+        if idx < self.len {
+            Ok(())
+        } else {
+            Err(BitError::OutOfBounds {
+                index: idx,
+                len: self.len,
+            })
+        }
+    }
+
+    /// Getter for the bit at index `idx`, or an error if it is not less than the length of the array.
+    pub fn try_get(&self, idx: usize) -> Result<Bit, BitError> {
+        self.try_check_index(idx)?;
+        Ok(self.get(idx))
+    }
+
+    /// Setter for the bit at index `idx`, or an error if it is not less than the length of the array.
+    pub fn try_set(&mut self, idx: usize, val: bool) -> Result<(), BitError> {
+        self.try_check_index(idx)?;
+        self.set(idx, val);
+        Ok(())
+    }
+
+    /// Flips the bit at index `idx`, returning its new value, or an error if `idx` is not less than
+    /// the length of the array.
+    pub fn try_toggle(&mut self, idx: usize) -> Result<Bit, BitError> {
+        self.try_check_index(idx)?;
+        Ok(self.toggle(idx))
+    }
+
+    /// The bits of the array as an unsigned integer, the first bit most significant.
+    ///
+    /// Returns an error if the array is longer than `width` bits.
+    fn to_u128(&self, width: u32) -> Result<u128, BitError> {
+        if self.len > width as usize {
+            return Err(BitError::InvalidLength {
+                len: self.len,
+                max: width as usize,
+            });
+        }
+        let value = self
+            .bytes()
+            .iter()
+            .fold(0, |acc, byte| (acc << 8) | u128::from(**byte));
+        Ok(value >> (self.bytes().len() * 8 - self.len))
+    }
+
+    /// Writes the bytes of the array as hex digits, two per byte.
+    fn fmt_hex(&self, f: &mut fmt::Formatter<'_>, upper: bool) -> fmt::Result {
+        let mut digits = String::with_capacity(self.bytes().len() * 2);
+        for byte in self.bytes() {
+            if upper {
+                write!(digits, "{:02X}", byte)?;
+            } else {
+                write!(digits, "{:02x}", byte)?;
+            }
+        }
+        f.pad_integral(true, "0x", &digits)
+    }
+
+    /// Parses an array from hex digits, each giving the next four bits with the most significant first.
+    ///
+    /// An optional `0x` or `0X` prefix is allowed, and digits may be either case. The array holds
+    /// four bits per digit, so an odd number of digits gives an array ending part way through a byte:
+    /// `"abc"` is the 12 bits `1010_1011_1100`. Returns an error if there are no digits or if any
+    /// character is not a hex digit.
+    pub fn from_hex(s: &str) -> Result<BitArray, ParseHexError> {
+        let digits = s
+            .strip_prefix("0x")
+            .or_else(|| s.strip_prefix("0X"))
+            .unwrap_or(s);
+        let nibbles = digits
+            .chars()
+            .map(|c| c.to_digit(16).ok_or(ParseHexError::InvalidDigit(c)))
+            .collect::<Result<Vec<_>, _>>()?;
+        if nibbles.is_empty() {
+            return Err(ParseHexError::Empty);
+        }
+        let mut arr = BitArray::new(nibbles.len() * 4);
+        for (i, &nibble) in nibbles.iter().enumerate() {
+            arr.set_bits(i * 4..i * 4 + 4, u64::from(nibble));
+        }
+        Ok(arr)
+    }
+
+    /// The bits of the array as lowercase hex digits, four bits to a digit, with no prefix.
+    ///
+    /// Only as many digits as the bits need are written: if the length is not a multiple of 4, the
+    /// final digit holds the remaining bits followed by zeros, so `1011_01` gives `"b4"`. This
+    /// differs from `{:x}`, which writes whole bytes, whenever the last byte is at most half used:
+    /// the 12 bits `abc` give `"abc"` here but `"abc0"` from `{:x}`. Parsing the result with
+    /// [`BitArray::from_hex`] gives the array back, extended with zeros to a multiple of 4 bits.
+    pub fn to_hex_nibbles(&self) -> String {
+        self.chunks(4)
+            .map(|(nibble, _)| {
+                char::from_digit(nibble as u32, 16).expect("a nibble is a hex digit")
+            })
+            .collect()
+    }
+
+    /// The bits of the array as uppercase hex digits, as for [`BitArray::to_hex_nibbles`].
+    pub fn to_hex_nibbles_upper(&self) -> String {
+        self.to_hex_nibbles().to_ascii_uppercase()
+    }
+
+    /// The bits of the array as lowercase hex digits, four bits to a digit.
+    #[deprecated(
+        note = "use `BitArray::to_hex_nibbles`, or `{:x}` to format whole bytes; the two differ when the last byte is at most half used"
+    )]
+    pub fn to_hex(&self) -> String {
+        self.to_hex_nibbles()
+    }
+
+    /// The bits of the array as uppercase hex digits, four bits to a digit.
+    #[deprecated(
+        note = "use `BitArray::to_hex_nibbles_upper`, or `{:X}` to format whole bytes; the two differ when the last byte is at most half used"
+    )]
+    pub fn to_hex_upper(&self) -> String {
+        self.to_hex_nibbles_upper()
+    }
+
+    /// The bits of the array as a string of `█` for each `1` and `·` for each `0`, on one line.
+    pub fn render_blocks(&self) -> String {
+        self.render_blocks_with('█', '·', None)
+    }
+
+    /// The bits of the array as a string of `one` and `zero` characters, starting a new line after
+    /// every `width` bits if a width is given.
+    ///
+    /// Lines are separated by `\n`, with none after the last. Panics if `width` is `Some(0)`.
+    pub fn render_blocks_with(&self, one: char, zero: char, width: Option<usize>) -> String {
+        assert!(width != Some(0), "line width must be non-zero");
+        let mut out = String::with_capacity(self.len * one.len_utf8().max(zero.len_utf8()));
+        for (idx, bit) in self.iter().enumerate() {
+            if idx > 0 && width.is_some_and(|width| idx % width == 0) {
+                out.push('\n');
+            }
+            out.push(if *bit { one } else { zero });
+        }
+        out
+    }
+
+    /// Writes the length and bits of the array, wrapped in the given type name.
+    ///
+    /// Bits are grouped by nibble with an underscore and by byte with a space, as in
+    /// `BitArray(len=11, 1000_0001 001)`. Only the first [`DEBUG_MAX_BITS`] bits are written,
+    /// followed by `...`. The alternate flag writes one byte per line instead.
+    pub(crate) fn fmt_named(&self, name: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}(len={}", name, self.len)?;
+        if self.len == 0 {
+            return f.write_str(")");
+        }
+        let shown = self.len.min(DEBUG_MAX_BITS);
+        let (start, sep) = if f.alternate() {
+            (",\n    ", "\n    ")
+        } else {
+            (", ", " ")
+        };
+        f.write_str(start)?;
+        for idx in 0..shown {
+            if idx % 8 == 0 && idx != 0 {
+                f.write_str(sep)?;
+            } else if idx % 8 == 4 {
+                f.write_str("_")?;
+            }
+            // SAFETY: `idx` is less than `shown`, which is at most the length.
+            let bit = unsafe { self.get_unchecked(idx) };
+            f.write_char(if bit.is_one() { '1' } else { '0' })?;
+        }
+        if shown < self.len {
+            f.write_str(sep)?;
+            f.write_str("...")?;
+        }
+        f.write_str(if f.alternate() { "\n)" } else { ")" })
+    }
+}
+
+/// The most bits written by the `Debug` output of a [`BitArray`] or [`BitVec`](crate::BitVec).
+const DEBUG_MAX_BITS: usize = 128;
+
+/// The error returned when parsing hex digits into a [`BitArray`] fails.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ParseHexError {
+    /// The input held no digits, after any `0x` prefix.
+    Empty,
+    /// The input contained a character that is not a hex digit.
+    InvalidDigit(char),
+}
+
+impl fmt::Display for ParseHexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseHexError::Empty => f.write_str("no hex digits to parse"),
+            ParseHexError::InvalidDigit(c) => write!(f, "invalid hex digit {:?}", c),
+        }
+    }
+}
+
+impl core::error::Error for ParseHexError {}
+
+/// The bytes that open every serialized array.
+#[cfg(feature = "std")]
+const MAGIC: [u8; 4] = *b"BITA";
+
+/// The version of the serialized format written by [`BitArray::write_to`].
+#[cfg(feature = "std")]
+const FORMAT_VERSION: u8 = 1;
+
+#[cfg(feature = "std")]
+impl BitArray {
+    /// Writes the array in a self-describing binary format, returning the number of bytes written.
+    ///
+    /// The format is stable:
+    ///
+    /// | Offset | Size          | Contents                                           |
+    /// |--------|---------------|----------------------------------------------------|
+    /// | 0      | 4             | The magic bytes `BITA`                             |
+    /// | 4      | 1             | The format version, currently `1`                  |
+    /// | 5      | 8             | The number of bits, as a big-endian `u64`          |
+    /// | 13     | `ceil(len/8)` | The bits, packed as by [`BitArray::as_bytes`]      |
+    ///
+    /// The unused bits of the final payload byte are zero.
+    pub fn write_to<W: io::Write>(&self, mut w: W) -> io::Result<usize> {
+        w.write_all(&MAGIC)?;
+        w.write_all(&[FORMAT_VERSION])?;
+        w.write_all(&(self.len as u64).to_be_bytes())?;
+        w.write_all(self.as_bytes())?;
+        Ok(MAGIC.len() + 1 + 8 + self.as_bytes().len())
+    }
+
+    /// Reads an array written by [`BitArray::write_to`].
+    ///
+    /// Fails with [`io::ErrorKind::InvalidData`] if the magic bytes or version are not recognised,
+    /// if the input ends before the declared number of bits, or if any unused payload bit is set.
+    pub fn read_from<R: Read>(mut r: R) -> io::Result<BitArray> {
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg);
+        let truncated = |e: io::Error| match e.kind() {
+            io::ErrorKind::UnexpectedEof => invalid("serialized bit array is truncated"),
+            _ => e,
+        };
+
+        let mut header = [0; 13];
+        r.read_exact(&mut header).map_err(truncated)?;
+        if header[..4] != MAGIC {
+            return Err(invalid("not a serialized bit array"));
+        }
+        if header[4] != FORMAT_VERSION {
+            return Err(invalid("unsupported serialized bit array version"));
+        }
+        let mut len = [0; 8];
+        len.copy_from_slice(&header[5..]);
+        let len = usize::try_from(u64::from_be_bytes(len))
+            .map_err(|_| invalid("serialized bit array is too long"))?;
+
+        // Read through `take` rather than allocating up front, so a corrupt length cannot exhaust memory.
+        let size = len.div_ceil(8);
+        let mut bytes = Vec::new();
+        r.take(size as u64).read_to_end(&mut bytes)?;
+        if bytes.len() != size {
+            return Err(invalid("serialized bit array is truncated"));
+        }
+        let padding = bytes.last().map_or(0, |&last| match len % 8 {
+            0 => 0,
+            n => last & (u8::MAX >> n),
+        });
+        if padding != 0 {
+            return Err(invalid("serialized bit array has padding bits set"));
+        }
+        Ok(BitArray::from_vec(bytes, len).expect("the payload holds every bit"))
+    }
+}
+
+impl fmt::Debug for BitArray {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_named("BitArray", f)
+    }
+}
+
+/// Flips every bit of the array; the unused bits of the final block stay zero.
+impl Not for BitArray {
+    type Output = BitArray;
+
+    fn not(mut self) -> Self::Output {
+        self.toggle_all();
+        self
+    }
+}
+
+impl Not for &BitArray {
+    type Output = BitArray;
+
+    fn not(self) -> Self::Output {
+        !self.clone()
+    }
+}
+
+/// Shifts the bits towards the start, as for [`BitArray::shift_left`].
+impl Shl<usize> for BitArray {
+    type Output = BitArray;
+
+    fn shl(mut self, n: usize) -> Self::Output {
+        self.shift_left(n);
+        self
+    }
+}
+
+impl Shl<usize> for &BitArray {
+    type Output = BitArray;
+
+    fn shl(self, n: usize) -> Self::Output {
+        self.clone() << n
+    }
+}
+
+impl ShlAssign<usize> for BitArray {
+    fn shl_assign(&mut self, n: usize) {
+        self.shift_left(n);
+    }
+}
+
+/// Shifts the bits towards the end, as for [`BitArray::shift_right`].
+impl Shr<usize> for BitArray {
+    type Output = BitArray;
+
+    fn shr(mut self, n: usize) -> Self::Output {
+        self.shift_right(n);
+        self
+    }
+}
+
+impl Shr<usize> for &BitArray {
+    type Output = BitArray;
+
+    fn shr(self, n: usize) -> Self::Output {
+        self.clone() >> n
+    }
+}
+
+impl ShrAssign<usize> for BitArray {
+    fn shr_assign(&mut self, n: usize) {
+        self.shift_right(n);
+    }
+}
+
+impl PartialOrd for BitArray {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BitArray {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Compare the shared prefix 64 bits at a time, then fall back to the lengths.
+        let common = self.len.min(other.len);
+        (0..common)
+            .step_by(64)
+            .map(|start| {
+                let range = start..(start + 64).min(common);
+                self.get_bits(range.clone()).cmp(&other.get_bits(range))
+            })
+            .find(|&ord| ord != Ordering::Equal)
+            .unwrap_or_else(|| self.len.cmp(&other.len))
+    }
+}
+
+impl Hash for BitArray {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.slice(0..self.len).hash(state);
+    }
+}
+
+/// Panics if `idx` is not less than the length of the array.
+///
+/// `IndexMut` is not provided, since the bits are packed; use [`BitArray::set`] or
+/// [`BitArray::get_mut`] to change a bit.
+impl Index<usize> for BitArray {
+    type Output = Bit;
+
+    fn index(&self, idx: usize) -> &Self::Output {
+        self.get(idx).as_static()
+    }
+}
+
+/// A guard for changing one bit of a [`BitArray`], created by [`BitArray::get_mut`].
+///
+/// The value is cached in the guard and only written back to the array, if it changed, on drop.
+#[derive(Debug)]
+pub struct BitRefMut<'a> {
+    /// The array holding the bit.
+    arr: &'a mut BitArray,
+    /// The index of the bit.
+    idx: usize,
+    /// The current value of the bit.
+    val: bool,
+    /// The value of the bit when the guard was created.
+    orig: bool,
+}
+
+impl BitRefMut<'_> {
+    /// Setter for the value of the bit.
+    pub fn set(&mut self, val: bool) {
+        self.val = val;
+    }
+}
+
+impl Deref for BitRefMut<'_> {
+    type Target = bool;
+
+    fn deref(&self) -> &Self::Target {
+        &self.val
+    }
+}
+
+impl DerefMut for BitRefMut<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.val
+    }
+}
+
+impl Drop for BitRefMut<'_> {
+    fn drop(&mut self) {
+        if self.val != self.orig {
+            self.arr.set(self.idx, self.val);
+        }
+    }
+}
+
+/// A borrowed view of the bits of a [`BitArray`], laid out for C, created by [`BitArray::as_view`].
+///
+/// In C the view is `struct { const uint8_t *data; size_t bit_len; }`. `data` points at
+/// `bit_len.div_ceil(8)` bytes holding the bits in the crate's order, with any unused bits of the
+/// final byte zero; it has no alignment requirement on the C side, and is dangling but non-null
+/// when `bit_len` is zero. The bytes stay valid, and unchanged, only while the array is borrowed.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct BitArrayView<'a> {
+    /// The first byte of the array.
+    data: *const u8,
+    /// The number of bits in the array.
+    bit_len: usize,
+    /// Ties the view to the borrow of the array.
+    marker: PhantomData<&'a BitArray>,
+}
+
+impl<'a> BitArrayView<'a> {
+    /// The pointer to the first byte of the array.
+    pub fn data(&self) -> *const u8 {
+        self.data
+    }
+
+    /// The number of bits in the array.
+    pub fn bit_len(&self) -> usize {
+        self.bit_len
+    }
+
+    /// The bytes described by the view.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        // SAFETY: the view was made from a live borrow of an array of `bit_len` bits, whose bytes
+        // are the first `bit_len.div_ceil(8)` behind `data`.
+        unsafe { slice::from_raw_parts(self.data, self.bit_len.div_ceil(8)) }
+    }
+}
+
+impl<'a> From<&'a BitArray> for BitArrayView<'a> {
+    fn from(arr: &'a BitArray) -> Self {
+        BitArrayView {
+            data: arr.as_bytes().as_ptr(),
+            bit_len: arr.len,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// An iterator over the positions of a pattern within an array, created by [`BitArray::matches`].
+#[derive(Debug, Clone)]
+pub struct Matches<'a> {
+    /// The array being searched.
+    haystack: &'a BitArray,
+    /// The pattern being searched for.
+    pattern: &'a BitArray,
+    /// The first index at which the next match may start.
+    pos: usize,
+}
+
+impl Iterator for Matches<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let found = self.haystack.find_from(self.pattern, self.pos);
+        // Stop for good once the search fails, so the iterator is fused.
+        self.pos = found.map_or(usize::MAX, |pos| pos + 1);
+        found
+    }
+}
+
+impl FusedIterator for Matches<'_> {}
+
+/// An iterator over the pieces of an array between occurrences of a delimiter, created by
+/// [`BitArray::split`] and [`BitArray::splitn`].
+#[derive(Debug, Clone)]
+pub struct Split<'a> {
+    /// The array being split.
+    haystack: &'a BitArray,
+    /// The pattern separating the pieces.
+    delim: &'a BitArray,
+    /// The index at which the next piece starts, or `None` once the last piece has been returned.
+    pos: Option<usize>,
+    /// The number of pieces still to be returned, at most.
+    remaining: usize,
+}
+
+impl Iterator for Split<'_> {
+    type Item = BitArray;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.pos.filter(|_| self.remaining > 0)?;
+        self.remaining -= 1;
+        let found = if self.remaining == 0 {
+            None
+        } else {
+            self.haystack.find_from(self.delim, start)
+        };
+        let end = found.unwrap_or(self.haystack.len);
+        self.pos = found.map(|at| at + self.delim.len);
+        let mut piece = BitArray::new(0);
+        piece.append_range(self.haystack, start..end);
+        Some(piece)
+    }
+}
+
+impl FusedIterator for Split<'_> {}
+
+/// An iterator over the runs of equal bits in an array, created by [`BitArray::runs`].
+#[derive(Debug, Clone)]
+pub struct Runs<'a> {
+    /// The array being iterated over.
+    arr: &'a BitArray,
+    /// The index at which the next run starts.
+    pos: usize,
+}
+
+impl Iterator for Runs<'_> {
+    type Item = (Bit, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.arr.len {
+            return None;
+        }
+        // SAFETY: `pos` was just checked against the length.
+        let bit = unsafe { self.arr.get_unchecked(self.pos) };
+        let end = self.arr.next_equal(self.pos, !*bit).unwrap_or(self.arr.len);
+        let len = end - self.pos;
+        self.pos = end;
+        Some((bit, len))
+    }
+}
+
+impl FusedIterator for Runs<'_> {}
+
+/// An iterator over fixed-width groups of bits in an array, created by [`BitArray::chunks`].
+#[derive(Debug, Clone)]
+pub struct Chunks<'a> {
+    /// The array being iterated over.
+    arr: &'a BitArray,
+    /// The number of bits in each full group.
+    width: usize,
+    /// The index at which the next group starts.
+    pos: usize,
+}
+
+impl Iterator for Chunks<'_> {
+    type Item = (u64, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.arr.len {
+            return None;
+        }
+        let end = (self.pos + self.width).min(self.arr.len);
+        let len = end - self.pos;
+        let value = self.arr.get_bits(self.pos..end) << (self.width - len);
+        self.pos = end;
+        Some((value, len))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.arr.len - self.pos).div_ceil(self.width);
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for Chunks<'_> {}
+
+impl FusedIterator for Chunks<'_> {}
+
+/// An iterator over the bytes of an array starting at any bit offset, created by
+/// [`BitArray::iter_bytes_at`].
+#[derive(Debug, Clone)]
+pub struct BytesAt<'a> {
+    /// The stored bytes of the array, whose unused bits are zero.
+    bytes: &'a [u8],
+    /// The index of the first bit of the next byte.
+    pos: usize,
+    /// The length of the array.
+    end: usize,
+}
+
+impl BytesAt<'_> {
+    /// The bits after the last whole byte, padded on the right with zeros, and how many there are.
+    ///
+    /// The count is less than 8, and neither depends on how far the iterator has advanced.
+    pub fn remainder(&self) -> (u8, usize) {
+        let count = (self.end - self.pos) % 8;
+        if count == 0 {
+            return (0, 0);
+        }
+        let start = self.end - count;
+        // The padding past the end is zero, so the tail needs no masking.
+        (self.byte_at(start), count)
+    }
+
+    /// The eight bits starting at index `pos`, reading zeros past the end of the stored bytes.
+    #[inline]
+    fn byte_at(&self, pos: usize) -> u8 {
+        let (idx, shift) = (pos / 8, pos % 8);
+        let hi = self.bytes[idx];
+        if shift == 0 {
+            return hi;
+        }
+        let lo = self.bytes.get(idx + 1).copied().unwrap_or(0);
+        (hi << shift) | (lo >> (8 - shift))
+    }
+}
+
+impl Iterator for BytesAt<'_> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.end - self.pos < 8 {
+            return None;
+        }
+        let byte = self.byte_at(self.pos);
+        self.pos += 8;
+        Some(byte)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.end - self.pos) / 8;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for BytesAt<'_> {}
+
+impl FusedIterator for BytesAt<'_> {}
+
+/// An iterator over the overlapping windows of bits of a [`BitArray`], created by [`BitArray::windows`].
+#[derive(Debug, Clone)]
+pub struct Windows<'a> {
+    /// The array being iterated over.
+    arr: &'a BitArray,
+    /// The number of bits in each window.
+    width: usize,
+    /// The index at which the next window starts.
+    pos: usize,
+    /// The value of the previous window, if any.
+    value: u64,
+}
+
+impl Iterator for Windows<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let end = self.pos + self.width;
+        if end > self.arr.len {
+            return None;
+        }
+        self.value = if self.pos == 0 {
+            self.arr.get_bits(0..self.width)
+        } else {
+            let mask = u64::MAX >> (64 - self.width);
+            // SAFETY: `end` is at most the length, and is not zero since `width` is not.
+            let bit = unsafe { self.arr.get_unchecked(end - 1) };
+            ((self.value << 1) | bit.as_u8() as u64) & mask
+        };
+        self.pos += 1;
+        Some(self.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.arr.len + 1).saturating_sub(self.pos + self.width);
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for Windows<'_> {}
+
+impl FusedIterator for Windows<'_> {}
+
+/// Packs the bits into bytes as they arrive.
+impl FromIterator<bool> for BitArray {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        iter.into_iter().collect::<BitVec>().into()
+    }
+}
+
+/// Packs the bits into bytes as they arrive.
+impl FromIterator<Bit> for BitArray {
+    fn from_iter<I: IntoIterator<Item = Bit>>(iter: I) -> Self {
+        iter.into_iter().collect::<BitVec>().into()
+    }
+}
+
+impl From<&[bool]> for BitArray {
+    fn from(bits: &[bool]) -> Self {
+        bits.iter().copied().collect()
+    }
+}
+
+impl From<Vec<bool>> for BitArray {
+    fn from(bits: Vec<bool>) -> Self {
+        bits.into_iter().collect()
+    }
+}
+
+/// Formats the bytes of the array in order as one string of hex digits, two per byte (`deadbeef`).
+///
+/// The `#` flag adds a `0x` prefix, and the width and fill flags apply to the whole string.
+/// If the length is not a multiple of 8, the final byte is shown as stored, with its unused bits zero;
+/// [`BitArray::to_hex_nibbles`] instead stops after the last nibble holding any bits.
+impl fmt::LowerHex for BitArray {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_hex(f, false)
+    }
+}
+
+/// Formats the bytes of the array in order as one string of hex digits, two per byte (`DEADBEEF`).
+///
+/// The `#` flag adds a `0x` prefix, and the width and fill flags apply to the whole string.
+/// If the length is not a multiple of 8, the final byte is shown as stored, with its unused bits zero.
+impl fmt::UpperHex for BitArray {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_hex(f, true)
+    }
+}
+
+/// Implements conversions between arrays and an unsigned integer type.
+macro_rules! impl_array_int {
+    ($($t:ty),*) => {
+        $(
+            /// Creates an array of every bit of the value, most-significant first.
+            impl From<$t> for BitArray {
+                fn from(value: $t) -> Self {
+                    Self::from_bytes(&value.to_be_bytes())
+                }
+            }
+
+            /// Reads the bits of the array as an unsigned integer, the first bit most significant.
+            ///
+            /// Returns an error if the array is longer than the integer type.
+            impl TryFrom<&BitArray> for $t {
+                type Error = BitError;
+
+                fn try_from(arr: &BitArray) -> Result<Self, Self::Error> {
+                    arr.to_u128(<$t>::BITS).map(|value| value as $t)
+                }
+            }
+        )*
+    };
+}
+
+impl_array_int!(u8, u16, u32, u64, u128);
+
+/// Implements a set operation between arrays in terms of the in-place method `$with`.
+///
+/// Arrays of different lengths give a result with the longer length; the shorter array is
+/// treated as if it were zero-extended.
+macro_rules! impl_array_op {
+    ($op:ident, $method:ident, $op_assign:ident, $method_assign:ident, $with:ident) => {
+        impl $op for BitArray {
+            type Output = BitArray;
+
+            fn $method(mut self, rhs: BitArray) -> Self::Output {
+                self.$with(&rhs);
+                self
+            }
+        }
+
+        impl $op<&BitArray> for &BitArray {
+            type Output = BitArray;
+
+            fn $method(self, rhs: &BitArray) -> Self::Output {
+                let mut res = self.clone();
+                res.$with(rhs);
+                res
+            }
+        }
+
+        impl $op_assign for BitArray {
+            fn $method_assign(&mut self, rhs: BitArray) {
+                self.$with(&rhs);
+            }
+        }
+
+        impl $op_assign<&BitArray> for BitArray {
+            fn $method_assign(&mut self, rhs: &BitArray) {
+                self.$with(rhs);
+            }
+        }
+    };
+}
+
+impl_array_op!(BitAnd, bitand, BitAndAssign, bitand_assign, intersect_with);
+impl_array_op!(BitOr, bitor, BitOrAssign, bitor_assign, union_with);
+impl_array_op!(
+    BitXor,
+    bitxor,
+    BitXorAssign,
+    bitxor_assign,
+    symmetric_difference_with
+);
+
+/// An iterator over the indices of the `1` or `0` bits of an array, created by
+/// [`BitArray::iter_ones`] and [`BitArray::iter_zeros`].
+#[derive(Debug, Clone)]
+pub struct ArrayIndices<'a> {
+    /// The bytes not yet reached from either end, with their positions.
+    bytes: iter::Enumerate<slice::Iter<'a, Byte>>,
+    /// XORed into every byte before searching it, so that the wanted bits are ones.
+    flip: u8,
+    /// The position of the final byte of the array.
+    last: usize,
+    /// The mask selecting the bits of the final byte that are part of the array.
+    last_mask: u8,
+    /// The partially dispatched byte at the front, with the index of its first bit.
+    front: Option<(usize, ByteIndices)>,
+    /// The partially dispatched byte at the back, with the index of its first bit.
+    back: Option<(usize, ByteIndices)>,
+}
+
+impl<'a> ArrayIndices<'a> {
+    /// Creates an iterator over the bits of `arr` that are ones once XORed with `flip`.
+    fn new(arr: &'a BitArray, flip: u8) -> Self {
+        Self {
+            bytes: arr.bytes().iter().enumerate(),
+            flip,
+            last: arr.bytes().len().wrapping_sub(1),
+            last_mask: arr.last_byte_mask(),
+            front: None,
+            back: None,
+        }
+    }
+
+    /// The wanted bits of the byte at position `i`, with the index of its first bit.
+    fn indices(&self, i: usize, byte: Byte) -> (usize, ByteIndices) {
+        let mut bits = *byte ^ self.flip;
+        if i == self.last {
+            bits &= self.last_mask;
+        }
+        (i * 8, Byte(bits).iter_ones())
+    }
+}
+
+impl Iterator for ArrayIndices<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((base, indices)) = &mut self.front {
+                if let Some(idx) = indices.next() {
+                    return Some(*base + idx);
+                }
+            }
+            match self.bytes.next() {
+                Some((i, &byte)) => self.front = Some(self.indices(i, byte)),
+                None => {
+                    let (base, indices) = self.back.as_mut()?;
+                    return indices.next().map(|idx| *base + idx);
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let pending = |end: &Option<(usize, ByteIndices)>| end.as_ref().map_or(0, |(_, i)| i.len());
+        let known = pending(&self.front) + pending(&self.back);
+        (known, Some(known + self.bytes.len() * 8))
+    }
+}
+
+impl DoubleEndedIterator for ArrayIndices<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((base, indices)) = &mut self.back {
+                if let Some(idx) = indices.next_back() {
+                    return Some(*base + idx);
+                }
+            }
+            match self.bytes.next_back() {
+                Some((i, &byte)) => self.back = Some(self.indices(i, byte)),
+                None => {
+                    let (base, indices) = self.front.as_mut()?;
+                    return indices.next_back().map(|idx| *base + idx);
+                }
+            }
+        }
+    }
+}
+
+impl FusedIterator for ArrayIndices<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Prbs, PrbsKind};
+    use alloc::format;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_new() {
+        let arr = BitArray::new(13);
+        assert_eq!(arr.len(), 13);
+        assert!(!arr.is_empty());
+        assert!((0..13).all(|idx| arr.get(idx).is_zero()));
+        assert!(BitArray::new(0).is_empty());
+    }
+
+    #[test]
+    fn test_from_fn() {
+        let mut calls = Vec::new();
+        let arr = BitArray::from_fn(19, |i| {
+            calls.push(i);
+            [2, 3, 5, 7, 11, 13, 17].contains(&i)
+        });
+        assert_eq!(calls, (0..19).collect::<Vec<_>>());
+        assert_eq!(arr, bits("0011010100010100010"));
+        check_fresh(
+            &arr,
+            &(0..19).map(|i| arr.get(i).as_bool()).collect::<Vec<_>>(),
+        );
+        assert!(BitArray::from_fn(0, |_| unreachable!()).is_empty());
+    }
+
+    #[test]
+    fn test_unchecked() {
+        let arr: BitArray = Prbs::new(PrbsKind::Prbs15).take(130).collect();
+        let mut copy = BitArray::new(130);
+        for idx in 0..130 {
+            // SAFETY: every index is less than 130.
+            unsafe {
+                assert_eq!(arr.get_unchecked(idx), arr.get(idx));
+                copy.set_unchecked(idx, *arr.get_unchecked(idx));
+            }
+        }
+        assert_eq!(copy, arr);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "index 13 out of range for bit array of length 13")]
+    fn test_unchecked_debug_assert() {
+        // SAFETY: this test only runs with debug assertions, which panic before the index is used.
+        unsafe { BitArray::new(13).get_unchecked(13) };
+    }
+
+    #[test]
+    fn test_get_set() {
+        let mut arr = BitArray::new(11);
+        arr.set(0, true);
+        arr.set(7, true);
+        arr.set(8, true);
+        arr.set(10, true);
+        assert_eq!(arr.as_bytes(), [0b1000_0001, 0b1010_0000]);
+        assert!(arr.get(0).is_one());
+        assert!(arr.get(1).is_zero());
+        assert!(arr.get(8).is_one());
+        assert!(arr.get(9).is_zero());
+
+        arr.set(8, false);
+        assert!(arr.get(8).is_zero());
+        assert!(arr.get(10).is_one());
+        assert_eq!(format!("{:?}", arr), "BitArray(len=11, 1000_0001 001)");
+    }
+
+    #[test]
+    fn test_debug() {
+        assert_eq!(format!("{:?}", BitArray::new(0)), "BitArray(len=0)");
+        let arr = bits("1100110101010011110");
+        assert_eq!(
+            format!("{:?}", arr),
+            "BitArray(len=19, 1100_1101 0101_0011 110)"
+        );
+        assert_eq!(
+            format!("{:#?}", arr),
+            "BitArray(len=19,\n    1100_1101\n    0101_0011\n    110\n)"
+        );
+        assert_eq!(format!("{:?}", bits("101101")), "BitArray(len=6, 1011_01)");
+    }
+
+    #[test]
+    fn test_debug_truncated() {
+        let arr = BitArray::from_bytes(&[0xA5; 40]);
+        let shown = vec!["1010_0101"; 16].join(" ");
+        assert_eq!(
+            format!("{:?}", arr),
+            format!("BitArray(len=320, {} ...)", shown)
+        );
+        let pretty = format!("{:#?}", arr);
+        assert_eq!(pretty.lines().count(), 19);
+        assert!(pretty.ends_with("\n    1010_0101\n    ...\n)"));
+        assert_eq!(
+            format!("{:?}", BitArray::from_bytes(&[0xFF; 16])),
+            format!("BitArray(len=128, {})", vec!["1111_1111"; 16].join(" "))
+        );
+    }
+
+    #[test]
+    fn test_de_bruijn() {
+        assert_eq!(BitArray::de_bruijn(0), Ok(bits("0")));
+        assert_eq!(BitArray::de_bruijn(1), Ok(bits("01")));
+        assert_eq!(BitArray::de_bruijn(2), Ok(bits("0011")));
+        assert_eq!(BitArray::de_bruijn(4), Ok(bits("0000100110101111")));
+        for n in 1..=8 {
+            let seq = BitArray::de_bruijn(n).unwrap();
+            assert_eq!(seq.len(), 1 << n);
+            // Wrap the first n - 1 bits around so every cyclic window is a plain window.
+            let cyclic = seq.concat(&BitArray::from_fn(n - 1, |i| *seq.get(i)));
+            let mut seen = vec![0; 1 << n];
+            for window in cyclic.windows(n) {
+                seen[window as usize] += 1;
+            }
+            assert!(seen.iter().all(|&count| count == 1));
+        }
+        assert_eq!(BitArray::de_bruijn(16).unwrap().count_ones(), 1 << 15);
+    }
+
+    #[test]
+    fn test_de_bruijn_too_large() {
+        assert_eq!(
+            BitArray::de_bruijn(25),
+            Err(BitError::InvalidLength { len: 25, max: 24 })
+        );
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let bytes = [0xDE, 0xAD, 0xBE, 0xEF];
+        let arr = BitArray::from_bytes(&bytes);
+        assert_eq!(arr.len(), 32);
+        assert_eq!(arr.as_bytes(), bytes);
+        assert!(arr.get(0).is_one());
+        assert!(arr.get(2).is_zero());
+        assert_eq!(arr.clone().into_vec(), bytes);
+
+        let from_vec = BitArray::from_vec(bytes.to_vec(), 32).unwrap();
+        assert_eq!(from_vec, arr);
+        assert_eq!(from_vec.into_vec(), bytes);
+        assert_eq!(BitArray::from_bytes(&[]), BitArray::new(0));
+    }
+
+    #[test]
+    fn test_from_vec_normalizes() {
+        let arr = BitArray::from_vec(vec![0xFF, 0xFF, 0xFF], 11).unwrap();
+        assert_eq!(arr.len(), 11);
+        assert_eq!(arr.as_bytes(), [0xFF, 0b1110_0000]);
+        assert_eq!(arr.count_ones(), 11);
+
+        let mut expected = BitArray::new(11);
+        (0..11).for_each(|idx| expected.set(idx, true));
+        assert_eq!(arr, expected);
+    }
+
+    #[test]
+    fn test_from_vec_rejects_length() {
+        let err = BitArray::from_vec(vec![0; 2], 17).unwrap_err();
+        assert_eq!(err, BitError::InvalidLength { len: 17, max: 16 });
+        assert_eq!(
+            format!("{}", err),
+            "length 17 exceeds the 16 bits available"
+        );
+        assert!(BitArray::from_vec(vec![0; 2], 16).is_ok());
+    }
+
+    #[test]
+    fn test_try_from_bytes_strict() {
+        let arr = BitArray::try_from_bytes_strict(&[0xA5, 0b1010_0000], 11).unwrap();
+        assert_eq!(arr, bits("10100101101"));
+        assert_eq!(BitArray::try_from_bytes_strict(&[], 0).unwrap().len(), 0);
+        assert_eq!(
+            BitArray::try_from_bytes_strict(&[0xA5, 0xFF], 16).unwrap(),
+            BitArray::from_bytes(&[0xA5, 0xFF])
+        );
+
+        // Every padding bit is checked, not just the one after the last used bit.
+        for padding in [0b0001_0000, 0b0000_0001] {
+            let err = BitArray::try_from_bytes_strict(&[0xA5, 0b1010_0000 | padding], 11);
+            assert_eq!(err, Err(BitError::NonZeroPadding { len: 11 }));
+        }
+        assert_eq!(
+            BitError::NonZeroPadding { len: 11 }.to_string(),
+            "padding bits past length 11 are not zero"
+        );
+
+        assert_eq!(
+            BitArray::try_from_bytes_strict(&[0xA5, 0, 0], 11),
+            Err(BitError::LengthMismatch { left: 3, right: 2 })
+        );
+        assert_eq!(
+            BitArray::try_from_bytes_strict(&[0xA5], 11),
+            Err(BitError::LengthMismatch { left: 1, right: 2 })
+        );
+    }
+
+    #[test]
+    fn test_into_bytes_pad() {
+        let arr = bits("10100101101");
+        assert_eq!(arr.clone().into_bytes(Bit::ZERO), [0xA5, 0b1010_0000]);
+        assert_eq!(arr.clone().into_bytes(Bit::ONE), [0xA5, 0b1011_1111]);
+        // Without padding there is nothing to fill.
+        assert_eq!(bits("00000000").into_bytes(Bit::ONE), [0]);
+        assert!(BitArray::new(0).into_bytes(Bit::ONE).is_empty());
+
+        let mut prbs = Prbs::new(PrbsKind::Prbs15);
+        for len in 0..40 {
+            let arr: BitArray = prbs.by_ref().take(len).collect();
+            let zero_padded = arr.clone().into_bytes(Bit::ZERO);
+            assert_eq!(zero_padded, arr.as_bytes());
+            assert_eq!(
+                BitArray::try_from_bytes_strict(&zero_padded, len).unwrap(),
+                arr
+            );
+            let one_padded = arr.clone().into_bytes(Bit::ONE);
+            assert_eq!(
+                BitArray::try_from_bytes_strict(&one_padded, len).is_err(),
+                len % 8 != 0
+            );
+            assert_eq!(BitArray::from_vec(one_padded, len).unwrap(), arr);
+        }
+    }
+
+    #[test]
+    fn test_raw_parts_round_trip() {
+        for len in [0, 1, 13, 64, 100] {
+            let mut arr = BitArray::from_fn(len, |i| i % 3 == 0);
+            arr.blocks.reserve(5);
+            let (cap, expected) = (arr.blocks.capacity() * 8, arr.clone());
+
+            let (ptr, bit_len, byte_cap) = arr.into_raw_parts();
+            assert_eq!((bit_len, byte_cap), (len, cap));
+            // SAFETY: the parts are used once, straight from `into_raw_parts`.
+            let arr = unsafe { BitArray::from_raw_parts(ptr, bit_len, byte_cap) };
+            assert_eq!(arr, expected);
+            assert_eq!(arr.blocks.capacity() * 8, cap);
+        }
+    }
+
+    #[test]
+    fn test_raw_parts_clears_padding() {
+        let (ptr, bit_len, byte_cap) = bits("101").into_raw_parts();
+        // SAFETY: the first byte is in the buffer, and the parts are then used once.
+        let arr = unsafe {
+            *ptr = 0xFF;
+            BitArray::from_raw_parts(ptr, bit_len, byte_cap)
+        };
+        assert_eq!(arr, bits("111"));
+    }
+
+    #[test]
+    fn test_view() {
+        let arr = BitArray::from_fn(21, |i| i % 5 == 0);
+        let view = arr.as_view();
+        assert_eq!(view.bit_len(), 21);
+        assert_eq!(view.as_bytes(), arr.as_bytes());
+        // SAFETY: the view describes `bit_len.div_ceil(8)` bytes of a live array.
+        let read = unsafe { slice::from_raw_parts(view.data(), 3) };
+        assert_eq!(read, [0b1000_0100, 0b0010_0001, 0b0000_1000]);
+        assert!(BitArray::new(0).as_view().as_bytes().is_empty());
+        assert_eq!(
+            core::mem::size_of::<BitArrayView<'_>>(),
+            2 * core::mem::size_of::<usize>()
+        );
+    }
+
+    #[test]
+    fn test_try_get_set() {
+        let mut arr = BitArray::new(10);
+        assert_eq!(arr.try_set(9, true), Ok(()));
+        assert_eq!(arr.try_get(9), Ok(Bit::ONE));
+        assert_eq!(arr.try_get(0), Ok(Bit::ZERO));
+
+        let err = BitError::OutOfBounds { index: 10, len: 10 };
+        assert_eq!(arr.try_get(10), Err(err.clone()));
+        assert_eq!(arr.try_set(10, true), Err(err));
+        assert_eq!(arr.count_ones(), 1);
+    }
+
+    #[test]
+    fn test_try_toggle_set_range_copy() {
+        let mut arr = BitArray::new(10);
+        assert_eq!(arr.try_toggle(3), Ok(Bit::ONE));
+        assert_eq!(arr.try_toggle(3), Ok(Bit::ZERO));
+        let err = arr.try_toggle(10).unwrap_err();
+        assert_eq!(err, BitError::OutOfBounds { index: 10, len: 10 });
+        assert_eq!(err.to_string(), "index 10 out of range for length 10");
+
+        assert_eq!(arr.try_set_range(2..5), Ok(()));
+        assert_eq!(arr, bits("0011100000"));
+        let err = arr.try_set_range(5..11).unwrap_err();
+        assert_eq!(err, BitError::OutOfBounds { index: 11, len: 10 });
+        #[allow(clippy::reversed_empty_ranges)]
+        let err = arr.try_set_range(6..5).unwrap_err();
+        assert_eq!(err, BitError::DecreasingRange { start: 6, end: 5 });
+        assert_eq!(err.to_string(), "range 6..5 is decreasing");
+        assert_eq!(arr, bits("0011100000"));
+
+        let src = bits("1101");
+        assert_eq!(arr.try_copy_bits_from(6, &src, 0..4), Ok(()));
+        assert_eq!(arr, bits("0011101101"));
+        assert_eq!(
+            arr.try_copy_bits_from(7, &src, 0..4),
+            Err(BitError::OutOfBounds { index: 11, len: 10 })
+        );
+        assert_eq!(
+            arr.try_copy_bits_from(0, &src, 2..5),
+            Err(BitError::OutOfBounds { index: 5, len: 4 })
+        );
+        #[allow(clippy::reversed_empty_ranges)]
+        let err = arr.try_copy_bits_from(0, &src, 3..1).unwrap_err();
+        assert_eq!(err, BitError::DecreasingRange { start: 3, end: 1 });
+        assert_eq!(arr, bits("0011101101"));
+    }
+
+    #[test]
+    fn test_predicates() {
+        let empty = BitArray::new(0);
+        assert!(empty.all() && !empty.any() && empty.none());
+
+        for len in [1, 7, 8, 9, 63, 64, 65, 130] {
+            let mut arr = BitArray::new(len);
+            assert!(!arr.all() && !arr.any() && arr.none());
+            (0..len).for_each(|idx| arr.set(idx, true));
+            // Padding zeros after the final bit do not count against all().
+            assert!(arr.all() && arr.any() && !arr.none());
+            arr.set(len - 1, false);
+            assert!(!arr.all());
+            assert_eq!(arr.any(), len > 1);
+            arr = BitArray::new(len);
+            arr.set(len - 1, true);
+            assert!(arr.any() && !arr.none());
+            assert_eq!(arr.all(), len == 1);
+        }
+    }
+
+    #[test]
+    fn test_parity() {
+        assert_eq!(BitArray::new(0).parity(), Bit::ZERO);
+        for len in [1, 9, 64, 100] {
+            let mut arr = BitArray::new(len);
+            assert_eq!(arr.parity(), Bit::ZERO);
+            arr.set_all();
+            assert_eq!(arr.parity(), Bit::from(len % 2 == 1));
+            let arr: BitArray = Prbs::new(PrbsKind::Prbs15).take(len).collect();
+            assert_eq!(arr.parity(), Bit::from(arr.count_ones() % 2 == 1));
+        }
+    }
+
+    #[test]
+    fn test_count() {
+        for len in [0, 1, 7, 8, 9, 63, 100] {
+            let mut arr = BitArray::new(len);
+            let mut prbs = Prbs::new(PrbsKind::Prbs15);
+            for idx in 0..len {
+                arr.set(idx, prbs.next().unwrap().is_one());
+            }
+            let naive = (0..len).filter(|&idx| arr.get(idx).is_one()).count();
+            assert_eq!(arr.count_ones(), naive);
+            assert_eq!(arr.count_zeros(), len - naive);
+        }
+
+        let mut arr = BitArray::new(9);
+        (0..9).for_each(|idx| arr.set(idx, true));
+        assert_eq!(arr.count_ones(), 9);
+        assert_eq!(arr.count_zeros(), 0);
+    }
+
+    #[test]
+    fn test_scan() {
+        for len in [0, 1, 7, 8, 9, 20, 64, 70] {
+            for seed in [1, 2, 3] {
+                let mut arr = BitArray::new(len);
+                let mut prbs = Prbs::new(PrbsKind::Prbs15).skip(seed * 37);
+                // Sparse bits, so that whole zero bytes occur.
+                for idx in 0..len {
+                    let one = (0..3).all(|_| prbs.next().unwrap().is_one());
+                    arr.set(idx, one);
+                }
+                let bits: Vec<bool> = (0..len).map(|idx| *arr.get(idx)).collect();
+                assert_eq!(arr.first_one(), bits.iter().position(|&b| b));
+                assert_eq!(arr.first_zero(), bits.iter().position(|&b| !b));
+                assert_eq!(arr.last_one(), bits.iter().rposition(|&b| b));
+                assert_eq!(
+                    arr.leading_zeros(),
+                    bits.iter().take_while(|&&b| !b).count()
+                );
+                assert_eq!(
+                    arr.trailing_zeros(),
+                    bits.iter().rev().take_while(|&&b| !b).count()
+                );
+            }
+        }
+
+        let mut arr = BitArray::new(13);
+        (0..13).for_each(|idx| arr.set(idx, true));
+        assert_eq!(arr.first_zero(), None);
+        arr.set(12, false);
+        assert_eq!(arr.first_zero(), Some(12));
+        assert_eq!(arr.last_one(), Some(11));
+        assert_eq!(arr.trailing_zeros(), 1);
+        assert_eq!(BitArray::new(13).first_one(), None);
+        assert_eq!(BitArray::new(13).leading_zeros(), 13);
+    }
+
+    #[test]
+    fn test_from_bytes_ordered() {
+        let bytes = [0b1000_0000, 0b0000_0011];
+        let msb = BitArray::from_bytes_ordered(&bytes, BitOrder::Msb0);
+        let lsb = BitArray::from_bytes_ordered(&bytes, BitOrder::Lsb0);
+        assert_eq!(msb.len(), 16);
+        assert_eq!(lsb.len(), 16);
+        for idx in 0..16 {
+            let byte = Byte::from(bytes[idx / 8]);
+            assert_eq!(msb.get(idx), byte.get_bit_ordered(idx % 8, BitOrder::Msb0));
+            assert_eq!(lsb.get(idx), byte.get_bit_ordered(idx % 8, BitOrder::Lsb0));
+        }
+        assert_eq!(
+            format!("{:?}", lsb),
+            "BitArray(len=16, 0000_0001 1100_0000)"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "index 11 out of range for bit array of length 11")]
+    fn test_get_out_of_range() {
+        BitArray::new(11).get(11);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 3 out of range for bit array of length 0")]
+    fn test_set_out_of_range() {
+        BitArray::new(0).set(3, true);
+    }
+
+    #[test]
+    fn test_iter_indices() {
+        for len in [0, 1, 7, 8, 9, 20, 64, 70, 333] {
+            let mut arr = BitArray::new(len);
+            let mut prbs = Prbs::new(PrbsKind::Prbs15);
+            for idx in 0..len {
+                arr.set(idx, prbs.next().unwrap().is_one());
+            }
+            let ones: Vec<usize> = (0..len).filter(|&idx| arr.get(idx).is_one()).collect();
+            let zeros: Vec<usize> = (0..len).filter(|&idx| arr.get(idx).is_zero()).collect();
+            assert_eq!(arr.iter_ones().collect::<Vec<_>>(), ones);
+            assert_eq!(arr.iter_zeros().collect::<Vec<_>>(), zeros);
+            assert!(arr.iter_ones().rev().eq(ones.iter().rev().copied()));
+            assert!(arr.iter_zeros().rev().eq(zeros.iter().rev().copied()));
+
+            // Alternate ends, meeting in the middle.
+            let mut iter = arr.iter_zeros();
+            let mut front = Vec::new();
+            let mut back = Vec::new();
+            while let Some(idx) = iter.next() {
+                front.push(idx);
+                back.extend(iter.next_back());
+            }
+            front.extend(back.into_iter().rev());
+            assert_eq!(front, zeros);
+        }
+    }
+
+    #[test]
+    fn test_iter_sparse() {
+        let len = 8 * 1024 * 16;
+        let mut arr = BitArray::new(len);
+        let ones: Vec<usize> = (0..16).map(|k| k * 8 * 1024 + k * 37).collect();
+        ones.iter().for_each(|&idx| arr.set(idx, true));
+        assert_eq!(arr.iter_ones().collect::<Vec<_>>(), ones);
+        assert_eq!(arr.iter_ones().next_back(), Some(15 * 8 * 1024 + 15 * 37));
+        assert_eq!(arr.iter_zeros().count(), len - 16);
+
+        // Padding bits are never yielded as zeros.
+        let mut arr = BitArray::new(11);
+        (0..11).for_each(|idx| arr.set(idx, true));
+        assert_eq!(arr.iter_zeros().next(), None);
+        assert_eq!(arr.iter_zeros().next_back(), None);
+    }
+
+    #[test]
+    fn test_rank_select() {
+        for len in [0, 1, 7, 8, 9, 20, 64, 70, 333] {
+            let mut arr = BitArray::new(len);
+            let mut prbs = Prbs::new(PrbsKind::Prbs15).skip(len);
+            for idx in 0..len {
+                arr.set(idx, prbs.next().unwrap().is_one());
+            }
+            let ones: Vec<usize> = (0..len).filter(|&idx| arr.get(idx).is_one()).collect();
+            let zeros: Vec<usize> = (0..len).filter(|&idx| arr.get(idx).is_zero()).collect();
+            for idx in 0..=len {
+                let rank1 = ones.iter().filter(|&&i| i < idx).count();
+                assert_eq!(arr.rank1(idx), rank1);
+                assert_eq!(arr.rank0(idx), idx - rank1);
+                if let Some(pos) = arr.select1(rank1) {
+                    assert!(pos >= idx);
+                }
+            }
+            for k in 0..=len {
+                assert_eq!(arr.select1(k), ones.get(k).copied());
+                assert_eq!(arr.select0(k), zeros.get(k).copied());
+                if let Some(pos) = arr.select1(k) {
+                    assert_eq!(arr.rank1(pos), k);
+                }
+                if let Some(pos) = arr.select0(k) {
+                    assert_eq!(arr.rank0(pos), k);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_prefix_counts() {
+        let mut prbs = Prbs::new(PrbsKind::Prbs15);
+        for len in [0, 1, 63, 64, 500, 1000, 1024, 3000] {
+            let arr: BitArray = prbs.by_ref().take(len).collect();
+            for block in [1, 64, 100, 512] {
+                let index = arr.prefix_counts(block);
+                assert_eq!(index.len(), len / block + 1);
+                assert_eq!(index[0], 0);
+                for (k, &count) in index.iter().enumerate() {
+                    assert_eq!(count as usize, arr.rank1(k * block));
+                }
+                for idx in (0..=len).step_by(7).chain([len]) {
+                    assert_eq!(arr.rank1_with_index(&index, block, idx), arr.rank1(idx));
+                }
+            }
+        }
+        assert_eq!(BitArray::new(0).prefix_counts(64), [0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "block size must be non-zero")]
+    fn test_prefix_counts_zero_block() {
+        BitArray::new(8).prefix_counts(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "rank index 12 out of range for bit array of length 11")]
+    fn test_rank_out_of_range() {
+        BitArray::new(11).rank1(12);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_set_ops() {
+        use std::collections::HashSet;
+
+        /// Builds a sparse array and the set of its indices.
+        fn random(len: usize, skip: usize) -> (BitArray, HashSet<usize>) {
+            let mut arr = BitArray::new(len);
+            let mut prbs = Prbs::new(PrbsKind::Prbs15).skip(skip);
+            for idx in 0..len {
+                arr.set(idx, (0..2).all(|_| prbs.next().unwrap().is_one()));
+            }
+            let set = arr.iter_ones().collect();
+            (arr, set)
+        }
+
+        /// Checks that `arr` holds exactly the indices of `set`.
+        fn check(arr: &BitArray, len: usize, set: HashSet<&usize>) {
+            assert_eq!(arr.len(), len);
+            assert_eq!(arr.count_ones(), set.len());
+            assert!(arr.iter_ones().all(|idx| set.contains(&idx)));
+        }
+
+        for (len_a, len_b) in [(0, 0), (8, 8), (13, 13), (13, 30), (64, 9), (100, 0)] {
+            let (a, set_a) = random(len_a, 1);
+            let (b, set_b) = random(len_b, 1000);
+            let len = len_a.max(len_b);
+
+            check(&(&a | &b), len, set_a.union(&set_b).collect());
+            check(&(&a & &b), len, set_a.intersection(&set_b).collect());
+            check(
+                &(&a ^ &b),
+                len,
+                set_a.symmetric_difference(&set_b).collect(),
+            );
+            check(&a.difference(&b), len, set_a.difference(&set_b).collect());
+            check(&b.difference(&a), len, set_b.difference(&set_a).collect());
+
+            let mut in_place = a.clone();
+            in_place.union_with(&b);
+            assert_eq!(in_place, a.clone() | b.clone());
+            in_place.symmetric_difference_with(&a);
+            assert_eq!(in_place, b.difference(&a));
+            in_place &= &b;
+            in_place ^= b.clone();
+            in_place.difference_with(&BitArray::new(len));
+            check(&in_place, len, set_a.intersection(&set_b).collect());
+        }
+    }
+
+    #[test]
+    fn test_set_ops_padding() {
+        let mut a = BitArray::new(3);
+        (0..3).for_each(|idx| a.set(idx, true));
+        let mut b = BitArray::new(11);
+        (0..11).for_each(|idx| b.set(idx, true));
+
+        let xor = &a ^ &b;
+        assert_eq!(xor.len(), 11);
+        assert_eq!(xor.as_bytes(), [0b0001_1111, 0b1110_0000]);
+        assert_eq!(xor.count_ones(), 8);
+        assert_eq!((&b & &a).count_ones(), 3);
+        assert_eq!((a | b).count_ones(), 11);
+    }
+
+    #[test]
+    fn test_zip_words() {
+        let mut prbs = Prbs::new(PrbsKind::Prbs15);
+        for len in [0, 5, 64, 100, 129] {
+            let a: BitArray = prbs.by_ref().take(len).collect();
+            let b: BitArray = prbs.by_ref().take(len).collect();
+            assert_eq!(a.zip_words(&b, |x, y| x & y), Ok(&a & &b));
+            assert_eq!(a.zip_words(&b, |x, y| x | y), Ok(&a | &b));
+            assert_eq!(a.zip_words(&b, |x, y| x ^ y), Ok(&a ^ &b));
+
+            // Each bit is the majority of a, b and the bit of a after it in the word.
+            let majority = a
+                .zip_words(&b, |x, y| (x & y) | ((x ^ y) & (x << 1)))
+                .unwrap();
+            let expected = BitArray::from_fn(len, |i| {
+                let next = i % 64 != 63 && i + 1 < len && *a.get(i + 1);
+                let (x, y) = (*a.get(i), *b.get(i));
+                (x && y) || ((x ^ y) && next)
+            });
+            assert_eq!(majority, expected);
+
+            // A closure that sets every bit still leaves the padding clear.
+            let ones = a.zip_words(&b, |_, _| u64::MAX).unwrap();
+            assert!(ones.all());
+            assert_eq!(ones.count_ones(), len);
+            check_fresh(&ones, &vec![true; len]);
+        }
+        assert_eq!(
+            bits("101").zip_words(&bits("10"), |x, _| x),
+            Err(BitError::LengthMismatch { left: 3, right: 2 })
+        );
+    }
+
+    #[test]
+    fn test_hamming_distance() {
+        for len in [0, 1, 7, 8, 13, 64, 101] {
+            let mut prbs = Prbs::new(PrbsKind::Prbs15);
+            let mut a = BitArray::new(len);
+            let mut b = BitArray::new(len);
+            for idx in 0..len {
+                a.set(idx, prbs.next().unwrap().is_one());
+                b.set(idx, prbs.next().unwrap().is_one());
+            }
+            let naive = (0..len).filter(|&idx| a.get(idx) != b.get(idx)).count();
+            assert_eq!(a.hamming_distance(&b), Ok(naive));
+            assert_eq!(b.hamming_distance(&a), Ok(naive));
+            assert_eq!(a.hamming_distance(&a), Ok(0));
+        }
+
+        let err = BitArray::new(9).hamming_distance(&BitArray::new(16));
+        assert_eq!(err, Err(BitError::LengthMismatch { left: 9, right: 16 }));
+        assert_eq!(
+            err.unwrap_err().to_string(),
+            "lengths 9 and 16 do not match"
+        );
+    }
+
+    #[test]
+    fn test_format_hex() {
+        let arr = BitArray::from_bytes(&[0xDE, 0xAD, 0xBE, 0xEF]);
+        assert_eq!(format!("{:x}", arr), "deadbeef");
+        assert_eq!(format!("{:X}", arr), "DEADBEEF");
+        assert_eq!(format!("{:#x}", arr), "0xdeadbeef");
+        assert_eq!(format!("{:>12x}", arr), "    deadbeef");
+        assert_eq!(format!("{:#012X}", arr), "0x00DEADBEEF");
+
+        let partial = BitArray::from_vec(vec![0x0F, 0xFF], 12).unwrap();
+        assert_eq!(format!("{:x}", partial), "0ff0");
+        assert_eq!(format!("{:x}", BitArray::new(0)), "");
+        assert_eq!(format!("{:#x}", BitArray::new(3)), "0x00");
+    }
+
+    #[test]
+    fn test_int_conversions() {
+        let arr = BitArray::from(0x8000_0000u32);
+        assert_eq!(arr.len(), 32);
+        assert!(arr.get(0).is_one());
+        assert_eq!(arr.count_ones(), 1);
+        assert_eq!(arr.as_bytes(), [0x80, 0, 0, 0]);
+
+        assert_eq!(u8::try_from(&BitArray::from(0xA5u8)), Ok(0xA5));
+        assert_eq!(u16::try_from(&BitArray::from(0xBEEFu16)), Ok(0xBEEF));
+        assert_eq!(
+            u32::try_from(&BitArray::from(0xDEAD_BEEFu32)),
+            Ok(0xDEAD_BEEF)
+        );
+        let wide = 0x0123_4567_89AB_CDEF_u64;
+        assert_eq!(u64::try_from(&BitArray::from(wide)), Ok(wide));
+        assert_eq!(u64::try_from(&BitArray::from(u64::MAX)), Ok(u64::MAX));
+        let widest = 0xFEDC_BA98_7654_3210_0123_4567_89AB_CDEF_u128;
+        assert_eq!(u128::try_from(&BitArray::from(widest)), Ok(widest));
+
+        // Shorter arrays are read as narrower integers.
+        assert_eq!(u64::try_from(&BitArray::from(0xBEEFu16)), Ok(0xBEEF));
+        let mut arr = BitArray::new(11);
+        arr.set(0, true);
+        arr.set(10, true);
+        assert_eq!(u16::try_from(&arr), Ok(0b100_0000_0001));
+        assert_eq!(u8::try_from(&BitArray::new(0)), Ok(0));
+
+        assert_eq!(
+            u8::try_from(&arr),
+            Err(BitError::InvalidLength { len: 11, max: 8 })
+        );
+        assert!(u32::try_from(&BitArray::from(1u64)).is_err());
+    }
+
+    #[test]
+    fn test_get_set_bits() {
+        let arr =
+            BitArray::from_bytes(&[0xDE, 0xAD, 0xBE, 0xEF, 0x01, 0x23, 0x45, 0x67, 0x89, 0xAB]);
+        assert_eq!(arr.get_bits(0..8), 0xDE);
+        assert_eq!(arr.get_bits(4..12), 0xEA);
+        assert_eq!(arr.get_bits(3..14), 0b111_1010_1011);
+        // Spanning three bytes.
+        assert_eq!(arr.get_bits(6..22), 0b1010_1011_0110_1111);
+        assert_eq!(arr.get_bits(0..64), 0xDEAD_BEEF_0123_4567);
+        assert_eq!(arr.get_bits(12..76), 0xDBEE_F012_3456_789A);
+        assert_eq!(arr.get_bits(80..80), 0);
+
+        let mut prbs = Prbs::new(PrbsKind::Prbs15);
+        let mut arr = BitArray::new(150);
+        for idx in 0..150 {
+            arr.set(idx, prbs.next().unwrap().is_one());
+        }
+        for (start, end) in [
+            (0, 1),
+            (3, 14),
+            (6, 22),
+            (7, 71),
+            (20, 84),
+            (86, 150),
+            (149, 150),
+        ] {
+            let naive =
+                (start..end).fold(0, |acc, idx| (acc << 1) | u64::from(arr.get(idx).is_one()));
+            assert_eq!(arr.get_bits(start..end), naive);
+
+            let mut copy = arr.clone();
+            let width = end - start;
+            let value = 0x5555_5555_5555_5555 >> (64 - width);
+            copy.set_bits(start..end, value);
+            assert_eq!(copy.get_bits(start..end), value);
+            assert!((0..150)
+                .filter(|idx| !(start..end).contains(idx))
+                .all(|idx| copy.get(idx) == arr.get(idx)));
+            copy.set_bits(start..end, naive);
+            assert_eq!(copy, arr);
+        }
+
+        let mut arr = BitArray::new(13);
+        arr.set_bits(5..13, 0xFF);
+        assert_eq!(arr.as_bytes(), [0b0000_0111, 0b1111_1000]);
+    }
+
+    #[test]
+    #[should_panic(expected = "range 3..14 out of range for bit array of length 13")]
+    fn test_get_bits_out_of_range() {
+        BitArray::new(13).get_bits(3..14);
+    }
+
+    #[test]
+    #[should_panic(expected = "range 0..65 is wider than 64 bits")]
+    fn test_get_bits_too_wide() {
+        BitArray::new(100).get_bits(0..65);
+    }
+
+    #[test]
+    #[should_panic(expected = "value 0x10 does not fit in 4 bits")]
+    fn test_set_bits_too_wide() {
+        BitArray::new(8).set_bits(2..6, 0x10);
+    }
+
+    #[test]
+    fn test_mask() {
+        let mut arr = bits("0000000011111111");
+        arr.mask_or(0, Byte::from(0b1010_0000)).unwrap();
+        arr.mask_and(8, Byte::from(0b1100_0011)).unwrap();
+        assert_eq!(arr, bits("1010000011000011"));
+        arr.mask_xor(3, Byte::from(0xFF)).unwrap();
+        assert_eq!(arr, bits("1011111100100011"));
+
+        let mut arr = bits("111111111111111111111");
+        arr.mask_and(13, Byte::from(0b0110_1001)).unwrap();
+        assert_eq!(arr, bits("111111111111101101001"));
+    }
+
+    #[test]
+    fn test_mask_model() {
+        let arr: BitArray = Prbs::new(PrbsKind::Prbs15).take(30).collect();
+        let mask = [0b1100_1010, 0b0111_0001, 0b1000_0000];
+        let mask_bit = |i: usize| mask[i / 8] & (0x80 >> (i % 8)) != 0;
+        for len in [1, 8, 13, 17] {
+            for offset in (0..=30 - len).step_by(3) {
+                let mut ors = arr.clone();
+                let mut ands = arr.clone();
+                let mut xors = arr.clone();
+                ors.mask_or_bytes(offset, &mask, len).unwrap();
+                ands.mask_and_bytes(offset, &mask, len).unwrap();
+                xors.mask_xor_bytes(offset, &mask, len).unwrap();
+                for idx in 0..30 {
+                    let old = *arr.get(idx);
+                    let inside = (offset..offset + len).contains(&idx);
+                    let m = inside && mask_bit(idx - offset);
+                    assert_eq!(*ors.get(idx), old | m);
+                    assert_eq!(*ands.get(idx), if inside { old & m } else { old });
+                    assert_eq!(*xors.get(idx), old ^ m);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mask_errors() {
+        let mut arr = BitArray::new(21);
+        assert_eq!(
+            arr.mask_or(14, Byte::from(1)),
+            Err(BitError::OutOfBounds { index: 22, len: 21 })
+        );
+        assert_eq!(
+            arr.mask_xor_bytes(0, &[0xFF], 9),
+            Err(BitError::InvalidLength { len: 9, max: 8 })
+        );
+        assert_eq!(arr.mask_or(13, Byte::from(0xFF)), Ok(()));
+        assert_eq!(arr.count_ones(), 8);
+        assert_eq!(arr.mask_and_bytes(21, &[], 0), Ok(()));
+    }
+
+    #[test]
+    fn test_fill() {
+        let len = 100_003;
+        for (start, end) in [
+            (0, len),
+            (1, 2),
+            (5, 7),
+            (7, 9),
+            (8, 16),
+            (13, 10_013),
+            (99_990, len),
+        ] {
+            for val in [true, false] {
+                let mut arr = BitArray::new(len);
+                if !val {
+                    arr.set_range(0..len).unwrap();
+                }
+                arr.fill(start..end, val).unwrap();
+                assert_eq!(arr.get(start), Bit::from(val));
+                assert_eq!(arr.get(end - 1), Bit::from(val));
+                if start > 0 {
+                    assert_eq!(arr.get(start - 1), Bit::from(!val));
+                }
+                if end < len {
+                    assert_eq!(arr.get(end), Bit::from(!val));
+                }
+                let ones = if val {
+                    end - start
+                } else {
+                    len - (end - start)
+                };
+                assert_eq!(arr.count_ones(), ones);
+            }
+        }
+
+        let mut arr = BitArray::new(11);
+        arr.set_range(3..11).unwrap();
+        assert_eq!(arr.as_bytes(), [0b0001_1111, 0b1110_0000]);
+        arr.clear_range(4..4).unwrap();
+        arr.clear_range(0..5).unwrap();
+        assert_eq!(arr.as_bytes(), [0b0000_0111, 0b1110_0000]);
+        assert_eq!(
+            arr.set_range(4..12),
+            Err(BitError::OutOfBounds { index: 12, len: 11 })
+        );
+        let before = arr.clone();
+        let (start, end) = (5, 3);
+        let err = BitError::DecreasingRange { start: 5, end: 3 };
+        assert_eq!(arr.fill(start..end, true), Err(err.clone()));
+        assert_eq!(err.to_string(), "range 5..3 is decreasing");
+        // A decreasing range is reported as such even when it also extends past the end.
+        let (start, end) = (20, 15);
+        assert_eq!(
+            arr.clear_range(start..end),
+            Err(BitError::DecreasingRange { start: 20, end: 15 })
+        );
+        assert_eq!(arr, before);
+    }
+
+    #[test]
+    fn test_toggle() {
+        let mut arr = BitArray::new(13);
+        assert_eq!(arr.toggle(0), Bit::ONE);
+        assert_eq!(arr.toggle(12), Bit::ONE);
+        assert_eq!(arr.as_bytes(), [0b1000_0000, 0b0000_1000]);
+        assert_eq!(arr.toggle(0), Bit::ZERO);
+
+        let original = arr.clone();
+        for idx in 0..13 {
+            arr.toggle(idx);
+        }
+        assert_eq!(arr.count_ones(), 12);
+        assert_eq!(arr.as_bytes(), [0xFF, 0b1111_0000]);
+        (0..13).for_each(|idx| {
+            arr.toggle(idx);
+        });
+        assert_eq!(arr, original);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 13 out of range for bit array of length 13")]
+    fn test_toggle_out_of_range() {
+        BitArray::new(13).toggle(13);
+    }
+
+    #[test]
+    fn test_test_and_update() {
+        let mut arr = BitArray::new(70);
+        assert_eq!(arr.test_and_set(65), Bit::ZERO);
+        assert_eq!(arr.test_and_set(65), Bit::ONE);
+        assert_eq!(arr.test_and_set(65), Bit::ONE);
+        assert_eq!(arr.count_ones(), 1);
+
+        assert_eq!(arr.test_and_clear(65), Bit::ONE);
+        assert_eq!(arr.test_and_clear(65), Bit::ZERO);
+        assert!(arr.none());
+
+        assert_eq!(arr.test_and_toggle(3), Bit::ZERO);
+        assert_eq!(arr.test_and_toggle(3), Bit::ONE);
+        assert_eq!(arr.test_and_toggle(3), Bit::ZERO);
+        assert_eq!(arr.get(3), Bit::ONE);
+        assert_eq!(arr.count_ones(), 1);
+    }
+
+    #[test]
+    fn test_try_test_and_update() {
+        let mut arr = BitArray::new(9);
+        assert_eq!(arr.try_test_and_set(8), Ok(Bit::ZERO));
+        assert_eq!(arr.try_test_and_toggle(8), Ok(Bit::ONE));
+        assert_eq!(arr.try_test_and_clear(0), Ok(Bit::ZERO));
+
+        let err = BitError::OutOfBounds { index: 9, len: 9 };
+        assert_eq!(arr.try_test_and_set(9), Err(err.clone()));
+        assert_eq!(arr.try_test_and_clear(9), Err(err.clone()));
+        assert_eq!(arr.try_test_and_toggle(9), Err(err));
+        assert!(arr.none());
+    }
+
+    #[test]
+    #[should_panic(expected = "index 9 out of range for bit array of length 9")]
+    fn test_test_and_set_out_of_range() {
+        BitArray::new(9).test_and_set(9);
+    }
+
+    #[test]
+    fn test_set_clear_toggle_all() {
+        let len = 8 * 4096 + 5;
+        let original: BitArray = Prbs::new(PrbsKind::Prbs15).take(len).collect();
+        let mut arr = original.clone();
+        arr.set_all();
+        assert_eq!(arr.count_ones(), len);
+        assert!(arr.all());
+        // The padding of the final byte stays zero.
+        assert_eq!(arr.as_bytes()[len / 8], 0b1111_1000);
+
+        arr.toggle_all();
+        assert!(arr.none());
+        assert_eq!(arr.as_bytes()[len / 8], 0);
+
+        arr = original.clone();
+        arr.toggle_all();
+        assert_eq!(arr.count_ones(), original.count_zeros());
+        assert_eq!(arr.as_bytes()[len / 8] & 0b0000_0111, 0);
+        arr.toggle_all();
+        assert_eq!(arr, original);
+
+        arr.clear_all();
+        assert_eq!(arr.count_ones(), 0);
+        assert_eq!(arr.len(), len);
+
+        let mut empty = BitArray::new(0);
+        empty.set_all();
+        empty.toggle_all();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_concat() {
+        let header = bits("101");
+        let payload = bits("1100111100001");
+        let packet = header.concat(&payload);
+        assert_eq!(packet.len(), 16);
+        for idx in 0..3 {
+            assert_eq!(packet.get(idx), header.get(idx));
+        }
+        for idx in 0..13 {
+            assert_eq!(packet.get(3 + idx), payload.get(idx));
+        }
+        assert_eq!(packet.as_bytes(), [0b1011_1001, 0b1110_0001]);
+
+        let empty = BitArray::new(0);
+        assert_eq!(header.concat(&empty), header);
+        assert_eq!(empty.concat(&payload), payload);
+        assert_eq!(empty.concat(&empty), empty);
+    }
+
+    #[test]
+    fn test_concat_all() {
+        let bits: Vec<bool> = Prbs::new(PrbsKind::Prbs15)
+            .take(500)
+            .map(|bit| *bit)
+            .collect();
+        let mut fields = Vec::new();
+        let mut start = 0;
+        for len in [3, 0, 64, 1, 130, 7, 8, 200] {
+            fields.push(BitArray::from(&bits[start..start + len]));
+            start += len;
+        }
+        let joined = BitArray::concat_all(&fields);
+        assert_eq!(joined, BitArray::from(&bits[..start]));
+        assert_eq!(BitArray::concat_all(&[]), BitArray::new(0));
+    }
+
+    #[test]
+    fn test_shift() {
+        for len in [1, 13, 30, 71] {
+            let arr: BitArray = Prbs::new(PrbsKind::Prbs15).take(len).collect();
+            let model: Vec<bool> = arr.iter().map(|bit| *bit).collect();
+            for n in [0, 1, 7, 8, 9, len - 1, len, len + 5] {
+                let left: Vec<bool> = (0..len).map(|i| i + n < len && model[i + n]).collect();
+                let right: Vec<bool> = (0..len).map(|i| i >= n && model[i - n]).collect();
+                check_fresh(&(&arr << n), &left);
+                check_fresh(&(&arr >> n), &right);
+                let mut shifted = arr.clone();
+                shifted <<= n;
+                shifted >>= n;
+                assert_eq!(shifted, &arr & &(!BitArray::new(len) >> n));
+            }
+        }
+        let mut arr = bits("1011001110");
+        arr.shift_right(3);
+        assert_eq!(arr, bits("0001011001"));
+        arr.shift_left(9);
+        assert_eq!(arr, bits("1000000000"));
+        arr.shift_left(1);
+        assert_eq!(arr, BitArray::new(10));
+    }
+
+    #[test]
+    fn test_copy_bits_from() {
+        let src: BitArray = Prbs::new(PrbsKind::Prbs15).take(200).collect();
+        let mut dst = BitArray::new(190);
+        dst.set_all();
+        // The source starts 3 bits further into its byte than the destination does.
+        dst.copy_bits_from(10, &src, 13..163).unwrap();
+        let expected: Vec<bool> = (0..190)
+            .map(|i| !(10..160).contains(&i) || *src.get(i + 3))
+            .collect();
+        check_fresh(&dst, &expected);
+
+        let before = dst.clone();
+        assert_eq!(dst.copy_bits_from(190, &src, 200..200), Ok(()));
+        assert_eq!(dst, before);
+        assert_eq!(
+            dst.copy_bits_from(180, &src, 0..11),
+            Err(BitError::OutOfBounds {
+                index: 191,
+                len: 190
+            })
+        );
+        assert_eq!(
+            dst.copy_bits_from(0, &src, 150..201),
+            Err(BitError::OutOfBounds {
+                index: 201,
+                len: 200
+            })
+        );
+        assert_eq!(dst, before);
+    }
+
+    #[test]
+    fn test_copy_within() {
+        let arr: BitArray = Prbs::new(PrbsKind::Prbs15).take(301).collect();
+        let model: Vec<bool> = arr.iter().map(|bit| *bit).collect();
+        for (src, dst) in [
+            (5..205, 9),
+            (9..209, 5),
+            (0..150, 151),
+            (100..300, 100),
+            (7..7, 301),
+        ] {
+            let mut expected = model.clone();
+            expected.copy_within(src.clone(), dst);
+            let mut copied = arr.clone();
+            copied.copy_within(src, dst).unwrap();
+            check_fresh(&copied, &expected);
+        }
+        assert_eq!(
+            arr.clone().copy_within(0..10, 292),
+            Err(BitError::OutOfBounds {
+                index: 302,
+                len: 301
+            })
+        );
+    }
+
+    #[test]
+    fn test_rotate() {
+        for len in [1, 8, 9, 64, 1000] {
+            let original: BitArray = Prbs::new(PrbsKind::Prbs15).take(len).collect();
+            let model: Vec<bool> = original.iter().map(|bit| *bit).collect();
+            for n in [0, 1, 3, 8, 63, 64, 65, 999, 1000, 2501] {
+                let mut left = original.clone();
+                left.rotate_left(n);
+                let mut expected = model.clone();
+                expected.rotate_left(n % len);
+                assert_eq!(left, BitArray::from(expected.clone()));
+
+                let mut right = original.clone();
+                right.rotate_right(n);
+                expected = model.clone();
+                expected.rotate_right(n % len);
+                assert_eq!(right, BitArray::from(expected));
+
+                right.rotate_left(n);
+                assert_eq!(right, original);
+            }
+        }
+
+        let mut empty = BitArray::new(0);
+        empty.rotate_left(3);
+        empty.rotate_right(3);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_ordering() {
+        let mut arrays: Vec<BitArray> = ["1", "", "0110", "011", "01101", "1000", "0", "0111"]
+            .iter()
+            .map(|digits| bits(digits))
+            .collect();
+        arrays.sort();
+        let sorted: Vec<BitArray> = ["", "0", "011", "0110", "01101", "0111", "1", "1000"]
+            .iter()
+            .map(|digits| bits(digits))
+            .collect();
+        assert_eq!(arrays, sorted);
+
+        // Differences past the first block are still found.
+        let long: BitArray = Prbs::new(PrbsKind::Prbs15).take(200).collect();
+        let mut other = long.clone();
+        other.toggle(150);
+        assert_eq!(long.cmp(&other), long.get(150).cmp(&other.get(150)));
+        assert_eq!(long.partial_cmp(&long), Some(Ordering::Equal));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_hash_dedup() {
+        use std::collections::HashSet;
+
+        let mut trimmed = bits("1011001110");
+        trimmed.set(9, true);
+        trimmed.set(9, false);
+        let mut grown = bits("1011");
+        grown.union_with(&bits("0000001110"));
+        let set: HashSet<BitArray> = [bits("1011001110"), trimmed, grown, bits("101100111")]
+            .iter()
+            .cloned()
+            .collect();
+        assert_eq!(set.len(), 2);
+    }
+
+    /// Checks an array against a freshly built one, including the raw padding of its final byte.
+    fn check_fresh(arr: &BitArray, bools: &[bool]) {
+        assert_eq!(arr, &BitArray::from(bools));
+        assert_eq!(arr.count_ones(), bools.iter().filter(|&&b| b).count());
+        if let Some(&last) = arr.as_bytes().last() {
+            let pad = arr.as_bytes().len() * 8 - bools.len();
+            assert_eq!(last & !(u8::MAX << pad), 0);
+        }
+    }
+
+    #[test]
+    fn test_padding_invariant() {
+        for len in [9, 13, 63, 70, 130] {
+            let model: Vec<bool> = Prbs::new(PrbsKind::Prbs15)
+                .take(len)
+                .map(|bit| *bit)
+                .collect();
+            let inverse: Vec<bool> = model.iter().map(|b| !b).collect();
+
+            // Setting then clearing the trailing bits.
+            let mut arr = BitArray::from(&model[..]);
+            arr.set(len - 1, true);
+            arr.set(len - 1, model[len - 1]);
+            arr.toggle(len - 2);
+            arr.toggle(len - 2);
+            check_fresh(&arr, &model);
+
+            // Bulk writes up to the end.
+            arr.fill(len - 5..len, true).unwrap();
+            arr.set_bits(len - 5..len, 0);
+            let mut expected = model.clone();
+            expected[len - 5..].iter_mut().for_each(|b| *b = false);
+            check_fresh(&arr, &expected);
+
+            // Negation, reversal and rotation.
+            let mut arr = !BitArray::from(&model[..]);
+            let mut expected = inverse.clone();
+            check_fresh(&arr, &expected);
+            arr.reverse();
+            expected.reverse();
+            check_fresh(&arr, &expected);
+            arr.rotate_left(3);
+            expected.rotate_left(3);
+            check_fresh(&arr, &expected);
+
+            // Combining with longer and shorter arrays.
+            let mut arr = BitArray::from(&model[..]);
+            arr ^= &!BitArray::new(len + 3);
+            let mut expected = inverse.clone();
+            expected.extend([true; 3]);
+            check_fresh(&arr, &expected);
+            arr &= &!BitArray::new(len);
+            expected[len..].iter_mut().for_each(|b| *b = false);
+            check_fresh(&arr, &expected);
+
+            // Growing then shrinking a vector.
+            let mut vec: BitVec = model.iter().copied().collect();
+            (0..3).for_each(|_| vec.push(true));
+            (0..3).for_each(|_| {
+                vec.pop();
+            });
+            vec.remove(len - 1);
+            vec.insert(len - 1, model[len - 1]);
+            check_fresh(&BitArray::from(vec), &model);
+
+            let arr = BitArray::from_vec(vec![0xFF; len.div_ceil(8)], len).unwrap();
+            check_fresh(&arr, &[true; 130][..len]);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_serialize_round_trip() {
+        use std::io::Cursor;
+
+        for len in [0, 1, 8, 13, 64, 1000] {
+            let arr: BitArray = Prbs::new(PrbsKind::Prbs15).take(len).collect();
+            let mut buf = Vec::new();
+            let written = arr.write_to(&mut buf).unwrap();
+            assert_eq!(written, buf.len());
+            assert_eq!(written, 13 + len.div_ceil(8));
+            assert_eq!(BitArray::read_from(Cursor::new(&buf)).unwrap(), arr);
+        }
+
+        let mut buf = Vec::new();
+        bits("1011").write_to(&mut buf).unwrap();
+        assert_eq!(buf, b"BITA\x01\0\0\0\0\0\0\0\x04\xB0");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_serialize_invalid() {
+        use std::io::{Cursor, ErrorKind};
+
+        let read = |buf: &[u8]| BitArray::read_from(Cursor::new(buf)).unwrap_err().kind();
+        let mut buf = Vec::new();
+        bits("1011001111").write_to(&mut buf).unwrap();
+
+        // The declared length needs a third payload byte.
+        let mut longer = buf.clone();
+        longer[12] = 17;
+        assert_eq!(read(&longer), ErrorKind::InvalidData);
+        // The payload is cut short, as is the header.
+        assert_eq!(read(&buf[..buf.len() - 1]), ErrorKind::InvalidData);
+        assert_eq!(read(&buf[..7]), ErrorKind::InvalidData);
+        // A shorter declared length leaves set bits in the padding.
+        let mut shorter = buf.clone();
+        shorter[12] = 9;
+        assert_eq!(read(&shorter), ErrorKind::InvalidData);
+        let mut magic = buf.clone();
+        magic[0] = b'X';
+        assert_eq!(read(&magic), ErrorKind::InvalidData);
+        let mut version = buf;
+        version[4] = 2;
+        assert_eq!(read(&version), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_repeat() {
+        assert_eq!(bits("101").repeat(3), bits("101101101"));
+        assert_eq!(bits("101").repeat(1), bits("101"));
+        assert_eq!(bits("101").repeat(0), bits(""));
+        assert_eq!(bits("").repeat(5), bits(""));
+        assert_eq!(BitArray::tiled(&bits("110"), 7), bits("1101101"));
+        assert_eq!(BitArray::tiled(&bits("110"), 2), bits("11"));
+        assert_eq!(BitArray::tiled(&bits("110"), 0), bits(""));
+        assert_eq!(BitArray::tiled(&bits(""), 0), bits(""));
+
+        let mut prbs = Prbs::new(PrbsKind::Prbs15);
+        for pattern_len in [1, 3, 7, 63, 64, 65, 100] {
+            let pattern: BitArray = prbs.by_ref().take(pattern_len).collect();
+            for n in [0, 1, 2, 5, 17] {
+                let mut naive = BitArray::new(0);
+                (0..n).for_each(|_| naive.append_bits(&pattern));
+                assert_eq!(pattern.repeat(n), naive);
+                for total in [0, 1, pattern_len * n / 2 + 1, pattern_len * n] {
+                    let expected = BitArray::from_fn(total, |i| *pattern.get(i % pattern_len));
+                    assert_eq!(BitArray::tiled(&pattern, total), expected);
+                }
+            }
+        }
+
+        let big = bits("011").repeat(1_000_000);
+        assert_eq!(big.len(), 3_000_000);
+        assert_eq!(big.count_ones(), 2_000_000);
+        assert_eq!(big.get_bits(2_999_997..3_000_000), 0b011);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot tile an empty pattern")]
+    fn test_tiled_empty_pattern() {
+        BitArray::tiled(&bits(""), 1);
+    }
+
+    #[test]
+    fn test_eq_ignore_zeros() {
+        let check = |a: &str, b: &str, trailing: bool, leading: bool| {
+            let (a, b) = (bits(a), bits(b));
+            assert_eq!(a.eq_ignore_trailing_zeros(&b), trailing);
+            assert_eq!(b.eq_ignore_trailing_zeros(&a), trailing);
+            assert_eq!(a.eq_ignore_leading_zeros(&b), leading);
+            assert_eq!(b.eq_ignore_leading_zeros(&a), leading);
+        };
+        check("101", "10100", true, false);
+        check("101", "00101", false, true);
+        check("101", "101", true, true);
+        check("101", "100", false, false);
+        check("", "000", true, true);
+        check("", "010", false, false);
+        // A one in the padded region breaks the match.
+        check("101", "10101", false, false);
+        check("101", "01101", false, false);
+        check("0", "", true, true);
+
+        let mut prbs = Prbs::new(PrbsKind::Prbs15);
+        for (len, extra) in [(0, 1), (5, 60), (63, 1), (64, 64), (65, 130), (200, 7)] {
+            let arr: BitArray = prbs.by_ref().take(len).collect();
+            let padded_right = arr.concat(&BitArray::new(extra));
+            let padded_left = BitArray::new(extra).concat(&arr);
+            assert!(arr.eq_ignore_trailing_zeros(&padded_right));
+            assert!(padded_left.eq_ignore_leading_zeros(&arr));
+            for idx in [0, extra / 2, extra - 1] {
+                let mut dirty = padded_right.clone();
+                dirty.set(len + idx, true);
+                assert!(!arr.eq_ignore_trailing_zeros(&dirty));
+                let mut dirty = padded_left.clone();
+                dirty.set(idx, true);
+                assert!(!dirty.eq_ignore_leading_zeros(&arr));
+            }
+        }
+    }
+
+    #[test]
+    fn test_find_run() {
+        let arr = bits("1100010000111");
+        assert_eq!(arr.find_zero_run(3), Some(2));
+        assert_eq!(arr.find_zero_run(4), Some(6));
+        assert_eq!(arr.find_zero_run(5), None);
+        assert_eq!(arr.find_zero_run_at_or_after(3, 2), Some(3));
+        assert_eq!(arr.find_zero_run_at_or_after(3, 3), Some(6));
+        assert_eq!(arr.find_zero_run_at_or_after(4, 3), Some(6));
+        assert_eq!(arr.find_one_run(3), Some(10));
+        assert_eq!(arr.find_one_run(2), Some(0));
+        assert_eq!(arr.find_zero_run(0), Some(0));
+        assert_eq!(arr.find_zero_run_at_or_after(13, 0), Some(13));
+        assert_eq!(arr.find_zero_run_at_or_after(14, 0), None);
+        assert_eq!(arr.find_zero_run(14), None);
+        assert_eq!(bits("").find_zero_run(0), Some(0));
+        assert_eq!(bits("").find_one_run(1), None);
+
+        // An exact fit at the very end, after many gaps that are too short.
+        let mut arr = BitArray::from_fn(1000, |i| i % 7 == 0);
+        arr.fill(990..1000, false).unwrap();
+        assert_eq!(arr.find_zero_run(7), Some(988));
+        assert_eq!(arr.find_zero_run(12), Some(988));
+        assert_eq!(arr.find_zero_run(13), None);
+
+        // Runs straddling block boundaries, after whole blocks of ones.
+        let mut arr = BitArray::from_fn(300, |_| true);
+        arr.fill(60..70, false).unwrap();
+        arr.fill(190..260, false).unwrap();
+        assert_eq!(arr.find_zero_run(10), Some(60));
+        assert_eq!(arr.find_zero_run(11), Some(190));
+        assert_eq!(arr.find_zero_run_at_or_after(61, 5), Some(61));
+        assert_eq!(arr.find_zero_run_at_or_after(66, 5), Some(190));
+        assert_eq!(arr.find_zero_run(70), Some(190));
+        assert_eq!(arr.find_zero_run(71), None);
+        assert_eq!(arr.find_one_run(120), Some(70));
+    }
+
+    #[test]
+    fn test_find_run_matches_naive() {
+        fn naive(arr: &BitArray, from: usize, n: usize, val: bool) -> Option<usize> {
+            (from..=arr.len().checked_sub(n)?)
+                .find(|&start| (start..start + n).all(|i| *arr.get(i) == val))
+        }
+
+        let mut prbs = Prbs::new(PrbsKind::Prbs15);
+        for len in [0, 1, 63, 64, 65, 200] {
+            // Sparse ones give long zero runs, and dense ones give long one runs.
+            let sparse = BitArray::from_fn(len, |_| prbs.by_ref().take(3).all(|bit| bit.is_one()));
+            for arr in [sparse.clone(), !sparse] {
+                for n in [1, 2, 5, 9, 20, 64] {
+                    for from in [0, 3, len / 2] {
+                        let from = from.min(len);
+                        assert_eq!(
+                            arr.find_zero_run_at_or_after(from, n),
+                            naive(&arr, from, n, false)
+                        );
+                    }
+                    assert_eq!(arr.find_zero_run(n), naive(&arr, 0, n, false));
+                    assert_eq!(arr.find_one_run(n), naive(&arr, 0, n, true));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_split() {
+        let pieces = |arr: &str, n: usize, delim: &str| -> Vec<BitArray> {
+            bits(arr).splitn(n, &bits(delim)).unwrap().collect()
+        };
+        let all = |arr: &str, delim: &str| -> Vec<BitArray> {
+            bits(arr).split(&bits(delim)).unwrap().collect()
+        };
+        // "aa,a,,a,".split(',') == ["aa", "a", "", "a", ""]
+        assert_eq!(
+            all("00101101", "1"),
+            [bits("00"), bits("0"), bits(""), bits("0"), bits("")]
+        );
+        // ",".split(',') == ["", ""], and "".split(',') == [""]
+        assert_eq!(all("1", "1"), [bits(""), bits("")]);
+        assert_eq!(all("", "1"), [bits("")]);
+        // "aaa".split(',') == ["aaa"]
+        assert_eq!(all("000", "1"), [bits("000")]);
+        // "aaaaa".split("aa") == ["", "", "a"]
+        assert_eq!(all("11111", "11"), [bits(""), bits(""), bits("1")]);
+        // "a,b,c".splitn(2, ',') == ["a", "b,c"], and splitn(0) yields nothing
+        assert_eq!(pieces("01010", 2, "1"), [bits("0"), bits("010")]);
+        assert_eq!(pieces("01010", 1, "1"), [bits("01010")]);
+        assert!(pieces("01010", 0, "1").is_empty());
+        assert_eq!(pieces("01010", 10, "1"), all("01010", "1"));
+
+        assert_eq!(
+            bits("0101").split(&bits("")).unwrap_err(),
+            BitError::EmptyPattern
+        );
+        assert_eq!(BitError::EmptyPattern.to_string(), "pattern is empty");
+
+        let (arr, one) = (bits("010"), bits("1"));
+        let mut split = arr.split(&one).unwrap();
+        assert_eq!(split.by_ref().count(), 2);
+        assert_eq!(split.next(), None);
+    }
+
+    #[test]
+    fn test_split_rejoins() {
+        let mut prbs = Prbs::new(PrbsKind::Prbs15);
+        for delim in ["1", "00", "101", "1100"] {
+            let delim = bits(delim);
+            for len in [0, 1, 5, 63, 64, 65, 200] {
+                let arr: BitArray = prbs.by_ref().take(len).collect();
+                let pieces: Vec<BitArray> = arr.split(&delim).unwrap().collect();
+                for piece in &pieces[..pieces.len() - 1] {
+                    assert!(piece.find(&delim).is_none());
+                }
+                let mut joined = pieces[0].clone();
+                for piece in &pieces[1..] {
+                    joined.append_bits(&delim);
+                    joined.append_bits(piece);
+                }
+                assert_eq!(joined, arr);
+            }
+        }
+    }
+
+    #[test]
+    fn test_render_blocks() {
+        assert_eq!(bits("1011").render_blocks(), "█·██");
+        assert_eq!(BitArray::new(0).render_blocks(), "");
+        let arr = bits("1100101");
+        assert_eq!(arr.render_blocks_with('#', '.', None), "##..#.#");
+        assert_eq!(arr.render_blocks_with('#', '.', Some(3)), "##.\n.#.\n#");
+        assert_eq!(arr.render_blocks_with('1', '0', Some(7)), "1100101");
+        assert_eq!(
+            arr.render_blocks_with('1', '0', Some(1)),
+            "1\n1\n0\n0\n1\n0\n1"
+        );
+        assert_eq!(BitArray::new(0).render_blocks_with('1', '0', Some(4)), "");
+    }
+
+    #[test]
+    #[should_panic(expected = "line width must be non-zero")]
+    fn test_render_blocks_zero_width() {
+        bits("1").render_blocks_with('1', '0', Some(0));
+    }
+
+    #[test]
+    fn test_hex() {
+        let expected = BitArray::from_bytes(&[0xDE, 0xAD, 0xBE, 0xEF]);
+        for s in ["deadbeef", "0xDEADBEEF", "0XdeadBEEF"] {
+            assert_eq!(BitArray::from_hex(s), Ok(expected.clone()));
+        }
+        assert_eq!(expected.to_hex_nibbles(), "deadbeef");
+        assert_eq!(expected.to_hex_nibbles_upper(), "DEADBEEF");
+        assert_eq!(expected.to_hex_nibbles(), format!("{:x}", expected));
+
+        let odd = BitArray::from_hex("abc").unwrap();
+        assert_eq!(odd, bits("101010111100"));
+        assert_eq!(odd.to_hex_nibbles(), "abc");
+        assert_eq!(bits("101101").to_hex_nibbles(), "b4");
+        assert_eq!(BitArray::new(0).to_hex_nibbles(), "");
+
+        for len in [4, 8, 60, 132] {
+            let arr: BitArray = Prbs::new(PrbsKind::Prbs15).take(len).collect();
+            assert_eq!(BitArray::from_hex(&arr.to_hex_nibbles()), Ok(arr.clone()));
+            assert_eq!(BitArray::from_hex(&arr.to_hex_nibbles_upper()), Ok(arr));
+        }
+    }
+
+    #[test]
+    fn test_hex_nibbles_vs_format() {
+        // Nibble-granular digits stop after the last used nibble; `{:x}` always writes whole bytes.
+        let odd = bits("101010111100");
+        assert_eq!(odd.to_hex_nibbles(), "abc");
+        assert_eq!(format!("{:x}", odd), "abc0");
+        assert_eq!(format!("{:X}", odd), "ABC0");
+        assert_eq!(bits("1").to_hex_nibbles(), "8");
+        assert_eq!(format!("{:x}", bits("1")), "80");
+        // They agree once more than half of the last byte is used.
+        assert_eq!(bits("101101").to_hex_nibbles(), "b4");
+        assert_eq!(format!("{:x}", bits("101101")), "b4");
+
+        for len in 0..40 {
+            let arr: BitArray = Prbs::new(PrbsKind::Prbs15).take(len).collect();
+            let nibbles = arr.to_hex_nibbles();
+            let bytes = format!("{:x}", arr);
+            assert_eq!(nibbles.len(), len.div_ceil(4));
+            assert_eq!(bytes.len(), len.div_ceil(8) * 2);
+            assert!(bytes.starts_with(&nibbles));
+            assert_eq!(nibbles == bytes, len.div_ceil(4) % 2 == 0);
+        }
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_hex_deprecated_names() {
+        let arr = bits("101010111100");
+        assert_eq!(arr.to_hex(), arr.to_hex_nibbles());
+        assert_eq!(arr.to_hex_upper(), "ABC");
+    }
+
+    #[test]
+    fn test_hex_invalid() {
+        assert_eq!(BitArray::from_hex(""), Err(ParseHexError::Empty));
+        assert_eq!(BitArray::from_hex("0x"), Err(ParseHexError::Empty));
+        assert_eq!(
+            BitArray::from_hex("dead_beef"),
+            Err(ParseHexError::InvalidDigit('_'))
+        );
+        assert_eq!(
+            BitArray::from_hex("0xfg"),
+            Err(ParseHexError::InvalidDigit('g'))
+        );
+        assert_eq!(
+            BitArray::from_hex("00x1").unwrap_err().to_string(),
+            "invalid hex digit 'x'"
+        );
+    }
+
+    #[test]
+    fn test_hex_error_is_error() {
+        use alloc::boxed::Box;
+
+        fn parse_boxed(s: &str) -> Result<BitArray, Box<dyn core::error::Error>> {
+            Ok(BitArray::from_hex(s)?)
+        }
+        assert_eq!(parse_boxed("a5").unwrap(), bits("10100101"));
+        let err = parse_boxed("").unwrap_err();
+        assert_eq!(err.to_string(), "no hex digits to parse");
+    }
+
+    /// The low `len` bits of `value`, as an array with the most significant bit first.
+    fn from_uint(value: u128, len: usize) -> BitArray {
+        (0..len)
+            .map(|i| (value >> (len - 1 - i)) & 1 == 1)
+            .collect()
+    }
+
+    #[test]
+    fn test_arithmetic_shr() {
+        assert_eq!(bits("0110").arithmetic_shr(1), bits("0011"));
+        assert_eq!(bits("1010").arithmetic_shr(1), bits("1101"));
+        assert_eq!(bits("1010").logical_shr(1), bits("0101"));
+        assert_eq!(bits("1010").arithmetic_shr(0), bits("1010"));
+        assert_eq!(bits("1010").arithmetic_shr(4), bits("1111"));
+        assert_eq!(bits("1010").arithmetic_shr(100), bits("1111"));
+        assert_eq!(bits("0110").arithmetic_shr(100), bits("0000"));
+        assert_eq!(bits("").arithmetic_shr(3), bits(""));
+        let mut empty = bits("");
+        empty.arithmetic_shr_assign(1);
+        assert!(empty.is_empty());
+
+        let mut prbs = Prbs::new(PrbsKind::Prbs15);
+        for len in [1, 7, 8, 9, 31, 63, 64] {
+            for _ in 0..4 {
+                let arr: BitArray = prbs.by_ref().take(len).collect();
+                let value = ((arr.get_bits(0..len) << (64 - len)) as i64) >> (64 - len);
+                for n in [1, 7, 8, len - 1, len, len + 5] {
+                    let expected = value >> n.min(63);
+                    let mask = u64::MAX >> (64 - len);
+                    assert_eq!(
+                        arr.arithmetic_shr(n),
+                        from_uint(u128::from(expected as u64 & mask), len)
+                    );
+                    let mut shifted = arr.clone();
+                    shifted.arithmetic_shr_assign(n);
+                    assert_eq!(shifted, arr.arithmetic_shr(n));
+                    assert_eq!(arr.logical_shr(n), &arr >> n);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_overflowing_arithmetic() {
+        let mut prbs = Prbs::new(PrbsKind::Prbs15);
+        let mut next = |len: usize| -> u128 {
+            let value = (0..128).fold(0, |acc, _| {
+                (acc << 1) | u128::from(prbs.next().unwrap().as_u8())
+            });
+            value >> (128 - len)
+        };
+        for len in [1, 7, 8, 9, 63, 64, 65, 100, 128] {
+            let max = u128::MAX >> (128 - len);
+            let mut pairs = vec![(max, 1), (max, max), (0, 1), (0, max), (1, 0)];
+            pairs.extend((0..20).map(|_| (next(len), next(len))));
+            for (x, y) in pairs {
+                let (a, b) = (from_uint(x, len), from_uint(y, len));
+                let (sum, carry) = a.overflowing_add(&b).unwrap();
+                let (diff, borrow) = a.overflowing_sub(&b).unwrap();
+                let wide = x.wrapping_add(y);
+                let expected_carry = wide & max < x || (len == 128 && wide < x);
+                assert_eq!(
+                    (sum.to_u128(128).unwrap(), carry),
+                    (wide & max, expected_carry)
+                );
+                assert_eq!(
+                    (diff.to_u128(128).unwrap(), borrow),
+                    (x.wrapping_sub(y) & max, y > x)
+                );
+                assert_eq!(sum.len(), len);
+
+                let mut inc = a.clone();
+                let wrapped = inc.increment();
+                assert_eq!(inc.to_u128(128).unwrap(), x.wrapping_add(1) & max);
+                assert_eq!(wrapped, x == max);
+            }
+        }
+
+        // 130 bits: 2^129 + 2^64 + 1 plus 2^129 + (2^64 - 1) carries into bit 65 and out of the top.
+        let mut a = BitArray::new(130);
+        a.set(0, true);
+        a.set(129 - 64, true);
+        a.set(129, true);
+        let mut b = BitArray::new(130);
+        b.set(0, true);
+        b.fill(130 - 64..130, true).unwrap();
+        let (sum, carry) = a.overflowing_add(&b).unwrap();
+        assert!(carry);
+        assert_eq!(sum.iter_ones().collect::<Vec<_>>(), [129 - 65]);
+        let (diff, borrow) = sum.overflowing_sub(&b).unwrap();
+        assert!(borrow);
+        assert_eq!(diff, a);
+
+        assert_eq!(
+            a.overflowing_add(&BitArray::new(3)),
+            Err(BitError::LengthMismatch {
+                left: 130,
+                right: 3
+            })
+        );
+        assert!(BitArray::new(0).increment());
+    }
+
+    #[test]
+    fn test_div_rem_u32() {
+        let mut prbs = Prbs::new(PrbsKind::Prbs15);
+        for len in [1, 7, 32, 64, 65, 100, 128] {
+            for divisor in [1, 2, 3, 10, 64, 1 << 31, 0xFFFF_FFFB, u32::MAX] {
+                let value = (0..len).fold(0u128, |acc, _| {
+                    (acc << 1) | u128::from(prbs.next().unwrap().as_u8())
+                });
+                let (quotient, rem) = from_uint(value, len).div_rem_u32(divisor).unwrap();
+                let divisor = u128::from(divisor);
+                assert_eq!(quotient, from_uint(value / divisor, len));
+                assert_eq!(u128::from(rem), value % divisor);
+            }
+        }
+        // Dividing by 2^k shifts right by k.
+        let arr: BitArray = prbs.take(300).collect();
+        for k in [0, 1, 5, 31] {
+            let (quotient, rem) = arr.div_rem_u32(1 << k).unwrap();
+            assert_eq!(quotient, &arr >> k);
+            assert_eq!(u64::from(rem), arr.get_bits(300 - k..300));
+        }
+        assert_eq!(BitArray::new(0).div_rem_u32(7), Ok((BitArray::new(0), 0)));
+    }
+
+    #[test]
+    fn test_div_rem_multi_word() {
+        // 2^130 + 5 = 3 * 453709822561251284617832809909024281943 + 0
+        let mut arr = BitArray::new(131);
+        arr.set(0, true);
+        arr.set_bits(128..131, 0b101);
+        let (quotient, rem) = arr.div_rem_u32(3).unwrap();
+        assert_eq!(rem, 0);
+        assert_eq!(
+            quotient.to_decimal_string(),
+            "453709822561251284617832809909024281943"
+        );
+        assert_eq!(
+            arr.to_decimal_string(),
+            "1361129467683753853853498429727072845829"
+        );
+        let (_, rem) = arr.div_rem_u32(1_000_000_007).unwrap();
+        assert_eq!(rem, 118_529_106);
+    }
+
+    #[test]
+    fn test_div_by_zero() {
+        let err = BitArray::new(10).div_rem_u32(0).unwrap_err();
+        assert_eq!(err, BitError::DivideByZero);
+        assert_eq!(err.to_string(), "division by zero");
+    }
+
+    #[test]
+    fn test_to_decimal_string() {
+        assert_eq!(BitArray::new(0).to_decimal_string(), "0");
+        assert_eq!(BitArray::new(70).to_decimal_string(), "0");
+        assert_eq!(bits("1010").to_decimal_string(), "10");
+        for value in [1u128, 9, 10_000_000_000_000_000_000, u128::MAX, 12345 << 80] {
+            assert_eq!(from_uint(value, 128).to_decimal_string(), value.to_string());
+        }
+    }
+
+    #[test]
+    fn test_gray() {
+        let mut prbs = Prbs::new(PrbsKind::Prbs15);
+        for len in [1, 7, 9, 63, 64, 65, 100, 128] {
+            let max = u128::MAX >> (128 - len);
+            for _ in 0..10 {
+                let arr: BitArray = prbs.by_ref().take(len).collect();
+                let value = arr.to_u128(128).unwrap();
+                let gray = arr.to_gray();
+                assert_eq!(gray.to_u128(128).unwrap(), value ^ (value >> 1));
+                assert_eq!(BitArray::from_gray(&gray), arr);
+
+                // Consecutive values differ in exactly one bit of their codes.
+                let next = from_uint(value.wrapping_add(1) & max, len);
+                assert_eq!(gray.hamming_distance(&next.to_gray()), Ok(1));
+            }
+        }
+        let long: BitArray = prbs.take(1000).collect();
+        assert_eq!(BitArray::from_gray(&long.to_gray()), long);
+        assert_eq!(BitArray::new(0).to_gray(), BitArray::new(0));
+    }
+
+    /// The polynomial with bit `k` of `value` as the coefficient of `x^k`, in `len` bits.
+    fn poly(value: u128, len: usize) -> BitArray {
+        BitArray::from_fn(len, |k| (value >> k) & 1 == 1)
+    }
+
+    #[test]
+    fn test_prefix_xor() {
+        assert_eq!(bits("1000").prefix_xor(), bits("1111"));
+        assert_eq!(bits("0110100").prefix_xor(), bits("0100111"));
+        assert_eq!(bits("0100111").undo_prefix_xor(), bits("0110100"));
+
+        let mut prbs = Prbs::new(PrbsKind::Prbs15);
+        for len in [0, 1, 13, 63, 64, 65, 130, 1000] {
+            assert_eq!(BitArray::new(len).prefix_xor(), BitArray::new(len));
+            let ones = BitArray::from_fn(len, |_| true);
+            assert_eq!(ones.prefix_xor(), BitArray::from_fn(len, |i| i % 2 == 0));
+
+            let arr: BitArray = prbs.by_ref().take(len).collect();
+            let mut acc = false;
+            let expected = BitArray::from_fn(len, |i| {
+                acc ^= *arr.get(i);
+                acc
+            });
+            let scanned = arr.prefix_xor();
+            assert_eq!(scanned, expected);
+            assert_eq!(scanned.undo_prefix_xor(), arr);
+            assert_eq!(arr.undo_prefix_xor().prefix_xor(), arr);
+        }
+    }
+
+    #[test]
+    fn test_clmul() {
+        // (x + 1)(x + 1) = x^2 + 1
+        assert_eq!(bits("11").clmul(&bits("11")), bits("101"));
+        // (x^2 + x + 1)(x + 1) = x^3 + 1
+        assert_eq!(bits("111").clmul(&bits("11")), bits("1001"));
+        assert_eq!(bits("1").clmul(&bits("0110")), bits("0110"));
+        assert!(BitArray::new(0).clmul(&bits("101")).is_empty());
+
+        let mut prbs = Prbs::new(PrbsKind::Prbs15);
+        let mut next = |len: usize| -> BitArray { prbs.by_ref().take(len).collect() };
+        for (m, n) in [(1, 1), (8, 8), (13, 70), (64, 65), (100, 3)] {
+            let (a, b, c) = (next(m), next(n), next(n));
+            assert_eq!(a.clmul(&b).len(), m + n - 1);
+            assert_eq!(a.clmul(&b), b.clmul(&a));
+            // Multiplication distributes over XOR.
+            assert_eq!(a.clmul(&(&b ^ &c)), &a.clmul(&b) ^ &a.clmul(&c));
+        }
+    }
+
+    #[test]
+    fn test_polynomial_div_rem() {
+        // (x^3 + x + 1) / (x + 1) = x^2 + x, remainder 1
+        let (q, r) = bits("1101").polynomial_div_rem(&bits("11")).unwrap();
+        assert_eq!((q, r), (bits("011"), bits("1")));
+
+        let mut prbs = Prbs::new(PrbsKind::Prbs15);
+        let mut next = |len: usize| -> BitArray { prbs.by_ref().take(len).collect() };
+        for (m, n) in [(1, 1), (8, 3), (70, 13), (200, 65), (5, 20), (300, 100)] {
+            let (a, mut d) = (next(m), next(n));
+            d.set(n / 2, true);
+            let (q, r) = a.polynomial_div_rem(&d).unwrap();
+            let degree = d.last_one().unwrap();
+            assert_eq!(r.len(), n - 1);
+            assert_eq!(q.len(), m.saturating_sub(degree));
+            assert!(r.last_one().is_none_or(|top| top < degree));
+            // q * d + r == a, once all three are zero-extended to the same length.
+            assert!((&(&q.clmul(&d) ^ &r) ^ &a).none());
+        }
+    }
+
+    #[test]
+    fn test_polynomial_div_by_power_of_x() {
+        let a: BitArray = Prbs::new(PrbsKind::Prbs15).take(90).collect();
+        for k in [0, 1, 7, 64, 89] {
+            let mut d = BitArray::new(k + 1);
+            d.set(k, true);
+            let (q, r) = a.polynomial_div_rem(&d).unwrap();
+            // Dividing by x^k drops the k lowest coefficients into the remainder.
+            assert_eq!(q, (0..90 - k).map(|i| *a.get(i + k)).collect::<BitArray>());
+            assert_eq!(r, (0..k).map(|i| *a.get(i)).collect::<BitArray>());
+        }
+    }
+
+    #[test]
+    fn test_polynomial_div_leading_zeros() {
+        // x^2 + 1 with a zero x^3 term, divided by x + 1 with zero x^2 and x^3 terms.
+        let (q, r) = bits("1010").polynomial_div_rem(&bits("1100")).unwrap();
+        assert_eq!(q, bits("110"));
+        assert_eq!(r, bits("000"));
+        // A dividend of lower degree than the divisor is all remainder.
+        let (q, r) = bits("11").polynomial_div_rem(&bits("0001")).unwrap();
+        assert!(q.is_empty());
+        assert_eq!(r, bits("110"));
+    }
+
+    #[test]
+    fn test_polynomial_div_by_zero() {
+        assert_eq!(
+            bits("101").polynomial_div_rem(&bits("000")),
+            Err(BitError::DivideByZero)
+        );
+        assert_eq!(
+            bits("101").polynomial_div_rem(&BitArray::new(0)),
+            Err(BitError::DivideByZero)
+        );
+    }
+
+    #[test]
+    fn test_clmul_agrees_with_byte() {
+        for x in (0..=u8::MAX).step_by(7) {
+            for y in (0..=u8::MAX).step_by(11) {
+                let product = Byte::from(x).clmul(Byte::from(y));
+                assert_eq!(
+                    poly(x.into(), 8).clmul(&poly(y.into(), 8)),
+                    poly(product.into(), 15)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_interleave() {
+        assert_eq!(bits("110").interleave(&bits("001")), Ok(bits("101001")));
+        for len in [0, 1, 8, 13, 100] {
+            let mut prbs = Prbs::new(PrbsKind::Prbs15);
+            let a: BitArray = prbs.by_ref().take(len).collect();
+            let b: BitArray = prbs.take(len).collect();
+            let mixed = a.interleave(&b).unwrap();
+            assert_eq!(mixed.len(), 2 * len);
+            for i in 0..len {
+                assert_eq!(mixed.get(2 * i), a.get(i));
+                assert_eq!(mixed.get(2 * i + 1), b.get(i));
+            }
+        }
+        assert_eq!(
+            bits("1").interleave(&bits("10")),
+            Err(BitError::LengthMismatch { left: 1, right: 2 })
+        );
+    }
+
+    #[test]
+    fn test_swap() {
+        let mut arr = bits("1000000000");
+        arr.swap(0, 9);
+        assert_eq!(arr, bits("0000000001"));
+        arr.swap(9, 9);
+        arr.swap(1, 2);
+        assert_eq!(arr, bits("0000000001"));
+
+        // A permutation built from swaps, checked against a model.
+        let len = 150;
+        let mut arr: BitArray = Prbs::new(PrbsKind::Prbs15).take(len).collect();
+        let mut model: Vec<bool> = arr.iter().map(|bit| *bit).collect();
+        for k in 0..len {
+            let (i, j) = (k * 37 % len, k * 101 % len);
+            arr.swap(i, j);
+            model.swap(i, j);
+        }
+        assert_eq!(arr, BitArray::from(model));
+    }
+
+    #[test]
+    #[should_panic(expected = "index 10 out of range for bit array of length 10")]
+    fn test_swap_out_of_range() {
+        BitArray::new(10).swap(2, 10);
+    }
+
+    #[test]
+    fn test_swap_ranges() {
+        let mut arr = bits("1110000110");
+        arr.swap_ranges(0..3, 5..8).unwrap();
+        assert_eq!(arr, bits("0010011110"));
+        arr.swap_ranges(4..4, 2..2).unwrap();
+
+        let len = 300;
+        let original: BitArray = Prbs::new(PrbsKind::Prbs15).take(len).collect();
+        let mut arr = original.clone();
+        arr.swap_ranges(3..143, 150..290).unwrap();
+        for i in 0..140 {
+            assert_eq!(arr.get(3 + i), original.get(150 + i));
+            assert_eq!(arr.get(150 + i), original.get(3 + i));
+        }
+        assert_eq!(arr.slice(143..150), original.slice(143..150));
+
+        assert_eq!(
+            arr.swap_ranges(0..4, 3..7),
+            Err(BitError::Overlap {
+                first: 0,
+                second: 3,
+                len: 4
+            })
+        );
+        assert_eq!(
+            arr.swap_ranges(0..4, 10..15),
+            Err(BitError::LengthMismatch { left: 4, right: 5 })
+        );
+        assert!(arr.swap_ranges(0..4, 297..301).is_err());
+    }
+
+    #[test]
+    fn test_bit_reverse_permutation() {
+        // Bit `i` moves to the index with the bits of `i` reversed.
+        let perm8 = [0, 4, 2, 6, 1, 5, 3, 7];
+        let perm16 = [0, 8, 4, 12, 2, 10, 6, 14, 1, 9, 5, 13, 3, 11, 7, 15];
+        for perm in [&perm8[..], &perm16[..]] {
+            for (i, &j) in perm.iter().enumerate() {
+                let mut arr = BitArray::new(perm.len());
+                arr.set(i, true);
+                let permuted = arr.bit_reverse_permutation().unwrap();
+                assert_eq!(permuted.iter_ones().collect::<Vec<_>>(), [j]);
+            }
+        }
+        assert_eq!(
+            bits("01100000").bit_reverse_permutation(),
+            Ok(bits("00101000"))
+        );
+
+        for len in [1, 2, 64, 256] {
+            let arr: BitArray = Prbs::new(PrbsKind::Prbs15).take(len).collect();
+            let permuted = arr.bit_reverse_permutation().unwrap();
+            assert_eq!(permuted.count_ones(), arr.count_ones());
+            assert_eq!(permuted.bit_reverse_permutation(), Ok(arr));
+        }
+    }
+
+    #[test]
+    fn test_bit_reverse_permutation_errors() {
+        for len in [0, 3, 12, 100] {
+            let err = BitError::NotPowerOfTwo { len };
+            assert_eq!(BitArray::new(len).bit_reverse_permutation(), Err(err));
+        }
+        assert_eq!(
+            BitError::NotPowerOfTwo { len: 12 }.to_string(),
+            "length 12 is not a power of two"
+        );
+    }
+
+    #[test]
+    fn test_reverse() {
+        for len in [0, 1, 7, 8, 9, 17, 64, 100] {
+            let mut prbs = Prbs::new(PrbsKind::Prbs15);
+            let mut arr = BitArray::new(len);
+            for idx in 0..len {
+                arr.set(idx, prbs.next().unwrap().is_one());
+            }
+            let mut expected: Vec<bool> = (0..len).map(|idx| *arr.get(idx)).collect();
+            expected.reverse();
+
+            let original = arr.clone();
+            arr.reverse();
+            assert_eq!(arr.len(), len);
+            assert!((0..len).all(|idx| *arr.get(idx) == expected[idx]));
+            assert_eq!(arr.count_ones(), original.count_ones());
+            arr.reverse();
+            assert_eq!(arr, original);
+        }
+
+        let mut arr = BitArray::new(9);
+        arr.set(0, true);
+        arr.reverse();
+        assert_eq!(arr.as_bytes(), [0, 0b1000_0000]);
+    }
+
+    #[test]
+    fn test_from_iter() {
+        for len in [0, 1, 8, 9, 1000] {
+            let bools: Vec<bool> = Prbs::new(PrbsKind::Prbs15).take(len).map(|b| *b).collect();
+            let arr: BitArray = bools.iter().copied().collect();
+            assert_eq!(arr.len(), len);
+            assert!(arr.iter().map(|b| *b).eq(bools.iter().copied()));
+            assert_eq!(arr.iter().len(), len);
+
+            assert_eq!(arr.iter().collect::<BitArray>(), arr);
+            assert_eq!(BitArray::from(&bools[..]), arr);
+            assert_eq!(BitArray::from(bools), arr);
+        }
+
+        let arr: BitArray = [true, false, true].iter().copied().collect();
+        assert_eq!(arr.as_bytes(), [0b1010_0000]);
+    }
+
+    /// Builds an array from a string of binary digits.
+    fn bits(digits: &str) -> BitArray {
+        digits.chars().map(|c| c == '1').collect()
+    }
+
+    #[test]
+    fn test_find() {
+        let sync = bits("1111111111010");
+        let mut stream = bits("0110100");
+        for skip in [0, 3, 11] {
+            let mut haystack = stream.clone();
+            let mut extended: Vec<bool> = haystack.iter().map(|b| *b).collect();
+            extended.extend((0..skip).map(|i| i % 2 == 0));
+            extended.extend(sync.iter().map(|b| *b));
+            extended.extend([false, true]);
+            haystack = BitArray::from(extended);
+            assert_eq!(haystack.find(&sync), Some(7 + skip));
+            assert_eq!(haystack.find_from(&sync, 7 + skip), Some(7 + skip));
+            assert_eq!(haystack.find_from(&sync, 8 + skip), None);
+        }
+        stream.reverse();
+        assert_eq!(stream.find(&sync), None);
+
+        // Longer than the haystack.
+        assert_eq!(bits("101").find(&bits("1010")), None);
+        // The empty pattern matches immediately.
+        assert_eq!(bits("101").find(&BitArray::new(0)), Some(0));
+        assert_eq!(BitArray::new(0).find(&BitArray::new(0)), Some(0));
+        assert_eq!(bits("101").find_from(&bits("1"), 4), None);
+
+        // Patterns wider than one comparison chunk.
+        let long: BitArray = Prbs::new(PrbsKind::Prbs15).take(300).collect();
+        let needle: BitArray = Prbs::new(PrbsKind::Prbs15).skip(133).take(100).collect();
+        assert_eq!(long.find(&needle), Some(133));
+    }
+
+    #[test]
+    fn test_longest_common_substring() {
+        let arr: BitArray = Prbs::new(PrbsKind::Prbs15).take(150).collect();
+        assert_eq!(arr.longest_common_substring(&arr), Some((0, 0, 150)));
+        assert_eq!(bits("000").longest_common_substring(&bits("11")), None);
+        assert_eq!(BitArray::new(0).longest_common_substring(&arr), None);
+        assert_eq!(arr.longest_common_substring(&BitArray::new(0)), None);
+        // Ties go to the earliest offset in self, then in other.
+        assert_eq!(
+            bits("0110").longest_common_substring(&bits("1001")),
+            Some((0, 2, 2))
+        );
+    }
+
+    #[test]
+    fn test_longest_common_substring_planted() {
+        let mut prbs = Prbs::new(PrbsKind::Prbs15);
+        let planted: BitArray = prbs.by_ref().take(100).collect();
+        // The two backgrounds have different periods, so they only share short runs.
+        let mut a = BitArray::from_fn(300, |i| i % 2 == 0);
+        let mut b = BitArray::from_fn(250, |i| i % 3 == 0);
+        a.copy_bits_from(37, &planted, 0..100).unwrap();
+        b.copy_bits_from(101, &planted, 0..100).unwrap();
+        let (i, j, len) = a.longest_common_substring(&b).unwrap();
+        assert!(len >= 100);
+        assert!(i <= 37 && 37 + 100 <= i + len);
+        assert_eq!(j + 37 - i, 101);
+        assert_eq!(a.slice(i..i + len), b.slice(j..j + len));
+    }
+
+    #[test]
+    fn test_longest_common_substring_brute_force() {
+        let mut prbs = Prbs::new(PrbsKind::Prbs15);
+        for (n, m) in [(1, 1), (5, 9), (20, 13), (70, 66), (40, 130)] {
+            let a: BitArray = prbs.by_ref().take(n).collect();
+            let b: BitArray = prbs.by_ref().take(m).collect();
+            let mut best = None;
+            for len in (1..=n.min(m)).rev() {
+                best = (0..=n - len).find_map(|i| {
+                    (0..=m - len)
+                        .find(|&j| a.slice(i..i + len) == b.slice(j..j + len))
+                        .map(|j| (i, j, len))
+                });
+                if best.is_some() {
+                    break;
+                }
+            }
+            assert_eq!(a.longest_common_substring(&b), best);
+        }
+    }
+
+    #[test]
+    fn test_matches() {
+        assert_eq!(
+            bits("10101").matches(&bits("101")).collect::<Vec<_>>(),
+            [0, 2]
+        );
+        assert_eq!(
+            bits("1111").matches(&bits("11")).collect::<Vec<_>>(),
+            [0, 1, 2]
+        );
+        assert_eq!(bits("0000").matches(&bits("1")).count(), 0);
+        assert_eq!(
+            bits("01").matches(&BitArray::new(0)).collect::<Vec<_>>(),
+            [0, 1, 2]
+        );
+
+        let haystack: BitArray = Prbs::new(PrbsKind::Prbs9).take(2000).collect();
+        let pattern = bits("110010");
+        let naive: Vec<usize> = (0..=2000 - 6)
+            .filter(|&pos| (0..6).all(|i| haystack.get(pos + i) == pattern.get(i)))
+            .collect();
+        assert_eq!(haystack.matches(&pattern).collect::<Vec<_>>(), naive);
+    }
+
+    #[test]
+    fn test_chunks() {
+        let arr = bits("10111001011100000111");
+        let mut chunks = arr.chunks(3);
+        assert_eq!(chunks.len(), 7);
+        let expected = [
+            (0b101, 3),
+            (0b110, 3),
+            (0b010, 3),
+            (0b111, 3),
+            (0b000, 3),
+            (0b001, 3),
+            (0b110, 2),
+        ];
+        assert!(chunks.by_ref().eq(expected.iter().copied()));
+        assert_eq!(chunks.next(), None);
+
+        let arr: BitArray = Prbs::new(PrbsKind::Prbs15).take(61).collect();
+        let bytes: Vec<u8> = arr.chunks(8).map(|(value, _)| value as u8).collect();
+        assert_eq!(bytes, arr.as_bytes());
+        assert_eq!(
+            arr.chunks(64).collect::<Vec<_>>(),
+            [(arr.get_bits(0..61) << 3, 61)]
+        );
+        assert_eq!(BitArray::new(0).chunks(5).next(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk width 65 is not between 1 and 64")]
+    fn test_chunks_too_wide() {
+        BitArray::new(100).chunks(65);
+    }
+
+    #[test]
+    fn test_iter_bytes_at() {
+        let arr: BitArray = Prbs::new(PrbsKind::Prbs15).take(83).collect();
+        assert!(arr
+            .iter_bytes_at(0)
+            .eq(arr.as_bytes()[..10].iter().copied()));
+        for offset in 0..=16 {
+            let iter = arr.iter_bytes_at(offset);
+            let whole = (83 - offset) / 8;
+            assert_eq!(iter.len(), whole);
+            let expected =
+                (0..whole).map(|k| arr.get_bits(offset + 8 * k..offset + 8 * k + 8) as u8);
+            assert!(iter.clone().eq(expected));
+
+            let count = (83 - offset) % 8;
+            let tail = arr.get_bits(83 - count..83) << (8 - count);
+            assert_eq!(iter.remainder(), (tail as u8, count));
+        }
+    }
+
+    #[test]
+    fn test_iter_bytes_at_remainder() {
+        for len in [0, 7, 8, 9, 15, 16] {
+            let arr = BitArray::from_fn(len, |_| true);
+            let mut iter = arr.iter_bytes_at(0);
+            assert_eq!(iter.by_ref().count(), len / 8);
+            let count = len % 8;
+            assert_eq!(iter.remainder(), (!(u8::MAX >> count), count));
+        }
+        let arr = bits("101101101");
+        let mut iter = arr.iter_bytes_at(3);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.remainder(), (0b1011_0100, 6));
+        assert_eq!(arr.iter_bytes_at(9).remainder(), (0, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "offset 10 out of range for bit array of length 9")]
+    fn test_iter_bytes_at_out_of_range() {
+        BitArray::new(9).iter_bytes_at(10);
+    }
+
+    #[test]
+    fn test_windows() {
+        let arr = bits("1011001");
+        let mut windows = arr.windows(3);
+        assert_eq!(windows.len(), 5);
+        assert!(windows
+            .by_ref()
+            .eq([0b101, 0b011, 0b110, 0b100, 0b001].iter().copied()));
+        assert_eq!(windows.next(), None);
+        assert!(arr.windows(7).eq([0b1011001].iter().copied()));
+        assert_eq!(arr.windows(8).len(), 0);
+        assert_eq!(arr.windows(8).next(), None);
+
+        for (skip, len) in [(0, 1), (3, 17), (40, 64), (91, 150)] {
+            let arr: BitArray = Prbs::new(PrbsKind::Prbs15).skip(skip).take(len).collect();
+            for width in [1, 5, 8, 13, 64] {
+                let expected: Vec<u64> = (0..(len + 1).saturating_sub(width))
+                    .map(|k| arr.get_bits(k..k + width))
+                    .collect();
+                assert_eq!(arr.windows(width).len(), expected.len());
+                assert_eq!(arr.windows(width).collect::<Vec<_>>(), expected);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "window width 0 is not between 1 and 64")]
+    fn test_windows_empty_width() {
+        BitArray::new(10).windows(0);
+    }
+
+    #[test]
+    fn test_runs() {
+        let one = Bit::ONE;
+        let zero = Bit::ZERO;
+        let arr = bits("0001111111111111111111110");
+        assert_eq!(
+            arr.runs().collect::<Vec<_>>(),
+            [(zero, 3), (one, 21), (zero, 1)]
+        );
+        assert_eq!(BitArray::new(0).runs().next(), None);
+        assert_eq!(BitArray::new(19).runs().collect::<Vec<_>>(), [(zero, 19)]);
+        assert_eq!(
+            bits("1111111111111").runs().collect::<Vec<_>>(),
+            [(one, 13)]
+        );
+
+        let alternating: BitArray = (0..37).map(|i| i % 2 == 0).collect();
+        assert_eq!(alternating.runs().count(), 37);
+        assert!(alternating.runs().all(|(_, len)| len == 1));
+
+        for len in [1, 7, 8, 13, 100, 333] {
+            let arr: BitArray = Prbs::new(PrbsKind::Prbs15).take(len).collect();
+            let runs: Vec<_> = arr.runs().collect();
+            assert_eq!(runs.iter().map(|(_, len)| len).sum::<usize>(), len);
+            assert!(runs.windows(2).all(|w| w[0].0 != w[1].0));
+            assert_eq!(BitArray::from_runs(runs), arr);
+        }
+        assert_eq!(
+            BitArray::from_runs([(one, 0), (zero, 3), (one, 9)]),
+            bits("000111111111")
+        );
+    }
+
+    #[test]
+    fn test_get_mut() {
+        let mut arr = BitArray::new(12);
+        *arr.get_mut(3).unwrap() = true;
+        arr.get_mut(11).unwrap().set(true);
+        {
+            let mut bit = arr.get_mut(3).unwrap();
+            assert!(*bit);
+            *bit = !*bit;
+            // Not written back until the guard is dropped.
+            assert!(*bit.arr.get(3));
+        }
+        assert_eq!(arr.as_bytes(), [0, 0b0001_0000]);
+        assert!(arr.get_mut(12).is_none());
+
+        let before = arr.clone();
+        {
+            let mut bit = arr.get_mut(0).unwrap();
+            *bit = true;
+            *bit = false;
+        }
+        let _ = arr.get_mut(11).unwrap();
+        assert_eq!(arr, before);
+    }
+
+    #[test]
+    fn test_index() {
+        let mut arr = bits("0010000001");
+        assert!(*arr[2]);
+        assert!(!*arr[3]);
+        assert_eq!(arr[9], Bit::ONE);
+        assert_eq!(&arr[2], &Bit::from(true));
+        assert_eq!((0..10).filter(|&idx| arr[idx].is_one()).count(), 2);
+        if *arr[2] {
+            arr.set(2, false);
+        }
+        assert!(arr[2].is_zero());
+    }
+
+    #[test]
+    #[should_panic(expected = "index 10 out of range for bit array of length 10")]
+    fn test_index_out_of_range() {
+        let _ = BitArray::new(10)[10];
+    }
+
+    #[test]
+    fn test_bulk_large() {
+        let len = 100_037;
+        let a: BitArray = Prbs::new(PrbsKind::Prbs23).take(len).collect();
+        let b: BitArray = Prbs::new(PrbsKind::Prbs15).take(len).collect();
+        let naive_ones = a.iter().filter(|bit| bit.is_one()).count();
+        assert_eq!(a.count_ones(), naive_ones);
+        assert_eq!(a.rank1(len), naive_ones);
+
+        let and = &a & &b;
+        let xor = &a ^ &b;
+        let or = &a | &b;
+        for idx in (0..len).step_by(97).chain(len - 70..len) {
+            let (x, y) = (*a.get(idx), *b.get(idx));
+            assert_eq!(*and.get(idx), x & y);
+            assert_eq!(*xor.get(idx), x ^ y);
+            assert_eq!(*or.get(idx), x | y);
+        }
+        assert_eq!(a.hamming_distance(&b), Ok(xor.count_ones()));
+        assert_eq!(
+            or.count_ones() + and.count_ones(),
+            a.count_ones() + b.count_ones()
+        );
+
+        let mut filled = a.clone();
+        filled.set_range(0..len).unwrap();
+        assert_eq!(filled.count_ones(), len);
+        assert_eq!(filled.first_zero(), None);
+        filled.clear_range(1..len - 1).unwrap();
+        assert_eq!(filled.iter_ones().collect::<Vec<_>>(), [0, len - 1]);
+        assert_eq!(filled.last_one(), Some(len - 1));
+        assert_ne!(a, b);
+        assert_eq!(a, a.iter().collect::<BitArray>());
+        assert_eq!(a.as_bytes().len(), len.div_ceil(8));
+        assert_eq!(BitArray::from_bytes(a.as_bytes()).count_ones(), naive_ones);
+    }
+}
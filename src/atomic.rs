@@ -0,0 +1,233 @@
+//! A bit set that can be shared and updated between threads.
+
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use crate::BitArray;
+
+/// A fixed-length set of bits that many threads can update at once, such as a slot allocator.
+///
+/// Indices follow the same order as [`BitArray`]. Single-bit updates use `fetch_or`/`fetch_and`
+/// with [`Ordering::AcqRel`], so a thread that claims a slot sees every write made by the thread
+/// that released it. [`AtomicBitSet::test`] is a [`Ordering::Relaxed`] load.
+///
+/// Operations that read many bits, such as [`AtomicBitSet::to_bit_array`], are racy snapshots:
+/// each block is read atomically, but other threads may change the set part way through.
+///
+/// The set is only available on targets with 64-bit atomics, which rules out 32-bit Cortex-M parts.
+#[derive(Debug, Default)]
+pub struct AtomicBitSet {
+    /// The bits of the set, 64 to a block, with index 0 the most significant bit of the first block.
+    blocks: Vec<AtomicU64>,
+    /// The number of bits in the set.
+    len: usize,
+}
+
+impl AtomicBitSet {
+    /// Creates a set of `len` bits, all of which are `0`.
+    pub fn new(len: usize) -> Self {
+        Self {
+            blocks: (0..len.div_ceil(64)).map(|_| AtomicU64::new(0)).collect(),
+            len,
+        }
+    }
+
+    /// The number of bits in the set.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// True if the set holds no bits.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The block holding bit `idx`, and the mask selecting the bit within it.
+    ///
+    /// Panics if `idx` is not less than the length of the set.
+    fn locate(&self, idx: usize) -> (&AtomicU64, u64) {
+        assert!(
+            idx < self.len,
+            "index {} out of range for atomic bit set of length {}",
+            idx,
+            self.len
+        );
+        (&self.blocks[idx / 64], 0x8000_0000_0000_0000 >> (idx % 64))
+    }
+
+    /// Sets the bit at index `idx` to `1`, returning its previous value.
+    ///
+    /// Panics if `idx` is not less than the length of the set.
+    pub fn set(&self, idx: usize) -> bool {
+        let (block, mask) = self.locate(idx);
+        block.fetch_or(mask, Ordering::AcqRel) & mask != 0
+    }
+
+    /// Sets the bit at index `idx` to `0`, returning its previous value.
+    ///
+    /// Panics if `idx` is not less than the length of the set.
+    pub fn clear(&self, idx: usize) -> bool {
+        let (block, mask) = self.locate(idx);
+        block.fetch_and(!mask, Ordering::AcqRel) & mask != 0
+    }
+
+    /// True if the bit at index `idx` is `1`.
+    ///
+    /// Panics if `idx` is not less than the length of the set.
+    pub fn test(&self, idx: usize) -> bool {
+        let (block, mask) = self.locate(idx);
+        block.load(Ordering::Relaxed) & mask != 0
+    }
+
+    /// Finds a `0` bit and sets it, returning its index, or `None` if every bit is `1`.
+    ///
+    /// Each claim is a compare-and-swap with [`Ordering::AcqRel`], so no two callers can claim the
+    /// same bit. The lowest free index is preferred, but under contention a later one may be returned.
+    pub fn find_and_set_first_zero(&self) -> Option<usize> {
+        for (i, block) in self.blocks.iter().enumerate() {
+            // Bits past the end of the set are never free.
+            let valid = match (self.len - i * 64).min(64) {
+                64 => u64::MAX,
+                n => !(u64::MAX >> n),
+            };
+            let mut current = block.load(Ordering::Relaxed);
+            loop {
+                let free = !current & valid;
+                if free == 0 {
+                    break;
+                }
+                let offset = free.leading_zeros();
+                let claimed = current | (0x8000_0000_0000_0000 >> offset);
+                match block.compare_exchange_weak(
+                    current,
+                    claimed,
+                    Ordering::AcqRel,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => return Some(i * 64 + offset as usize),
+                    Err(actual) => current = actual,
+                }
+            }
+        }
+        None
+    }
+
+    /// The number of `1` bits, as a racy snapshot.
+    pub fn count_ones(&self) -> usize {
+        self.blocks
+            .iter()
+            .map(|block| block.load(Ordering::Relaxed).count_ones() as usize)
+            .sum()
+    }
+
+    /// Copies the bits into a plain array, as a racy snapshot.
+    pub fn to_bit_array(&self) -> BitArray {
+        BitArray {
+            blocks: self
+                .blocks
+                .iter()
+                .map(|block| block.load(Ordering::Acquire).to_be())
+                .collect(),
+            len: self.len,
+        }
+    }
+}
+
+impl From<BitArray> for AtomicBitSet {
+    fn from(arr: BitArray) -> Self {
+        Self {
+            blocks: arr
+                .blocks
+                .iter()
+                .map(|&block| AtomicU64::new(u64::from_be(block)))
+                .collect(),
+            len: arr.len,
+        }
+    }
+}
+
+impl From<AtomicBitSet> for BitArray {
+    fn from(set: AtomicBitSet) -> Self {
+        Self {
+            blocks: set
+                .blocks
+                .into_iter()
+                .map(|block| block.into_inner().to_be())
+                .collect(),
+            len: set.len,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Prbs, PrbsKind};
+
+    #[test]
+    fn test_set_clear() {
+        let set = AtomicBitSet::new(70);
+        assert!(!set.set(3));
+        assert!(set.set(3));
+        assert!(set.test(3));
+        assert!(!set.set(69));
+        assert!(set.clear(3));
+        assert!(!set.clear(3));
+        assert!(!set.test(3));
+        assert_eq!(set.count_ones(), 1);
+
+        let arr = set.to_bit_array();
+        assert_eq!(arr.iter_ones().collect::<alloc::vec::Vec<_>>(), [69]);
+    }
+
+    #[test]
+    fn test_claim() {
+        let set = AtomicBitSet::new(67);
+        for idx in 0..67 {
+            assert_eq!(set.find_and_set_first_zero(), Some(idx));
+        }
+        // The padding of the final block is never handed out.
+        assert_eq!(set.find_and_set_first_zero(), None);
+        set.clear(40);
+        assert_eq!(set.find_and_set_first_zero(), Some(40));
+        assert_eq!(AtomicBitSet::new(0).find_and_set_first_zero(), None);
+    }
+
+    #[test]
+    fn test_bit_array_round_trip() {
+        let arr: BitArray = Prbs::new(PrbsKind::Prbs15).take(200).collect();
+        let set = AtomicBitSet::from(arr.clone());
+        assert!((0..200).all(|idx| set.test(idx) == arr.get(idx).is_one()));
+        assert_eq!(set.to_bit_array(), arr);
+        assert_eq!(BitArray::from(set), arr);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_concurrent_claims() {
+        use std::vec::Vec;
+
+        let set = AtomicBitSet::new(1000);
+        let claims: Vec<Vec<usize>> = std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..8)
+                .map(|_| {
+                    scope.spawn(|| {
+                        core::iter::from_fn(|| set.find_and_set_first_zero()).collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            workers.into_iter().map(|w| w.join().unwrap()).collect()
+        });
+
+        let mut all: Vec<usize> = claims.into_iter().flatten().collect();
+        all.sort_unstable();
+        assert_eq!(all, (0..1000).collect::<Vec<_>>());
+        assert_eq!(set.count_ones(), 1000);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 70 out of range for atomic bit set of length 70")]
+    fn test_out_of_range() {
+        AtomicBitSet::new(70).set(70);
+    }
+}
@@ -0,0 +1,200 @@
+//! Bitmap indexes over columns of small categorical values.
+
+use alloc::{vec, vec::Vec};
+
+use crate::{BitArray, BitError};
+
+/// One [`BitArray`] per possible value of a column, where bit `i` of the bitmap for `v` is `1` if
+/// row `i` holds `v`.
+///
+/// Every row is set in exactly one bitmap. Filters on the column become ORs of bitmaps, and
+/// filters across several columns indexed over the same rows become ANDs.
+///
+/// ```
+/// use bit_array_rs::BitmapIndex;
+///
+/// let colour = BitmapIndex::build([0, 2, 1, 2], 3)?;
+/// let size = BitmapIndex::build([1, 1, 0, 0], 2)?;
+/// assert_eq!(colour.rows_matching_any(&[1, 2]).iter_ones().collect::<Vec<_>>(), [1, 2, 3]);
+/// assert_eq!(colour.rows_matching_all(2, &[(&size, 0)])?.iter_ones().collect::<Vec<_>>(), [3]);
+/// # Ok::<(), bit_array_rs::BitError>(())
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct BitmapIndex {
+    /// The bitmap for each value, indexed by value.
+    bitmaps: Vec<BitArray>,
+    /// The number of rows.
+    rows: usize,
+}
+
+impl BitmapIndex {
+    /// Builds an index over `values`, one row per value, for values less than `cardinality`.
+    ///
+    /// Returns an error holding the first value that is not less than `cardinality`.
+    pub fn build<I: IntoIterator<Item = u32>>(
+        values: I,
+        cardinality: usize,
+    ) -> Result<Self, BitError> {
+        let values: Vec<u32> = values.into_iter().collect();
+        let mut bitmaps = vec![BitArray::new(values.len()); cardinality];
+        for (row, &value) in values.iter().enumerate() {
+            bitmaps
+                .get_mut(value as usize)
+                .ok_or(BitError::OutOfBounds {
+                    index: value as usize,
+                    len: cardinality,
+                })?
+                .set(row, true);
+        }
+        Ok(Self {
+            bitmaps,
+            rows: values.len(),
+        })
+    }
+
+    /// The number of rows indexed.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// The number of distinct values the column may hold.
+    pub fn cardinality(&self) -> usize {
+        self.bitmaps.len()
+    }
+
+    /// The rows holding `value`, or `None` if it is not less than the cardinality.
+    pub fn bitmap_for(&self, value: u32) -> Option<&BitArray> {
+        self.bitmaps.get(value as usize)
+    }
+
+    /// The rows holding any of `values`, the OR of their bitmaps.
+    ///
+    /// Values not less than the cardinality match no rows.
+    pub fn rows_matching_any(&self, values: &[u32]) -> BitArray {
+        let mut res = BitArray::new(self.rows);
+        for bitmap in values.iter().filter_map(|&value| self.bitmap_for(value)) {
+            res.union_with(bitmap);
+        }
+        res
+    }
+
+    /// The rows holding `value` in this column and, for each `(index, value)` in `others`, that
+    /// value in the other column: the AND of their bitmaps.
+    ///
+    /// Values not less than the cardinality of their index match no rows. Returns an error if any
+    /// other index does not have the same number of rows.
+    pub fn rows_matching_all(
+        &self,
+        value: u32,
+        others: &[(&BitmapIndex, u32)],
+    ) -> Result<BitArray, BitError> {
+        let empty = BitArray::new(self.rows);
+        let mut res = self.bitmap_for(value).unwrap_or(&empty).clone();
+        for &(other, value) in others {
+            if other.rows != self.rows {
+                return Err(BitError::LengthMismatch {
+                    left: self.rows,
+                    right: other.rows,
+                });
+            }
+            res.intersect_with(other.bitmap_for(value).unwrap_or(&empty));
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Prbs, PrbsKind};
+
+    /// Row `i` of each of two small columns, derived from a PRBS.
+    fn columns(rows: usize) -> (Vec<u32>, Vec<u32>) {
+        let mut prbs = Prbs::new(PrbsKind::Prbs15);
+        let mut value = |width: usize| {
+            prbs.by_ref()
+                .take(width)
+                .fold(0, |acc, bit| (acc << 1) | u32::from(bit.as_u8()))
+        };
+        (0..rows).map(|_| (value(3) % 5, value(1))).unzip()
+    }
+
+    #[test]
+    fn test_build() {
+        let index = BitmapIndex::build([2, 0, 2, 1, 0], 4).unwrap();
+        assert_eq!((index.rows(), index.cardinality()), (5, 4));
+        let ones = |value| {
+            index
+                .bitmap_for(value)
+                .unwrap()
+                .iter_ones()
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ones(0), [1, 4]);
+        assert_eq!(ones(1), [3]);
+        assert_eq!(ones(2), [0, 2]);
+        assert!(ones(3).is_empty());
+        assert_eq!(index.bitmap_for(4), None);
+
+        let empty = BitmapIndex::build([], 3).unwrap();
+        assert_eq!(empty.rows(), 0);
+        assert_eq!(empty.rows_matching_any(&[0, 1, 2]), BitArray::new(0));
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        assert_eq!(
+            BitmapIndex::build([1, 3, 7], 3),
+            Err(BitError::OutOfBounds { index: 3, len: 3 })
+        );
+        assert!(BitmapIndex::build([0], 0).is_err());
+    }
+
+    #[test]
+    fn test_every_row_in_one_bitmap() {
+        let (values, _) = columns(300);
+        let index = BitmapIndex::build(values.iter().copied(), 5).unwrap();
+        let mut seen = BitArray::new(300);
+        for value in 0..5 {
+            let bitmap = index.bitmap_for(value).unwrap();
+            let mut overlap = seen.clone();
+            overlap.intersect_with(bitmap);
+            assert!(overlap.none());
+            seen.union_with(bitmap);
+        }
+        assert!(seen.all());
+    }
+
+    #[test]
+    fn test_queries_match_filters() {
+        let (colour, flag) = columns(300);
+        let colours = BitmapIndex::build(colour.iter().copied(), 5).unwrap();
+        let flags = BitmapIndex::build(flag.iter().copied(), 2).unwrap();
+
+        let wanted = [1, 3, 9];
+        let any = colours.rows_matching_any(&wanted);
+        assert!(any
+            .iter()
+            .enumerate()
+            .all(|(i, bit)| *bit == wanted.contains(&colour[i])));
+
+        for (c, f) in [(0, 0), (1, 1), (4, 0), (7, 1)] {
+            let all = colours.rows_matching_all(c, &[(&flags, f)]).unwrap();
+            let expected = BitArray::from_fn(300, |i| colour[i] == c && flag[i] == f);
+            assert_eq!(all, expected);
+        }
+        assert_eq!(
+            colours.rows_matching_all(2, &[]).unwrap(),
+            *colours.bitmap_for(2).unwrap()
+        );
+
+        let short = BitmapIndex::build([0, 1], 2).unwrap();
+        assert_eq!(
+            colours.rows_matching_all(0, &[(&short, 0)]),
+            Err(BitError::LengthMismatch {
+                left: 300,
+                right: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,176 @@
+//! Fluent construction of bit arrays from typed fields.
+
+use crate::BitArray;
+
+/// Builds a [`BitArray`] by appending fields one after another, each most-significant bit first.
+///
+/// Unlike [`BitWriter`](crate::BitWriter), the builder is consumed by each call and hands back a
+/// `BitArray` directly, so a packed header can be described in a single expression.
+///
+/// ```
+/// use bit_array_rs::BitArrayBuilder;
+///
+/// let header = BitArrayBuilder::new()
+///     .bits(0b101, 3)
+///     .bit(true)
+///     .u8(0xFF)
+///     .u16_be(1000)
+///     .build();
+/// assert_eq!(header.len(), 28);
+/// assert_eq!(header.as_bytes(), [0b1011_1111, 0b1111_0000, 0b0011_1110, 0b1000_0000]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BitArrayBuilder {
+    /// The bits appended so far.
+    arr: BitArray,
+}
+
+impl BitArrayBuilder {
+    /// Creates a builder holding no bits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of bits appended so far.
+    pub fn len(&self) -> usize {
+        self.arr.len()
+    }
+
+    /// True if no bits have been appended.
+    pub fn is_empty(&self) -> bool {
+        self.arr.is_empty()
+    }
+
+    /// Appends the low `width` bits of `value`, the most-significant of them first.
+    ///
+    /// Panics if `width` exceeds 64 or `value` does not fit in `width` bits.
+    pub fn bits(mut self, value: u64, width: u32) -> Self {
+        assert!(width <= 64, "field width {} exceeds 64 bits", width);
+        let start = self.arr.len();
+        self.arr.resize_len(start + width as usize);
+        self.arr.set_bits(start..self.arr.len(), value);
+        self
+    }
+
+    /// Appends a single bit.
+    pub fn bit(self, val: bool) -> Self {
+        self.bits(u64::from(val), 1)
+    }
+
+    /// Appends the eight bits of `value`, most-significant first.
+    pub fn u8(self, value: u8) -> Self {
+        self.bits(u64::from(value), 8)
+    }
+
+    /// Appends the sixteen bits of `value` in big-endian order.
+    pub fn u16_be(self, value: u16) -> Self {
+        self.bits(u64::from(value), 16)
+    }
+
+    /// Appends the low `width` bits of `value`, the most-significant of them first.
+    ///
+    /// Panics if `width` exceeds 32 or `value` does not fit in `width` bits.
+    pub fn u32_bits(self, value: u32, width: u32) -> Self {
+        assert!(width <= 32, "field width {} exceeds 32 bits", width);
+        self.bits(u64::from(value), width)
+    }
+
+    /// Appends every bit of `bytes`, in order.
+    pub fn bytes(self, bytes: &[u8]) -> Self {
+        self.append(&BitArray::from_bytes(bytes))
+    }
+
+    /// Appends every bit of `arr`, in order.
+    pub fn append(mut self, arr: &BitArray) -> Self {
+        let start = self.arr.len();
+        self.arr.resize_len(start + arr.len());
+        self.arr
+            .copy_bits_from(start, arr, 0..arr.len())
+            .expect("the array was just grown to fit");
+        self
+    }
+
+    /// Consumes the builder, returning the bits appended.
+    pub fn build(self) -> BitArray {
+        self.arr
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header() {
+        let payload = BitArray::from_fn(5, |i| i % 2 == 0);
+        let built = BitArrayBuilder::new()
+            .bits(0b101, 3)
+            .bit(true)
+            .u8(0xA5)
+            .u16_be(1000)
+            .u32_bits(4097, 13)
+            .bytes(&[0x0F, 0xF0])
+            .append(&payload)
+            .bit(false)
+            .build();
+
+        let mut expected = BitArray::new(0);
+        for (value, width) in [
+            (0b101, 3),
+            (1, 1),
+            (0xA5, 8),
+            (1000, 16),
+            (4097, 13),
+            (0x0FF0, 16),
+            (0b10101, 5),
+            (0, 1),
+        ] {
+            let start = expected.len();
+            expected.resize_len(start + width);
+            expected.set_bits(start..start + width, value);
+        }
+        assert_eq!(built, expected);
+        assert_eq!(built.len(), 63);
+    }
+
+    #[test]
+    fn test_empty() {
+        let builder = BitArrayBuilder::new();
+        assert!(builder.is_empty());
+        assert_eq!(builder.build(), BitArray::new(0));
+
+        let builder = BitArrayBuilder::new()
+            .bits(0, 0)
+            .bytes(&[])
+            .append(&BitArray::new(0));
+        assert_eq!(builder.len(), 0);
+    }
+
+    #[test]
+    fn test_interleaved_appends() {
+        let arr = BitArray::from_bytes(&[0xC3]);
+        let built = BitArrayBuilder::new()
+            .append(&arr)
+            .bit(true)
+            .append(&arr)
+            .bits(u64::MAX, 64)
+            .append(&arr)
+            .build();
+        assert_eq!(built.len(), 8 + 1 + 8 + 64 + 8);
+        assert_eq!(built.get_bits(0..17), 0b1_1000_0111_1100_0011);
+        assert_eq!(built.get_bits(17..81), u64::MAX);
+        assert_eq!(built.get_bits(81..89), 0xC3);
+    }
+
+    #[test]
+    #[should_panic(expected = "value 0x2000 does not fit in 13 bits")]
+    fn test_u32_bits_too_wide() {
+        BitArrayBuilder::new().u32_bits(1 << 13, 13);
+    }
+
+    #[test]
+    #[should_panic(expected = "field width 33 exceeds 32 bits")]
+    fn test_u32_bits_wide_field() {
+        BitArrayBuilder::new().u32_bits(0, 33);
+    }
+}
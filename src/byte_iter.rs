@@ -0,0 +1,120 @@
+//! Lazy bit-level iteration over any source of bytes.
+
+use core::borrow::Borrow;
+use core::iter::{FusedIterator, IntoIterator, Iterator};
+
+use crate::{Bit, BitIter, BitOrder, Byte};
+
+/// Extension trait for iterating over the bits of any iterator of bytes, without collecting them first.
+///
+/// Implemented for iterators over both `u8` and `&u8`.
+pub trait ByteIterExt: Iterator + Sized {
+    /// Iterates over the bits of each byte in turn, left-most first, pulling one byte at a time.
+    fn bits(self) -> ByteIterBits<Self>;
+}
+
+impl<I> ByteIterExt for I
+where
+    I: Iterator,
+    I::Item: Borrow<u8>,
+{
+    fn bits(self) -> ByteIterBits<Self> {
+        ByteIterBits {
+            bytes: self,
+            current: None,
+        }
+    }
+}
+
+/// Iterates over the bits of each byte from `bytes` in turn, left-most first.
+///
+/// Equivalent to calling [`ByteIterExt::bits`] on the iterator.
+pub fn bits_of<I>(bytes: I) -> ByteIterBits<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: Borrow<u8>,
+{
+    bytes.into_iter().bits()
+}
+
+/// An iterator over the bits of a stream of bytes, created by [`ByteIterExt::bits`] or [`bits_of`].
+#[derive(Debug, Clone)]
+pub struct ByteIterBits<I> {
+    /// The source of the bytes still to be read.
+    bytes: I,
+    /// The remaining bits of the byte most recently read.
+    current: Option<BitIter>,
+}
+
+impl<I> Iterator for ByteIterBits<I>
+where
+    I: Iterator,
+    I::Item: Borrow<u8>,
+{
+    type Item = Bit;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(bit) = self.current.as_mut().and_then(Iterator::next) {
+                return Some(bit);
+            }
+            let byte = Byte::from(*self.bytes.next()?.borrow());
+            self.current = Some(byte.bits_ordered(BitOrder::Msb0));
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let current = self.current.as_ref().map_or(0, ExactSizeIterator::len);
+        let (lower, upper) = self.bytes.size_hint();
+        (
+            lower.saturating_mul(8).saturating_add(current),
+            upper
+                .and_then(|upper| upper.checked_mul(8))
+                .and_then(|upper| upper.checked_add(current)),
+        )
+    }
+}
+
+impl<I> FusedIterator for ByteIterBits<I>
+where
+    I: FusedIterator,
+    I::Item: Borrow<u8>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    #[test]
+    fn test_bit_order() {
+        // "1100_1101" is read from the left-most bit, as in the crate's indexing table.
+        let bits = [0b1100_1101u8, 0b1000_0000].iter().bits();
+        let expected = [1, 1, 0, 0, 1, 1, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0];
+        assert!(bits.map(|bit| bit.as_u8()).eq(expected.iter().copied()));
+        assert_eq!(bits_of([0xA5u8]).filter(|bit| bit.is_one()).count(), 4);
+        assert_eq!(bits_of(core::iter::empty::<u8>()).next(), None);
+    }
+
+    #[test]
+    fn test_lazy() {
+        let pulled = Cell::new(0);
+        let source = [0xFFu8, 0x00, 0xFF]
+            .iter()
+            .inspect(|_| pulled.set(pulled.get() + 1));
+        let mut bits = source.bits();
+        assert_eq!(bits.by_ref().take(3).count(), 3);
+        assert_eq!(pulled.get(), 1);
+        assert_eq!(bits.size_hint(), (21, Some(21)));
+        assert_eq!(bits.count(), 21);
+        assert_eq!(pulled.get(), 3);
+    }
+
+    #[test]
+    fn test_size_hint() {
+        let bits = bits_of((0..10u8).filter(|b| b % 2 == 0));
+        assert_eq!(bits.size_hint(), (0, Some(80)));
+        assert_eq!(bits.count(), 40);
+    }
+}
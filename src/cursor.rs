@@ -0,0 +1,184 @@
+//! A byte-oriented cursor over the bits of an array.
+
+use std::borrow::{Borrow, BorrowMut};
+use std::convert::TryFrom;
+use std::io::{self, SeekFrom};
+
+use crate::{BitArray, Byte};
+
+/// Reads and writes the bits of a [`BitArray`] as a stream of bytes, like [`io::Cursor`].
+///
+/// The cursor holds either an owned `BitArray` or a `&mut BitArray`, and a position counted in
+/// bytes. Reading yields the packed bytes of the array; if its length is not a multiple of 8, the
+/// final byte is padded on the right with zeros. Writing overwrites the eight bits at the position
+/// with each byte and grows the array, a whole byte at a time, when it writes past the end.
+#[derive(Debug, Clone, Default)]
+pub struct BitArrayCursor<A> {
+    /// The array being read or written.
+    inner: A,
+    /// The byte position of the next read or write.
+    pos: u64,
+}
+
+impl<A> BitArrayCursor<A> {
+    /// Creates a cursor over `inner`, starting at its first byte.
+    pub fn new(inner: A) -> Self {
+        Self { inner, pos: 0 }
+    }
+
+    /// Consumes the cursor, returning the underlying array.
+    pub fn into_inner(self) -> A {
+        self.inner
+    }
+
+    /// The underlying array.
+    pub fn get_ref(&self) -> &A {
+        &self.inner
+    }
+
+    /// The position of the cursor, in bytes from the start of the array.
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /// Moves the cursor to `pos` bytes from the start of the array.
+    ///
+    /// The position may be past the end; writing there first fills the gap with `0` bits.
+    pub fn set_position(&mut self, pos: u64) {
+        self.pos = pos;
+    }
+}
+
+impl<A: Borrow<BitArray>> io::Read for BitArrayCursor<A> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let bytes = self.inner.borrow().as_bytes();
+        let start = usize::try_from(self.pos).map_or(bytes.len(), |pos| pos.min(bytes.len()));
+        let n = buf.len().min(bytes.len() - start);
+        buf[..n].copy_from_slice(&bytes[start..start + n]);
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl<A: BorrowMut<BitArray>> io::Write for BitArrayCursor<A> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let start = usize::try_from(self.pos).map_err(|_| {
+            io::Error::new(io::ErrorKind::InvalidInput, "cursor position out of range")
+        })?;
+        let arr = self.inner.borrow_mut();
+        let end = start + buf.len();
+        if end * 8 > arr.len() {
+            arr.resize_len(end * 8);
+        }
+        for (dst, &b) in arr.bytes_mut()[start..end].iter_mut().zip(buf) {
+            *dst = Byte(b);
+        }
+        self.pos += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<A: Borrow<BitArray>> io::Seek for BitArrayCursor<A> {
+    fn seek(&mut self, style: SeekFrom) -> io::Result<u64> {
+        let (base, offset) = match style {
+            SeekFrom::Start(pos) => {
+                self.pos = pos;
+                return Ok(pos);
+            }
+            SeekFrom::End(offset) => (self.inner.borrow().as_bytes().len() as u64, offset),
+            SeekFrom::Current(offset) => (self.pos, offset),
+        };
+        match base.checked_add_signed(offset) {
+            Some(pos) => {
+                self.pos = pos;
+                Ok(pos)
+            }
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Seek, Write};
+
+    #[test]
+    fn test_read_pads_final_byte() {
+        let arr: BitArray = "1011001110".chars().map(|c| c == '1').collect();
+        let mut buf = Vec::new();
+        BitArrayCursor::new(&arr).read_to_end(&mut buf).unwrap();
+        assert_eq!(buf, [0b1011_0011, 0b1000_0000]);
+    }
+
+    #[test]
+    fn test_copy_both_ways() {
+        let data: Vec<u8> = (0..=255).collect();
+        let mut arr = BitArray::new(0);
+        let copied = io::copy(&mut &data[..], &mut BitArrayCursor::new(&mut arr)).unwrap();
+        assert_eq!(copied, 256);
+        assert_eq!(arr.len(), 256 * 8);
+        assert_eq!(arr.as_bytes(), &data[..]);
+
+        let mut out = Vec::new();
+        io::copy(&mut BitArrayCursor::new(arr), &mut out).unwrap();
+        assert_eq!(out, data);
+    }
+
+    #[test]
+    fn test_seek_then_read() {
+        let arr = BitArray::from_bytes(&[1, 2, 3, 4, 5]);
+        let mut cursor = BitArrayCursor::new(&arr);
+        let mut buf = [0; 2];
+        assert_eq!(cursor.seek(SeekFrom::Start(1)).unwrap(), 1);
+        cursor.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [2, 3]);
+        assert_eq!(cursor.seek(SeekFrom::End(-1)).unwrap(), 4);
+        assert_eq!(cursor.read(&mut buf).unwrap(), 1);
+        assert_eq!(buf[0], 5);
+        assert_eq!(cursor.seek(SeekFrom::Current(-4)).unwrap(), 1);
+        assert_eq!(cursor.read(&mut buf).unwrap(), 2);
+        assert_eq!(buf, [2, 3]);
+        assert!(cursor.seek(SeekFrom::Current(-4)).is_err());
+        assert_eq!(cursor.position(), 3);
+
+        cursor.set_position(10);
+        assert_eq!(cursor.read(&mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_write_in_middle() {
+        let mut arr = BitArray::from_bytes(&[1, 2, 3, 4]);
+        let mut cursor = BitArrayCursor::new(&mut arr);
+        cursor.seek(SeekFrom::Start(2)).unwrap();
+        cursor.write_all(&[0xAA, 0xBB, 0xCC]).unwrap();
+        assert_eq!(cursor.position(), 5);
+        assert_eq!(arr.as_bytes(), [1, 2, 0xAA, 0xBB, 0xCC]);
+        assert_eq!(arr.len(), 40);
+    }
+
+    #[test]
+    fn test_write_past_end() {
+        let mut cursor = BitArrayCursor::new(BitArray::new(3));
+        cursor.set_position(2);
+        cursor.write_all(&[0xFF]).unwrap();
+        let arr = cursor.into_inner();
+        assert_eq!(arr.len(), 24);
+        assert_eq!(arr.as_bytes(), [0, 0, 0xFF]);
+    }
+
+    #[test]
+    fn test_write_over_partial_byte() {
+        let mut arr: BitArray = "101".chars().map(|c| c == '1').collect();
+        BitArrayCursor::new(&mut arr).write_all(&[0x0F]).unwrap();
+        assert_eq!(arr.len(), 8);
+        assert_eq!(arr.as_bytes(), [0x0F]);
+    }
+}
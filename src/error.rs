@@ -0,0 +1,112 @@
+//! Errors returned by the fallible bit accessors.
+
+use core::fmt;
+
+/// The error returned when a bit operation cannot be performed.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum BitError {
+    /// An index was not less than the number of bits in the container.
+    OutOfBounds {
+        /// The offending index.
+        index: usize,
+        /// The number of bits in the container.
+        len: usize,
+    },
+    /// A length was greater than the number of bits available.
+    InvalidLength {
+        /// The offending length.
+        len: usize,
+        /// The number of bits available.
+        max: usize,
+    },
+    /// A value had bits set beyond the width it was to be written in.
+    ValueTooWide {
+        /// The offending value.
+        value: u64,
+        /// The number of bits available for the value.
+        width: u32,
+    },
+    /// Two containers that must be the same length were not.
+    LengthMismatch {
+        /// The length of the first container.
+        left: usize,
+        /// The length of the second container.
+        right: usize,
+    },
+    /// A range ended before it started.
+    DecreasingRange {
+        /// The start of the range.
+        start: usize,
+        /// The end of the range, which is less than the start.
+        end: usize,
+    },
+    /// A length that must be a power of two was not.
+    NotPowerOfTwo {
+        /// The offending length.
+        len: usize,
+    },
+    /// A length that must be a whole number of fixed-width items was not.
+    NotMultipleOf {
+        /// The offending length.
+        len: usize,
+        /// The width of each item.
+        width: usize,
+    },
+    /// Two ranges that must be disjoint shared some bits.
+    Overlap {
+        /// The start of the first range.
+        first: usize,
+        /// The start of the second range.
+        second: usize,
+        /// The length of both ranges.
+        len: usize,
+    },
+    /// A division had a divisor of zero.
+    DivideByZero,
+    /// A pattern that must hold at least one bit was empty.
+    EmptyPattern,
+    /// Bits past the end of a container, which must be zero, were not.
+    NonZeroPadding {
+        /// The number of bits in the container.
+        len: usize,
+    },
+}
+
+impl fmt::Display for BitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BitError::OutOfBounds { index, len } => {
+                write!(f, "index {} out of range for length {}", index, len)
+            }
+            BitError::InvalidLength { len, max } => {
+                write!(f, "length {} exceeds the {} bits available", len, max)
+            }
+            BitError::ValueTooWide { value, width } => {
+                write!(f, "value {:#x} does not fit in {} bits", value, width)
+            }
+            BitError::LengthMismatch { left, right } => {
+                write!(f, "lengths {} and {} do not match", left, right)
+            }
+            BitError::DecreasingRange { start, end } => {
+                write!(f, "range {}..{} is decreasing", start, end)
+            }
+            BitError::NotPowerOfTwo { len } => write!(f, "length {} is not a power of two", len),
+            BitError::NotMultipleOf { len, width } => {
+                write!(f, "length {} is not a multiple of {}", len, width)
+            }
+            BitError::Overlap { first, second, len } => write!(
+                f,
+                "ranges of length {} starting at {} and {} overlap",
+                len, first, second
+            ),
+            BitError::DivideByZero => f.write_str("division by zero"),
+            BitError::EmptyPattern => f.write_str("pattern is empty"),
+            BitError::NonZeroPadding { len } => {
+                write!(f, "padding bits past length {} are not zero", len)
+            }
+        }
+    }
+}
+
+impl core::error::Error for BitError {}
@@ -0,0 +1,275 @@
+//! Lazy bitwise combinations of arrays, evaluated a block at a time.
+
+use alloc::vec::Vec;
+use core::iter::{FusedIterator, Iterator};
+
+use crate::{Bit, BitArray, BitError};
+
+/// A bitwise operator applied by a [`BitExpr`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum BitOp {
+    And,
+    Or,
+    Xor,
+}
+
+impl BitOp {
+    /// Applies the operator to two blocks.
+    fn apply(self, a: u64, b: u64) -> u64 {
+        match self {
+            BitOp::And => a & b,
+            BitOp::Or => a | b,
+            BitOp::Xor => a ^ b,
+        }
+    }
+}
+
+/// A bitwise combination of equal-length arrays, created by [`BitArray::and`], [`BitArray::or`]
+/// and [`BitArray::xor`], that is evaluated only when queried.
+///
+/// Operators apply from left to right, so `a.and(&b)?.or(&c)?` is `(a & b) | c`. No
+/// intermediate arrays are built: each query combines the corresponding blocks of every operand
+/// in one pass.
+///
+/// ```
+/// use bit_array_rs::BitArray;
+///
+/// let a = BitArray::from_bytes(&[0b1100_1100]);
+/// let b = BitArray::from_bytes(&[0b1010_1010]);
+/// let c = BitArray::from_bytes(&[0b0000_0001]);
+/// let expr = a.and(&b)?.or(&c)?;
+/// assert_eq!(expr.count_ones(), 3);
+/// assert_eq!(expr.materialize().as_bytes(), [0b1000_1001]);
+/// # Ok::<(), bit_array_rs::BitError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct BitExpr<'a> {
+    /// The left-most operand.
+    first: &'a BitArray,
+    /// Each later operand, with the operator combining it with everything before it.
+    rest: Vec<(BitOp, &'a BitArray)>,
+}
+
+impl<'a> BitExpr<'a> {
+    /// Starts an expression combining `first` and `second` with `op`.
+    fn new(first: &'a BitArray, op: BitOp, second: &'a BitArray) -> Result<Self, BitError> {
+        BitExpr {
+            first,
+            rest: Vec::new(),
+        }
+        .push(op, second)
+    }
+
+    /// Appends `operand`, combined with the expression so far by `op`.
+    ///
+    /// Returns an error if `operand` is not the same length as the expression.
+    fn push(mut self, op: BitOp, operand: &'a BitArray) -> Result<Self, BitError> {
+        if operand.len() != self.len() {
+            return Err(BitError::LengthMismatch {
+                left: self.len(),
+                right: operand.len(),
+            });
+        }
+        self.rest.push((op, operand));
+        Ok(self)
+    }
+
+    /// Extends the expression to the AND of its value with `other`.
+    ///
+    /// Returns an error if `other` is not the same length as the expression.
+    pub fn and(self, other: &'a BitArray) -> Result<Self, BitError> {
+        self.push(BitOp::And, other)
+    }
+
+    /// Extends the expression to the OR of its value with `other`.
+    ///
+    /// Returns an error if `other` is not the same length as the expression.
+    pub fn or(self, other: &'a BitArray) -> Result<Self, BitError> {
+        self.push(BitOp::Or, other)
+    }
+
+    /// Extends the expression to the XOR of its value with `other`.
+    ///
+    /// Returns an error if `other` is not the same length as the expression.
+    pub fn xor(self, other: &'a BitArray) -> Result<Self, BitError> {
+        self.push(BitOp::Xor, other)
+    }
+
+    /// The number of bits in the value of the expression.
+    pub fn len(&self) -> usize {
+        self.first.len()
+    }
+
+    /// True if the value of the expression holds no bits.
+    pub fn is_empty(&self) -> bool {
+        self.first.is_empty()
+    }
+
+    /// Block `i` of the value, as stored.
+    ///
+    /// Every operator maps zero padding to zero, so the padding of the result stays clear.
+    fn block(&self, i: usize) -> u64 {
+        self.rest
+            .iter()
+            .fold(self.first.blocks[i], |acc, &(op, arr)| {
+                op.apply(acc, arr.blocks[i])
+            })
+    }
+
+    /// The number of `1` bits in the value, without building it.
+    pub fn count_ones(&self) -> usize {
+        (0..self.first.blocks.len())
+            .map(|i| self.block(i).count_ones() as usize)
+            .sum()
+    }
+
+    /// Getter for the bit at index `idx` of the value.
+    ///
+    /// Panics if `idx` is not less than the length of the expression.
+    pub fn get(&self, idx: usize) -> Bit {
+        let bit = self.first.get(idx);
+        self.rest.iter().fold(bit, |acc, &(op, arr)| {
+            Bit(op.apply(u64::from(*acc), u64::from(*arr.get(idx))) == 1)
+        })
+    }
+
+    /// Iterates over the indices of the `1` bits of the value, in ascending order.
+    pub fn iter_ones(&self) -> ExprOnes<'_, 'a> {
+        ExprOnes {
+            expr: self,
+            block: 0,
+            bits: 0,
+        }
+    }
+
+    /// Evaluates the expression into a new array, in one pass over the blocks of every operand.
+    pub fn materialize(&self) -> BitArray {
+        let mut res = BitArray::new(self.len());
+        for (i, block) in res.blocks.iter_mut().enumerate() {
+            *block = self.block(i);
+        }
+        res.debug_check_padding();
+        res
+    }
+}
+
+impl BitArray {
+    /// A lazy expression for the AND of the array with `other`, extended with [`BitExpr::and`],
+    /// [`BitExpr::or`] and [`BitExpr::xor`].
+    ///
+    /// Returns an error if the arrays are not the same length.
+    pub fn and<'a>(&'a self, other: &'a BitArray) -> Result<BitExpr<'a>, BitError> {
+        BitExpr::new(self, BitOp::And, other)
+    }
+
+    /// A lazy expression for the OR of the array with `other`.
+    ///
+    /// Returns an error if the arrays are not the same length.
+    pub fn or<'a>(&'a self, other: &'a BitArray) -> Result<BitExpr<'a>, BitError> {
+        BitExpr::new(self, BitOp::Or, other)
+    }
+
+    /// A lazy expression for the XOR of the array with `other`.
+    ///
+    /// Returns an error if the arrays are not the same length.
+    pub fn xor<'a>(&'a self, other: &'a BitArray) -> Result<BitExpr<'a>, BitError> {
+        BitExpr::new(self, BitOp::Xor, other)
+    }
+}
+
+/// An iterator over the indices of the `1` bits of a [`BitExpr`], created by [`BitExpr::iter_ones`].
+///
+/// Each block of the value is computed once, when the iterator reaches it.
+#[derive(Debug, Clone)]
+pub struct ExprOnes<'e, 'a> {
+    /// The expression being iterated over.
+    expr: &'e BitExpr<'a>,
+    /// The index of the next block to compute.
+    block: usize,
+    /// The `1` bits of the current block not yet dispatched, in logical order.
+    bits: u64,
+}
+
+impl Iterator for ExprOnes<'_, '_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.bits == 0 {
+            if self.block == self.expr.first.blocks.len() {
+                return None;
+            }
+            self.bits = u64::from_be(self.expr.block(self.block));
+            self.block += 1;
+        }
+        let offset = self.bits.leading_zeros() as usize;
+        self.bits &= !(0x8000_0000_0000_0000 >> offset);
+        Some((self.block - 1) * 64 + offset)
+    }
+}
+
+impl FusedIterator for ExprOnes<'_, '_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Prbs, PrbsKind};
+    use alloc::vec::Vec;
+
+    fn random(prbs: &mut Prbs, len: usize) -> BitArray {
+        prbs.take(len).collect()
+    }
+
+    #[test]
+    fn test_matches_eager_operators() {
+        let mut prbs = Prbs::new(PrbsKind::Prbs15);
+        for len in [0, 1, 63, 64, 65, 200] {
+            let (a, b, c, d) = (
+                random(&mut prbs, len),
+                random(&mut prbs, len),
+                random(&mut prbs, len),
+                random(&mut prbs, len),
+            );
+            let expr = a.and(&b).unwrap().or(&c).unwrap().xor(&d).unwrap();
+            let eager = &(&(&a & &b) | &c) ^ &d;
+            assert_eq!(expr.len(), len);
+            assert_eq!(expr.materialize(), eager);
+            assert_eq!(expr.count_ones(), eager.count_ones());
+            assert!(expr.iter_ones().eq(eager.iter_ones()));
+            assert!((0..len).all(|idx| expr.get(idx) == eager.get(idx)));
+        }
+    }
+
+    #[test]
+    fn test_each_operator() {
+        let a = BitArray::from_bytes(&[0b1100_1100]);
+        let b = BitArray::from_bytes(&[0b1010_1010]);
+        assert_eq!(a.and(&b).unwrap().materialize().as_bytes(), [0b1000_1000]);
+        assert_eq!(a.or(&b).unwrap().materialize().as_bytes(), [0b1110_1110]);
+        assert_eq!(a.xor(&b).unwrap().materialize().as_bytes(), [0b0110_0110]);
+    }
+
+    #[test]
+    fn test_count_without_materializing() {
+        let len = 100_000;
+        let mut evens = BitArray::new(len);
+        let mut threes = BitArray::new(len);
+        let mut fives = BitArray::new(len);
+        (0..len).step_by(2).for_each(|i| evens.set(i, true));
+        (0..len).step_by(3).for_each(|i| threes.set(i, true));
+        (0..len).step_by(5).for_each(|i| fives.set(i, true));
+        // Multiples of 6, plus the multiples of 5 that are not.
+        let expr = evens.and(&threes).unwrap().or(&fives).unwrap();
+        let expected = (0..len).filter(|i| i % 6 == 0 || i % 5 == 0).count();
+        assert_eq!(expr.count_ones(), expected);
+        let ones: Vec<usize> = expr.iter_ones().take(4).collect();
+        assert_eq!(ones, [0, 5, 6, 10]);
+    }
+
+    #[test]
+    fn test_length_mismatch() {
+        let (a, b, c) = (BitArray::new(8), BitArray::new(8), BitArray::new(9));
+        let err = BitError::LengthMismatch { left: 8, right: 9 };
+        assert_eq!(a.and(&c).unwrap_err(), err);
+        assert_eq!(a.xor(&b).unwrap().or(&c).unwrap_err(), err);
+    }
+}
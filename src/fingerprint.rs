@@ -0,0 +1,222 @@
+//! A content fingerprint of a sequence of bits that is stable across platforms and versions.
+
+#[cfg(feature = "alloc")]
+use crate::BitArray;
+
+/// The FNV-1a 64-bit offset basis.
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// The FNV-1a 64-bit prime.
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Computes the fingerprint of a sequence of bits fed in one bit or one array at a time.
+///
+/// The algorithm is fixed and will not change between versions: FNV-1a 64 over the bits packed
+/// into bytes in the crate's order, the final byte padded with zeros, followed by the number of
+/// bits as 8 little-endian bytes. Including the length means `0` and `00` differ, and the result
+/// never depends on padding. Feeding bits in any split gives the same fingerprint as
+/// `BitArray::fingerprint64` of all of them at once.
+#[derive(Debug, Clone)]
+pub struct BitFingerprint {
+    /// The hash of every whole byte so far.
+    hash: u64,
+    /// The bits of the current partial byte, from the left.
+    byte: u8,
+    /// The number of bits fed so far.
+    len: u64,
+}
+
+impl Default for BitFingerprint {
+    fn default() -> Self {
+        Self {
+            hash: FNV_OFFSET,
+            byte: 0,
+            len: 0,
+        }
+    }
+}
+
+impl BitFingerprint {
+    /// Creates a fingerprint of no bits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mixes one byte into `hash`.
+    fn absorb(hash: u64, byte: u8) -> u64 {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    }
+
+    /// Feeds one more bit.
+    pub fn push(&mut self, bit: bool) {
+        if bit {
+            self.byte |= 0x80 >> (self.len % 8);
+        }
+        self.len += 1;
+        if self.len.is_multiple_of(8) {
+            self.hash = Self::absorb(self.hash, self.byte);
+            self.byte = 0;
+        }
+    }
+
+    /// Feeds every bit of `arr`, a byte at a time.
+    #[cfg(feature = "alloc")]
+    pub fn extend_from_bit_array(&mut self, arr: &BitArray) {
+        let whole = arr.len() / 8;
+        let shift = self.len % 8;
+        for &b in &arr.as_bytes()[..whole] {
+            if shift == 0 {
+                self.hash = Self::absorb(self.hash, b);
+            } else {
+                self.hash = Self::absorb(self.hash, self.byte | (b >> shift));
+                self.byte = b << (8 - shift);
+            }
+        }
+        self.len += whole as u64 * 8;
+        for idx in whole * 8..arr.len() {
+            self.push(*arr.get(idx));
+        }
+    }
+
+    /// The number of bits fed so far.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// True if no bits have been fed.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The fingerprint of the bits fed so far; more bits may still be fed afterwards.
+    pub fn finish(&self) -> u64 {
+        let mut hash = self.hash;
+        if !self.len.is_multiple_of(8) {
+            hash = Self::absorb(hash, self.byte);
+        }
+        self.len
+            .to_le_bytes()
+            .iter()
+            .fold(hash, |hash, &b| Self::absorb(hash, b))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl BitArray {
+    /// A 64-bit fingerprint of the length and bits of the array, by the fixed algorithm
+    /// documented on [`BitFingerprint`].
+    ///
+    /// Unlike `Hash`, the value is the same on every platform and in every version of the crate.
+    pub fn fingerprint64(&self) -> u64 {
+        self.fingerprint_incremental().finish()
+    }
+
+    /// A [`BitFingerprint`] that has been fed the bits of the array, to which more bits can be fed.
+    pub fn fingerprint_incremental(&self) -> BitFingerprint {
+        let mut fp = BitFingerprint::new();
+        fp.extend_from_bit_array(self);
+        fp
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::{Prbs, PrbsKind};
+    #[cfg(feature = "alloc")]
+    use alloc::vec::Vec;
+
+    // These values are part of the format, and must never change.
+    /// The fingerprint of no bits: FNV-1a 64 of the length 0 as 8 little-endian bytes.
+    const GOLDEN_EMPTY: u64 = 0xa8c7_f832_281a_39c5;
+    /// The fingerprint of `1011`: FNV-1a 64 of `0xB0`, then the length 4.
+    const GOLDEN_1011: u64 = 0xe89e_bf06_fc9e_a98b;
+    /// The fingerprint of `0`.
+    const GOLDEN_0: u64 = 0xc709_bb31_19a0_df9e;
+    /// The fingerprint of `00`.
+    const GOLDEN_00: u64 = 0xa80e_f428_0eb1_957d;
+    /// The fingerprint of the bytes of `hello world`.
+    const GOLDEN_HELLO: u64 = 0xc199_4228_1513_609f;
+
+    #[cfg(feature = "alloc")]
+    fn bits(digits: &str) -> BitArray {
+        digits.chars().map(|c| c == '1').collect()
+    }
+
+    #[test]
+    fn test_push() {
+        let mut fp = BitFingerprint::new();
+        assert!(fp.is_empty());
+        assert_eq!(fp.finish(), GOLDEN_EMPTY);
+        for bit in [true, false, true, true] {
+            fp.push(bit);
+        }
+        assert_eq!(fp.len(), 4);
+        assert_eq!(fp.finish(), GOLDEN_1011);
+
+        let mut fp = BitFingerprint::new();
+        fp.push(false);
+        assert_eq!(fp.finish(), GOLDEN_0);
+        fp.push(false);
+        assert_eq!(fp.finish(), GOLDEN_00);
+
+        let mut fp = BitFingerprint::new();
+        for &b in b"hello world" {
+            (0..8).for_each(|i| fp.push(b & (0x80 >> i) != 0));
+        }
+        assert_eq!(fp.finish(), GOLDEN_HELLO);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_golden() {
+        assert_eq!(BitArray::new(0).fingerprint64(), GOLDEN_EMPTY);
+        assert_eq!(bits("1011").fingerprint64(), GOLDEN_1011);
+        assert_eq!(bits("0").fingerprint64(), GOLDEN_0);
+        assert_eq!(bits("00").fingerprint64(), GOLDEN_00);
+        assert_eq!(
+            BitArray::from_bytes(b"hello world").fingerprint64(),
+            GOLDEN_HELLO
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_length_sensitive() {
+        let zeros: Vec<u64> = (0..20).map(|n| BitArray::new(n).fingerprint64()).collect();
+        for (i, a) in zeros.iter().enumerate() {
+            assert!(zeros[i + 1..].iter().all(|b| a != b));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_padding_insensitive() {
+        let (ptr, bit_len, byte_cap) = bits("101").into_raw_parts();
+        // SAFETY: the first byte is in the buffer, and the parts are then used once.
+        let dirty = unsafe {
+            *ptr = 0xBF;
+            BitArray::from_raw_parts(ptr, bit_len, byte_cap)
+        };
+        assert_eq!(dirty.fingerprint64(), bits("101").fingerprint64());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_incremental_matches_one_shot() {
+        let arr: BitArray = Prbs::new(PrbsKind::Prbs15).take(300).collect();
+        let expected = arr.fingerprint64();
+        for split in [0, 1, 7, 8, 13, 64, 299, 300] {
+            let head: BitArray = arr.iter().take(split).collect();
+            let tail: BitArray = arr.iter().skip(split).collect();
+            let mut fp = head.fingerprint_incremental();
+            fp.extend_from_bit_array(&tail);
+            assert_eq!(fp.finish(), expected);
+
+            let mut fp = head.fingerprint_incremental();
+            tail.iter().for_each(|bit| fp.push(*bit));
+            assert_eq!(fp.finish(), expected);
+        }
+    }
+}
@@ -0,0 +1,375 @@
+//! Bit-level views of the unsigned primitive integers.
+
+use core::iter::{DoubleEndedIterator, ExactSizeIterator, FusedIterator, Iterator, Rev};
+
+use crate::Bit;
+#[cfg(feature = "alloc")]
+use crate::BitArray;
+
+/// Extension trait exposing the bits of an unsigned primitive integer as [`Bit`]s.
+///
+/// Positions count from the least-significant bit, so position `i` is the bit selected by `1 << i`.
+pub trait BitsExt {
+    /// Iterates over every bit of the value, least-significant first.
+    fn bits(&self) -> IntBits;
+
+    /// Iterates over every bit of the value, most-significant first.
+    fn bits_msb(&self) -> Rev<IntBits>;
+
+    /// Iterates over the positions of the set bits, in ascending order.
+    fn iter_ones(&self) -> IntOnes;
+
+    /// The low `width` bits of the value as an array, most-significant first, so that reading the
+    /// array back as an integer gives the value with any higher bits cleared.
+    ///
+    /// Panics if `width` exceeds the width of the type.
+    #[cfg(feature = "alloc")]
+    fn to_bitarray(&self, width: u32) -> BitArray;
+}
+
+macro_rules! impl_bits_ext {
+    ($($t:ty),*) => {
+        $(
+            impl BitsExt for $t {
+                fn bits(&self) -> IntBits {
+                    IntBits {
+                        value: *self as u128,
+                        front: 0,
+                        back: <$t>::BITS,
+                    }
+                }
+
+                fn bits_msb(&self) -> Rev<IntBits> {
+                    self.bits().rev()
+                }
+
+                fn iter_ones(&self) -> IntOnes {
+                    IntOnes {
+                        value: *self as u128,
+                    }
+                }
+
+                #[cfg(feature = "alloc")]
+                fn to_bitarray(&self, width: u32) -> BitArray {
+                    assert!(
+                        width <= <$t>::BITS,
+                        "width {} exceeds the {} bits of {}",
+                        width,
+                        <$t>::BITS,
+                        stringify!($t)
+                    );
+                    let value = *self as u128;
+                    BitArray::from_fn(width as usize, |i| {
+                        (value >> (width as usize - 1 - i)) & 1 == 1
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_bits_ext!(u8, u16, u32, u64, u128, usize);
+
+/// Extension trait for reading and writing single bits of an unsigned primitive integer in place.
+///
+/// Unlike [`BitsExt`], positions follow the crate's [`Msb0`](crate::BitOrder::Msb0) convention,
+/// which the `_msb0` suffix of every method spells out: index 0 is the most significant bit, so
+/// for a type of `N` bits index `idx` is the bit selected by `1 << (N - 1 - idx)`, not by
+/// `1 << idx`. [`BitsExt::bits_msb`] iterates in the same order; [`BitsExt::bits`] and
+/// [`BitsExt::iter_ones`] use the reverse.
+///
+/// Every method panics if `idx` is not less than the width of the type.
+///
+/// ```
+/// use bit_array_rs::BitAccess;
+///
+/// // A 16-bit status register: bit 0 (the top bit) is "ready", bit 3 "error".
+/// let mut status: u16 = 0b1000_0000_0000_0001;
+/// assert!(status.bit_msb0(0).is_one());
+/// assert!(status.bit_msb0(3).is_zero());
+/// assert!(status.bit_msb0(15).is_one());
+///
+/// // Clear "ready" and flag an error.
+/// status.set_bit_msb0(0, false);
+/// status.toggle_bit_msb0(3);
+/// assert_eq!(status, 0b0001_0000_0000_0001);
+/// ```
+pub trait BitAccess {
+    /// Getter for the bit at index `idx`, counting from the most significant bit.
+    fn bit_msb0(&self, idx: usize) -> Bit;
+
+    /// Setter for the bit at index `idx`, counting from the most significant bit.
+    fn set_bit_msb0(&mut self, idx: usize, val: bool);
+
+    /// Flips the bit at index `idx`, counting from the most significant bit, returning its new value.
+    fn toggle_bit_msb0(&mut self, idx: usize) -> Bit;
+}
+
+macro_rules! impl_bit_access {
+    ($($t:ty),*) => {
+        $(
+            impl BitAccess for $t {
+                fn bit_msb0(&self, idx: usize) -> Bit {
+                    let shift = msb0_shift(idx, <$t>::BITS);
+                    Bit::from((*self >> shift) & 1 == 1)
+                }
+
+                fn set_bit_msb0(&mut self, idx: usize, val: bool) {
+                    let mask: $t = 1 << msb0_shift(idx, <$t>::BITS);
+                    if val {
+                        *self |= mask;
+                    } else {
+                        *self &= !mask;
+                    }
+                }
+
+                fn toggle_bit_msb0(&mut self, idx: usize) -> Bit {
+                    *self ^= 1 << msb0_shift(idx, <$t>::BITS);
+                    self.bit_msb0(idx)
+                }
+            }
+        )*
+    };
+}
+
+impl_bit_access!(u8, u16, u32, u64, u128, usize);
+
+/// The shift selecting bit `idx`, counting from the most significant, of a `width`-bit integer.
+///
+/// Panics if `idx` is not less than `width`.
+fn msb0_shift(idx: usize, width: u32) -> u32 {
+    assert!(
+        idx < width as usize,
+        "bit index {} out of range for a {}-bit integer",
+        idx,
+        width
+    );
+    width - 1 - idx as u32
+}
+
+/// Reverses the order of the low `width` bits of `idx`, as used for FFT input reordering.
+///
+/// Bits of `idx` above `width` are ignored. Panics if `width` exceeds `usize::BITS`.
+pub fn reverse_index_bits(idx: usize, width: u32) -> usize {
+    assert!(
+        width <= usize::BITS,
+        "width {} exceeds the {} bits of usize",
+        width,
+        usize::BITS
+    );
+    if width == 0 {
+        0
+    } else {
+        idx.reverse_bits() >> (usize::BITS - width)
+    }
+}
+
+/// An iterator over the bits of an integer, created by [`BitsExt::bits`].
+#[derive(Debug, Clone)]
+pub struct IntBits {
+    /// The integer being iterated over, widened to the largest supported type.
+    value: u128,
+    /// The position of the next bit dispatched from the front.
+    front: u32,
+    /// One past the position of the next bit dispatched from the back.
+    back: u32,
+}
+
+impl Iterator for IntBits {
+    type Item = Bit;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front < self.back {
+            let res = Bit::from(self.value & (1 << self.front) != 0);
+            self.front += 1;
+            Some(res)
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.back - self.front) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl DoubleEndedIterator for IntBits {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front < self.back {
+            self.back -= 1;
+            Some(Bit::from(self.value & (1 << self.back) != 0))
+        } else {
+            None
+        }
+    }
+}
+
+impl ExactSizeIterator for IntBits {}
+
+impl FusedIterator for IntBits {}
+
+/// An iterator over the positions of the set bits of an integer, created by [`BitsExt::iter_ones`].
+#[derive(Debug, Clone)]
+pub struct IntOnes {
+    /// The bits not yet dispatched.
+    value: u128,
+}
+
+impl Iterator for IntOnes {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.value == 0 {
+            None
+        } else {
+            let pos = self.value.trailing_zeros();
+            self.value &= self.value - 1;
+            Some(pos as usize)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.value.count_ones() as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for IntOnes {}
+
+impl FusedIterator for IntOnes {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Byte;
+
+    #[test]
+    fn test_agrees_with_byte() {
+        for value in [0u8, 1, 0b1010_0001, 0b0110_1100, u8::MAX] {
+            assert!(value.bits().eq(Byte::from(value)));
+            let byte = Byte::from(value);
+            assert!(value
+                .bits_msb()
+                .zip((0..8).rev())
+                .all(|(bit, idx)| bit == byte.get_bit(idx)));
+        }
+    }
+
+    #[test]
+    fn test_iter_ones() {
+        let sparse = (1u64 << 63) | (1 << 40) | (1 << 3) | 1;
+        let mut ones = sparse.iter_ones();
+        assert_eq!(ones.len(), 4);
+        assert_eq!(ones.next(), Some(0));
+        assert_eq!(ones.next(), Some(3));
+        assert_eq!(ones.next(), Some(40));
+        assert_eq!(ones.next(), Some(63));
+        assert_eq!(ones.next(), None);
+        assert_eq!(0u32.iter_ones().next(), None);
+    }
+
+    #[test]
+    fn test_reverse_index_bits() {
+        let perm8: [usize; 8] = [0, 4, 2, 6, 1, 5, 3, 7];
+        for (i, &j) in perm8.iter().enumerate() {
+            assert_eq!(reverse_index_bits(i, 3), j);
+        }
+        let perm16: [usize; 16] = [0, 8, 4, 12, 2, 10, 6, 14, 1, 9, 5, 13, 3, 11, 7, 15];
+        for (i, &j) in perm16.iter().enumerate() {
+            assert_eq!(reverse_index_bits(i, 4), j);
+            assert_eq!(reverse_index_bits(j, 4), i);
+        }
+        assert_eq!(reverse_index_bits(0b1_0110, 4), 0b0110);
+        assert_eq!(reverse_index_bits(5, 0), 0);
+        assert_eq!(reverse_index_bits(1, usize::BITS), 1 << (usize::BITS - 1));
+    }
+
+    macro_rules! check_bit_access {
+        ($($t:ty),*) => {
+            $(
+                let mut value: $t = 0;
+                for idx in 0..<$t>::BITS as usize {
+                    let mask: $t = 1 << (<$t>::BITS as usize - 1 - idx);
+                    assert!(value.bit_msb0(idx).is_zero());
+                    value.set_bit_msb0(idx, true);
+                    assert_eq!(value, mask);
+                    assert!(value.bit_msb0(idx).is_one());
+                    assert_eq!(value.toggle_bit_msb0(idx), Bit::ZERO);
+                    assert_eq!(value, 0);
+                }
+                let pattern = <$t>::MAX / 3;
+                assert!((0..<$t>::BITS as usize).map(|idx| pattern.bit_msb0(idx)).eq(pattern.bits_msb()));
+            )*
+        };
+    }
+
+    #[test]
+    fn test_bit_access() {
+        check_bit_access!(u8, u16, u32, u64, u128, usize);
+        assert_eq!(0b1000_0000u8.bit_msb0(0), Bit::ONE);
+        assert_eq!(0x8000_0001u32.bit_msb0(31), Bit::ONE);
+    }
+
+    #[test]
+    fn test_bit_access_agrees_with_bits_ext() {
+        let width = u32::BITS as usize;
+        for value in [0u32, 1, 0x8000_0000, 0xDEAD_BEEF, 0x0F0F_1234] {
+            // Position `p` counted from the least significant bit is index `width - 1 - p`.
+            for (pos, bit) in value.bits().enumerate() {
+                assert_eq!(value.bit_msb0(width - 1 - pos), bit);
+            }
+            let msb0_ones = (0..width)
+                .rev()
+                .filter(|&idx| value.bit_msb0(idx).is_one())
+                .map(|idx| width - 1 - idx);
+            assert!(value.iter_ones().eq(msb0_ones));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "bit index 32 out of range for a 32-bit integer")]
+    fn test_bit_access_out_of_range() {
+        0u32.bit_msb0(32);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_to_bitarray() {
+        use core::convert::TryFrom;
+
+        let arr = 0b1011_0110u8.to_bitarray(8);
+        assert_eq!(arr, BitArray::from_bytes(&[0b1011_0110]));
+        assert!(arr.iter().eq(0b1011_0110u8.bits_msb()));
+        assert!(0xFFu8.to_bitarray(0).is_empty());
+
+        let value = 0xDEAD_BEEF_u32;
+        for width in [1, 7, 8, 20, 31, 32] {
+            let arr = value.to_bitarray(width);
+            assert_eq!(arr.len(), width as usize);
+            let mask = (u64::MAX >> (64 - width)) as u32;
+            assert_eq!(u32::try_from(&arr), Ok(value & mask));
+        }
+        let wide = u128::MAX - 5;
+        assert_eq!(u128::try_from(&wide.to_bitarray(128)), Ok(wide));
+        assert_eq!(u64::try_from(&(1usize << 40).to_bitarray(41)), Ok(1 << 40));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    #[should_panic(expected = "width 17 exceeds the 16 bits of u16")]
+    fn test_to_bitarray_too_wide() {
+        0u16.to_bitarray(17);
+    }
+
+    #[test]
+    fn test_widths() {
+        assert_eq!(0u16.bits().len(), 16);
+        assert_eq!(0u128.bits_msb().len(), 128);
+        assert_eq!(
+            usize::MAX.bits().filter(|b| b.is_one()).count(),
+            usize::BITS as usize
+        );
+        assert_eq!(42u32.bits().take(6).filter(|b| b.is_one()).count(), 3);
+    }
+}
@@ -0,0 +1,236 @@
+//! Linear-feedback shift registers, for scramblers and test-pattern generation.
+
+use core::iter::{FusedIterator, Iterator};
+
+use crate::Bit;
+#[cfg(feature = "alloc")]
+use crate::BitArray;
+
+/// The update rule used by an [`Lfsr`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum LfsrKind {
+    /// The taps are XORed together and the result shifted in at the top of the register.
+    Fibonacci,
+    /// The output bit is XORed into the tapped positions as the register shifts.
+    Galois,
+}
+
+/// A linear-feedback shift register of up to 64 bits.
+///
+/// The register shifts right: each step outputs the least-significant bit of the state.
+/// For the polynomial `x^16 + x^14 + x^13 + x^11 + 1` the taps are `0x002D` in Fibonacci form
+/// (bits `0, 2, 3, 5`) and `0xB400` in Galois form (bits `15, 13, 12, 10`).
+///
+/// A register whose state is all zeros stays all zeros.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Lfsr {
+    /// The update rule.
+    kind: LfsrKind,
+    /// The number of bits in the register.
+    width: u32,
+    /// The tapped bits of the register.
+    taps: u64,
+    /// The current contents of the register.
+    state: u64,
+}
+
+impl Lfsr {
+    /// Creates a register of `width` bits with the given taps, seeded with `seed`.
+    ///
+    /// Panics if `width` is not in `1..=64`, or if `taps` or `seed` has bits set beyond `width`.
+    pub fn new(kind: LfsrKind, width: u32, taps: u64, seed: u64) -> Self {
+        assert!(
+            (1..=64).contains(&width),
+            "width {} is not in 1..=64",
+            width
+        );
+        let mask = Self::mask(width);
+        assert!(
+            taps & !mask == 0,
+            "taps {:#x} do not fit in {} bits",
+            taps,
+            width
+        );
+        assert!(
+            seed & !mask == 0,
+            "seed {:#x} does not fit in {} bits",
+            seed,
+            width
+        );
+        Self {
+            kind,
+            width,
+            taps,
+            state: seed,
+        }
+    }
+
+    /// Creates a register as wide as `seed`, with the given taps, seeded with the bits of `seed`.
+    ///
+    /// Index 0 of `seed` is the most significant bit of the state, as for [`BitArray::get_bits`].
+    /// Panics if `seed` is empty or longer than 64 bits, or if `taps` has bits set beyond its length.
+    #[cfg(feature = "alloc")]
+    pub fn from_seed_bits(kind: LfsrKind, taps: u64, seed: &BitArray) -> Self {
+        assert!(
+            (1..=64).contains(&seed.len()),
+            "width {} is not in 1..=64",
+            seed.len()
+        );
+        Self::new(kind, seed.len() as u32, taps, seed.get_bits(0..seed.len()))
+    }
+
+    /// A mask covering the low `width` bits.
+    fn mask(width: u32) -> u64 {
+        u64::MAX >> (64 - width)
+    }
+
+    /// Getter for the current contents of the register.
+    pub fn state(&self) -> u64 {
+        self.state
+    }
+
+    /// Getter for the number of bits in the register.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Advances the register by one step, returning the bit shifted out.
+    pub fn step(&mut self) -> Bit {
+        let out = self.state & 1;
+        match self.kind {
+            LfsrKind::Fibonacci => {
+                let feedback = u64::from((self.state & self.taps).count_ones() & 1);
+                self.state = (self.state >> 1) | (feedback << (self.width - 1));
+            }
+            LfsrKind::Galois => {
+                self.state >>= 1;
+                if out == 1 {
+                    self.state ^= self.taps;
+                }
+            }
+        }
+        Bit::from(out == 1)
+    }
+
+    /// Advances the register by `n` steps, discarding the output.
+    pub fn step_n(&mut self, n: usize) {
+        for _ in 0..n {
+            self.step();
+        }
+    }
+
+    /// Advances the register by `n` steps, collecting the output in order.
+    #[cfg(feature = "alloc")]
+    pub fn generate(&mut self, n: usize) -> BitArray {
+        self.take(n).collect()
+    }
+}
+
+/// Yields the output of successive steps, forever.
+impl Iterator for Lfsr {
+    type Item = Bit;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.step())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl FusedIterator for Lfsr {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The number of steps before the register returns to its seed.
+    fn period(mut lfsr: Lfsr) -> usize {
+        let seed = lfsr.state();
+        let mut steps = 0;
+        loop {
+            lfsr.step();
+            steps += 1;
+            if lfsr.state() == seed {
+                return steps;
+            }
+        }
+    }
+
+    #[test]
+    fn test_maximal_length() {
+        // x^4 + x^3 + 1
+        assert_eq!(period(Lfsr::new(LfsrKind::Fibonacci, 4, 0b0011, 1)), 15);
+        assert_eq!(period(Lfsr::new(LfsrKind::Galois, 4, 0b1100, 1)), 15);
+        // x^16 + x^14 + x^13 + x^11 + 1
+        assert_eq!(
+            period(Lfsr::new(LfsrKind::Fibonacci, 16, 0x002D, 0xACE1)),
+            65535
+        );
+        assert_eq!(
+            period(Lfsr::new(LfsrKind::Galois, 16, 0xB400, 0xACE1)),
+            65535
+        );
+    }
+
+    #[test]
+    fn test_known_sequence() {
+        let lfsr = Lfsr::new(LfsrKind::Fibonacci, 4, 0b0011, 1);
+        let expected = [1, 0, 0, 0, 1, 0, 0, 1, 1, 0, 1, 0, 1, 1, 1, 1, 0, 0];
+        assert!(lfsr
+            .take(expected.len())
+            .eq(expected.iter().map(|&b| Bit::from(b))));
+    }
+
+    #[test]
+    fn test_step_n() {
+        let mut single = Lfsr::new(LfsrKind::Galois, 16, 0xB400, 0xACE1);
+        let mut bulk = single.clone();
+        for _ in 0..1000 {
+            single.step();
+        }
+        bulk.step_n(1000);
+        assert_eq!(single, bulk);
+
+        let mut zero = Lfsr::new(LfsrKind::Fibonacci, 8, 0b1011_1000, 0);
+        zero.step_n(10);
+        assert_eq!(zero.state(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_generate() {
+        let mut single = Lfsr::new(LfsrKind::Fibonacci, 16, 0x002D, 0xACE1);
+        let mut bulk = single.clone();
+        let bits = bulk.generate(1000);
+        assert_eq!(bits.len(), 1000);
+        assert!(bits.iter().all(|bit| bit == single.step()));
+        assert_eq!(single, bulk);
+        assert!(bulk.generate(0).is_empty());
+        assert_eq!(single, bulk);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_from_seed_bits() {
+        let seed: BitArray = [true, false, true, true].iter().copied().collect();
+        let lfsr = Lfsr::from_seed_bits(LfsrKind::Galois, 0b1100, &seed);
+        assert_eq!(lfsr, Lfsr::new(LfsrKind::Galois, 4, 0b1100, 0b1011));
+        assert_eq!(lfsr.width(), 4);
+        assert_eq!(period(lfsr), 15);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    #[should_panic(expected = "width 65 is not in 1..=64")]
+    fn test_from_seed_bits_too_wide() {
+        Lfsr::from_seed_bits(LfsrKind::Fibonacci, 1, &BitArray::new(65));
+    }
+
+    #[test]
+    #[should_panic(expected = "seed 0x10 does not fit in 4 bits")]
+    fn test_seed_too_wide() {
+        Lfsr::new(LfsrKind::Galois, 4, 0b1100, 0x10);
+    }
+}
@@ -2,48 +2,195 @@
 //! All bit-values may be mapped to a corresponding boolean value, with
 //! "0" == `false`, and "1" == `true`.
 //!
+//! The bits within a byte may be indexed in either of two orders, named by [`BitOrder`]:
+//!
+//! - [`BitOrder::Msb0`]: index 0 is the left-most (most significant) bit.
+//! - [`BitOrder::Lsb0`]: index 0 is the right-most (least significant) bit.
+//!
+//! Each API uses one order throughout:
+//!
+//! | API | Order |
+//! |-----|-------|
+//! | `BitArray`, `BitVec`, `BitSlice` | `Msb0` |
+//! | [`BitSliceExt`], `pack_bits`, [`unpack_bits`], `transpose_records` | `Msb0` |
+//! | `Byte` indexing, `get_bits`, `toggle`, `swap`, `iter_ones`, `first_one` | `Msb0` |
+//! | `Byte` `get_bit_ordered`, `set_bit_ordered` | either, as given |
+//! | `Byte` `get_0` .. `get_7`, `set_N`, `toggle_N`, and iteration | `Lsb0` |
+//! | [`BitAccess`] (`bit_msb0`, `set_bit_msb0`, `toggle_bit_msb0`) | `Msb0` |
+//! | [`BitsExt`] `bits` and `iter_ones` | `Lsb0`: position `i` is `1 << i` |
+//! | [`BitsExt`] `bits_msb` | `Msb0` |
+//!
 //! Therefore, the byte "1100_1101" will be indexed as follows:
 //!
-//! | Index | Value | Boolean |
-//! |-------|-------|---------|
-//! | 0     | 1     | True    |
-//! | 1     | 0     | False   |
-//! | 2     | 1     | True    |
-//! | 3     | 1     | True    |
-//! | 4     | 0     | False   |
-//! | 5     | 0     | False   |
-//! | 6     | 1     | True    |
-//! | 7     | 1     | True    |
-
-use std::iter::{ExactSizeIterator, IntoIterator, Iterator};
-use std::ops::Deref;
+//! | Index | `Msb0` | `Lsb0` |
+//! |-------|--------|--------|
+//! | 0     | 1      | 1      |
+//! | 1     | 1      | 0      |
+//! | 2     | 0      | 1      |
+//! | 3     | 0      | 1      |
+//! | 4     | 1      | 0      |
+//! | 5     | 1      | 0      |
+//! | 6     | 0      | 1      |
+//! | 7     | 1      | 1      |
+//!
+//! Without the default `std` feature the crate is `#![no_std]`; `Bit` and `Byte` need neither
+//! `std` nor an allocator. Heap-backed containers are gated behind the `alloc` feature.
+//! `AtomicBitSet` additionally needs 64-bit atomics, so it is absent on targets without them,
+//! such as the Cortex-M `thumbv6m-none-eabi` and `thumbv7em-none-eabihf` targets.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+use core::convert::TryFrom;
+use core::fmt;
+use core::iter::{DoubleEndedIterator, ExactSizeIterator, FusedIterator, IntoIterator, Iterator};
+use core::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Deref, Index, Not, Range, Shl,
+    ShlAssign, Shr, ShrAssign,
+};
+
+#[cfg(feature = "alloc")]
+mod array;
+#[cfg(all(feature = "alloc", target_has_atomic = "64"))]
+mod atomic;
+#[cfg(feature = "alloc")]
+mod bitmap_index;
+#[cfg(feature = "alloc")]
+mod builder;
+mod byte_iter;
+#[cfg(feature = "std")]
+mod cursor;
+mod error;
+#[cfg(feature = "alloc")]
+mod expr;
+mod fingerprint;
+mod int_ext;
+mod lfsr;
+#[cfg(feature = "alloc")]
+mod matrix;
+mod pack;
+mod prbs;
+#[cfg(feature = "std")]
+mod reader;
+#[cfg(feature = "alloc")]
+mod slice;
+mod slice_ext;
+#[cfg(feature = "alloc")]
+mod vec;
+#[cfg(feature = "alloc")]
+mod writer;
+
+#[cfg(feature = "alloc")]
+pub use array::{
+    ArrayIndices, BitArray, BitArrayView, BitRefMut, BytesAt, Chunks, Matches, ParseHexError, Runs,
+    Split, Windows,
+};
+#[cfg(all(feature = "alloc", target_has_atomic = "64"))]
+pub use atomic::AtomicBitSet;
+#[cfg(feature = "alloc")]
+pub use bitmap_index::BitmapIndex;
+#[cfg(feature = "alloc")]
+pub use builder::BitArrayBuilder;
+pub use byte_iter::{bits_of, ByteIterBits, ByteIterExt};
+#[cfg(feature = "std")]
+pub use cursor::BitArrayCursor;
+pub use error::BitError;
+#[cfg(feature = "alloc")]
+pub use expr::{BitExpr, ExprOnes};
+pub use fingerprint::BitFingerprint;
+pub use int_ext::{reverse_index_bits, BitAccess, BitsExt, IntBits, IntOnes};
+pub use lfsr::{Lfsr, LfsrKind};
+#[cfg(feature = "alloc")]
+pub use matrix::BitMatrix;
+pub use pack::unpack_bits;
+#[cfg(feature = "alloc")]
+pub use pack::{pack_bits, transpose_records, untranspose_records};
+pub use prbs::{Prbs, PrbsChecker, PrbsKind};
+#[cfg(feature = "std")]
+pub use reader::BitReader;
+#[cfg(feature = "alloc")]
+pub use slice::{BitSlice, SliceIter};
+pub use slice_ext::{BitSliceExt, SliceBitIter};
+#[cfg(feature = "alloc")]
+pub use vec::{BitVec, Drain};
+#[cfg(feature = "alloc")]
+pub use writer::BitWriter;
 
 /// Wraps a single bit, whose value is indicated as follows:
 ///
 /// - `0`: *False*
 /// - `1`: *True*
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+///
+/// The default bit is [`Bit::ZERO`], which orders before [`Bit::ONE`].
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
 pub struct Bit(bool);
 
 impl Bit {
-    /// Getter for a bit that has a value of "1".
-    pub fn get_zero_bit() -> Self {
-        Self(false)
+    /// A bit with a value of `0`.
+    pub const ZERO: Bit = Bit(false);
+
+    /// A bit with a value of `1`.
+    pub const ONE: Bit = Bit(true);
+
+    /// Creates a bit from its boolean value; the `const` equivalent of `Bit::from`.
+    pub const fn new(val: bool) -> Self {
+        Self(val)
     }
 
     /// Getter for a bit that has a value of "0".
+    #[deprecated(note = "use `Bit::ZERO` instead")]
+    pub fn get_zero_bit() -> Self {
+        Self::ZERO
+    }
+
+    /// Getter for a bit that has a value of "1".
+    #[deprecated(note = "use `Bit::ONE` instead")]
     pub fn get_one_bit() -> Self {
-        Self(true)
+        Self::ONE
     }
+
     /// True if the bit encapsulated is a `1`.
-    pub fn is_one(&self) -> bool {
-        **self
+    pub const fn is_one(&self) -> bool {
+        self.0
     }
 
     /// True if the bit encapsulated is a `0`.
-    pub fn is_zero(&self) -> bool {
+    pub const fn is_zero(&self) -> bool {
         !self.is_one()
     }
+
+    /// The value of the bit as a boolean.
+    pub const fn as_bool(&self) -> bool {
+        self.0
+    }
+
+    /// A reference to a static bit with the same value, for `Index` impls over packed bits.
+    pub(crate) fn as_static(self) -> &'static Bit {
+        if self.0 {
+            &TRUE
+        } else {
+            &FALSE
+        }
+    }
+
+    /// The value of the bit as an integer, `0` or `1`.
+    pub const fn as_u8(&self) -> u8 {
+        self.0 as u8
+    }
+}
+
+impl PartialEq<bool> for Bit {
+    fn eq(&self, other: &bool) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<Bit> for bool {
+    fn eq(&self, other: &Bit) -> bool {
+        *self == other.0
+    }
 }
 
 impl From<bool> for Bit {
@@ -62,6 +209,25 @@ impl From<u8> for Bit {
     }
 }
 
+/// The value returned by indexing for a `1` bit.
+static TRUE: Bit = Bit(true);
+
+/// The value returned by indexing for a `0` bit.
+static FALSE: Bit = Bit(false);
+
+/// Accepts the characters `'0'` and `'1'`.
+impl TryFrom<char> for Bit {
+    type Error = ParseByteError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '0' => Ok(Bit::ZERO),
+            '1' => Ok(Bit::ONE),
+            _ => Err(ParseByteError::InvalidDigit(c)),
+        }
+    }
+}
+
 impl Deref for Bit {
     type Target = bool;
 
@@ -70,60 +236,157 @@ impl Deref for Bit {
     }
 }
 
-/// A composition of 8-bit values, as a byte.
+macro_rules! impl_bit_op {
+    ($op:ident, $method:ident, $op_assign:ident, $method_assign:ident) => {
+        impl $op for Bit {
+            type Output = Bit;
+
+            fn $method(self, rhs: Bit) -> Self::Output {
+                Bit(self.0.$method(rhs.0))
+            }
+        }
+
+        impl $op<bool> for Bit {
+            type Output = Bit;
+
+            fn $method(self, rhs: bool) -> Self::Output {
+                Bit(self.0.$method(rhs))
+            }
+        }
+
+        impl $op_assign for Bit {
+            fn $method_assign(&mut self, rhs: Bit) {
+                self.0.$method_assign(rhs.0);
+            }
+        }
+
+        impl $op_assign<bool> for Bit {
+            fn $method_assign(&mut self, rhs: bool) {
+                self.0.$method_assign(rhs);
+            }
+        }
+    };
+}
+
+impl_bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign);
+impl_bit_op!(BitOr, bitor, BitOrAssign, bitor_assign);
+impl_bit_op!(BitXor, bitxor, BitXorAssign, bitxor_assign);
+
+impl Not for Bit {
+    type Output = Bit;
+
+    fn not(self) -> Self::Output {
+        Bit(!self.0)
+    }
+}
+
+/// The order in which the bits of a byte are indexed.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
-struct Byte(u8);
+pub enum BitOrder {
+    /// Index 0 is the left-most (most significant) bit.
+    Msb0,
+    /// Index 0 is the right-most (least significant) bit.
+    Lsb0,
+}
 
-impl Default for Byte {
-    fn default() -> Self {
-        Self(Default::default())
+impl BitOrder {
+    /// Converts an index in this order to the equivalent `Lsb0` index.
+    ///
+    /// Panics if `idx` is not less than 8.
+    fn lsb0_index(self, idx: usize) -> usize {
+        assert!(idx < 8, "bit index {} out of range for a byte", idx);
+        match self {
+            BitOrder::Msb0 => 7 - idx,
+            BitOrder::Lsb0 => idx,
+        }
     }
 }
 
+/// A composition of 8-bit values, as a byte.
+///
+/// Bytes order and hash as their `u8` values.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+#[repr(transparent)]
+pub struct Byte(u8);
+
 impl Byte {
+    /// Creates a byte from its value; the `const` equivalent of `Byte::from`.
+    pub const fn new(value: u8) -> Self {
+        Self(value)
+    }
+
+    /// Creates a byte from eight booleans, left-most first; the `const` equivalent of `From<[bool; 8]>`.
+    pub const fn from_bools_const(bits: [bool; 8]) -> Self {
+        let mut value = 0;
+        let mut i = 0;
+        while i < 8 {
+            value = (value << 1) | bits[i] as u8;
+            i += 1;
+        }
+        Self(value)
+    }
+
+    /// Creates a byte whose left-most bit is `f(0)` and right-most bit is `f(7)`, matching
+    /// `From<[bool; 8]>`.
+    ///
+    /// `f` is called exactly once for each index, in ascending order.
+    pub fn from_fn<F: FnMut(usize) -> bool>(f: F) -> Self {
+        Self::from_bools_const(core::array::from_fn(f))
+    }
+
+    /// Creates a byte with only the bit at index `idx` set, counting from index 0 at the left like
+    /// [`Byte::from_bools_const`], so that `Byte::with_bit_set(1)` is `0b0100_0000`.
+    ///
+    /// Panics, or fails to compile in a `const`, if `idx` is not less than 8.
+    pub const fn with_bit_set(idx: usize) -> Self {
+        assert!(idx < 8, "bit index out of range for a byte");
+        Self(0x80 >> idx)
+    }
+
     /// Getter for the right-most bit in the byte. (Eighth left-most bit.)
-    pub fn get_0(&self) -> Bit {
+    pub const fn get_0(&self) -> Bit {
         self.get_bit(0)
     }
 
     /// Getter for the second right-most bit in the byte. (Seventh left-most bit.)
-    pub fn get_1(&self) -> Bit {
+    pub const fn get_1(&self) -> Bit {
         self.get_bit(1)
     }
 
     /// Getter for the third right-most bit in the byte. (Sixth left-most bit.)
-    pub fn get_2(&self) -> Bit {
+    pub const fn get_2(&self) -> Bit {
         self.get_bit(2)
     }
 
     /// Getter for the fourth right-most bit in the byte. (Fifth left-most bit.)
-    pub fn get_3(&self) -> Bit {
+    pub const fn get_3(&self) -> Bit {
         self.get_bit(3)
     }
 
     /// Getter for the fifth right-most bit in the byte. (Fourth left-most bit.)
-    pub fn get_4(&self) -> Bit {
+    pub const fn get_4(&self) -> Bit {
         self.get_bit(4)
     }
 
     /// Getter for the sixth right-most bit in the byte. (Third left-most bit.)
-    pub fn get_5(&self) -> Bit {
+    pub const fn get_5(&self) -> Bit {
         self.get_bit(5)
     }
 
     /// Getter for the seventh right-most bit in the byte. (Second left-most bit.)
-    pub fn get_6(&self) -> Bit {
+    pub const fn get_6(&self) -> Bit {
         self.get_bit(6)
     }
 
     /// Getter for the eighth right-most bit in the byte. (Left-most bit.)
-    pub fn get_7(&self) -> Bit {
+    pub const fn get_7(&self) -> Bit {
         self.get_bit(7)
     }
 
-    /// Arbitrary getter for the bit at index `idx`.
-    fn get_bit(&self, idx: usize) -> Bit {
-        Bit::from((usize::from(self.0) & (1 << idx)) > 0)
+    /// Arbitrary getter for the bit at index `idx`, in [`BitOrder::Lsb0`] order.
+    #[inline]
+    const fn get_bit(&self, idx: usize) -> Bit {
+        Bit((self.0 >> idx) & 1 == 1)
     }
 
     /// Setter for the right-most bit in the byte. (Eighth left-most bit.)
@@ -166,7 +429,90 @@ impl Byte {
         self.set_bit(val, 7);
     }
 
-    /// Arbitrary setter for the bit at index `idx`.
+    /// Flips the right-most bit in the byte, returning its new value. (Eighth left-most bit.)
+    pub fn toggle_0(&mut self) -> Bit {
+        self.toggle_bit(0)
+    }
+
+    /// Flips the second right-most bit in the byte, returning its new value. (Seventh left-most bit.)
+    pub fn toggle_1(&mut self) -> Bit {
+        self.toggle_bit(1)
+    }
+
+    /// Flips the third right-most bit in the byte, returning its new value. (Sixth left-most bit.)
+    pub fn toggle_2(&mut self) -> Bit {
+        self.toggle_bit(2)
+    }
+
+    /// Flips the fourth right-most bit in the byte, returning its new value. (Fifth left-most bit.)
+    pub fn toggle_3(&mut self) -> Bit {
+        self.toggle_bit(3)
+    }
+
+    /// Flips the fifth right-most bit in the byte, returning its new value. (Fourth left-most bit.)
+    pub fn toggle_4(&mut self) -> Bit {
+        self.toggle_bit(4)
+    }
+
+    /// Flips the sixth right-most bit in the byte, returning its new value. (Third left-most bit.)
+    pub fn toggle_5(&mut self) -> Bit {
+        self.toggle_bit(5)
+    }
+
+    /// Flips the seventh right-most bit in the byte, returning its new value. (Second left-most bit.)
+    pub fn toggle_6(&mut self) -> Bit {
+        self.toggle_bit(6)
+    }
+
+    /// Flips the eighth right-most bit in the byte, returning its new value. (Left-most bit.)
+    pub fn toggle_7(&mut self) -> Bit {
+        self.toggle_bit(7)
+    }
+
+    /// Flips the bit at index `idx`, counting from index 0 at the left like [`Byte::iter_ones`] and
+    /// `BitArray::toggle`, returning its new value.
+    ///
+    /// Panics if `idx` is not less than 8.
+    pub fn toggle(&mut self, idx: usize) -> Bit {
+        self.toggle_bit(BitOrder::Msb0.lsb0_index(idx))
+    }
+
+    /// Arbitrary toggle for the bit at index `idx`, in [`BitOrder::Lsb0`] order, returning its new value.
+    #[inline]
+    fn toggle_bit(&mut self, idx: usize) -> Bit {
+        self.0 ^= 1 << idx;
+        self.get_bit(idx)
+    }
+
+    /// Sets every bit of the byte to `1`.
+    pub fn set_all(&mut self) {
+        self.0 = u8::MAX;
+    }
+
+    /// Sets every bit of the byte to `0`.
+    pub fn clear_all(&mut self) {
+        self.0 = 0;
+    }
+
+    /// Flips every bit of the byte.
+    pub fn toggle_all(&mut self) {
+        self.0 = !self.0;
+    }
+
+    /// Exchanges the bits at indices `i` and `j`, counting from index 0 at the left like
+    /// [`Byte::iter_ones`] and `BitArray::swap`.
+    ///
+    /// Panics if either index is not less than 8.
+    pub fn swap(&mut self, i: usize, j: usize) {
+        let (i, j) = (BitOrder::Msb0.lsb0_index(i), BitOrder::Msb0.lsb0_index(j));
+        // Flipping both bits swaps them exactly when they differ.
+        if (self.0 >> i ^ self.0 >> j) & 1 != 0 {
+            self.0 ^= (1 << i) | (1 << j);
+        }
+    }
+
+    /// Arbitrary setter for the bit at index `idx`, in [`BitOrder::Lsb0`] order.
+    #[inline]
     fn set_bit(&mut self, val: bool, idx: usize) {
         if val {
             self.0 |= 1 << idx;
@@ -175,10 +521,298 @@ impl Byte {
         }
     }
 
+    /// The number of bits in the byte with a value of `1`.
+    pub const fn count_ones(&self) -> u32 {
+        self.0.count_ones()
+    }
+
+    /// The number of bits in the byte with a value of `0`.
+    pub const fn count_zeros(&self) -> u32 {
+        self.0.count_zeros()
+    }
+
+    /// True if every bit of the byte is `1`.
+    pub const fn all(&self) -> bool {
+        self.0 == u8::MAX
+    }
+
+    /// True if any bit of the byte is `1`.
+    pub const fn any(&self) -> bool {
+        self.0 != 0
+    }
+
+    /// True if every bit of the byte is `0`.
+    pub const fn none(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// The number of `0` bits before the left-most `1`, or 8 if there is none.
+    ///
+    /// Identical to `u8::leading_zeros`: the crate indexes bits from the left, so this is also
+    /// the index of the first `1`.
+    pub fn leading_zeros(&self) -> u32 {
+        self.0.leading_zeros()
+    }
+
+    /// The number of `0` bits after the right-most `1`, or 8 if there is none.
+    ///
+    /// Identical to `u8::trailing_zeros`; the last `1` is at index `7 - trailing_zeros()`.
+    pub fn trailing_zeros(&self) -> u32 {
+        self.0.trailing_zeros()
+    }
+
+    /// The index of the left-most `1`, counting from index 0 at the left, or `None` if all bits are `0`.
+    pub fn first_one(&self) -> Option<usize> {
+        match self.leading_zeros() {
+            8 => None,
+            n => Some(n as usize),
+        }
+    }
+
+    /// The index of the left-most `0`, counting from index 0 at the left, or `None` if all bits are `1`.
+    pub fn first_zero(&self) -> Option<usize> {
+        (!self).first_one()
+    }
+
+    /// The index of the right-most `1`, counting from index 0 at the left, or `None` if all bits are `0`.
+    pub fn last_one(&self) -> Option<usize> {
+        match self.trailing_zeros() {
+            8 => None,
+            n => Some(7 - n as usize),
+        }
+    }
+
+    /// Iterates over the indices of the `1` bits, counting from index 0 at the left, in ascending order.
+    pub fn iter_ones(&self) -> ByteIndices {
+        ByteIndices { bits: self.0 }
+    }
+
+    /// Iterates over the indices of the `0` bits, counting from index 0 at the left, in ascending order.
+    pub fn iter_zeros(&self) -> ByteIndices {
+        ByteIndices { bits: !self.0 }
+    }
+
+    /// Iterates over the maximal runs of equal bits, from left to right, as each run's value and length.
+    pub fn runs(&self) -> ByteRuns {
+        ByteRuns {
+            byte: self.0,
+            idx: 0,
+        }
+    }
+
+    /// Iterates over the bits of the byte, from the right-most bit (`get_0`) to the left-most (`get_7`).
+    ///
+    /// This is the [`BitOrder::Lsb0`] order.
+    pub fn bits(&self) -> BitIter {
+        BitIter::from(*self)
+    }
+
+    /// Iterates over the bits of the byte, from index 0 to index 7 in the given order.
+    pub fn bits_ordered(&self, order: BitOrder) -> BitIter {
+        BitIter {
+            order,
+            ..BitIter::from(*self)
+        }
+    }
+
+    /// Getter for the bit at index `idx`, counted in the given order.
+    ///
+    /// Panics if `idx` is not less than 8.
+    pub fn get_bit_ordered(&self, idx: usize, order: BitOrder) -> Bit {
+        self.get_bit(order.lsb0_index(idx))
+    }
+
+    /// Setter for the bit at index `idx`, counted in the given order.
+    ///
+    /// Panics if `idx` is not less than 8.
+    pub fn set_bit_ordered(&mut self, idx: usize, val: bool, order: BitOrder) {
+        self.set_bit(val, order.lsb0_index(idx));
+    }
+
+    /// The mask selecting the bits of `range`, counting from index 0 at the left.
+    ///
+    /// Panics if the range is decreasing or extends past index 7.
+    fn range_mask(range: &Range<usize>) -> u8 {
+        assert!(
+            range.start <= range.end && range.end <= 8,
+            "range {}..{} out of range for a byte",
+            range.start,
+            range.end
+        );
+        ((0xFF_u16 >> range.start) ^ (0xFF_u16 >> range.end)) as u8
+    }
+
+    /// The bits in `range`, counting from index 0 at the left, as an integer whose most
+    /// significant bit is the first bit of the range.
+    ///
+    /// Panics if the range is decreasing or extends past index 7.
+    pub fn get_bits(&self, range: Range<usize>) -> u8 {
+        let mask = Self::range_mask(&range);
+        ((u16::from(self.0 & mask)) >> (8 - range.end)) as u8
+    }
+
+    /// Overwrites the bits in `range`, counting from index 0 at the left, with the bits of `value`,
+    /// the most significant bit of `value` going to the first bit of the range.
+    ///
+    /// Panics if the range is decreasing or extends past index 7, or if `value` does not fit in
+    /// the width of the range.
+    pub fn set_bits(&mut self, range: Range<usize>, value: u8) {
+        let mask = Self::range_mask(&range);
+        let width = range.end - range.start;
+        assert!(
+            u16::from(value) >> width == 0,
+            "value {:#x} does not fit in {} bits",
+            value,
+            width
+        );
+        let shifted = (u16::from(value) << (8 - range.end)) as u8;
+        self.0 = (self.0 & !mask) | shifted;
+    }
+
+    /// Getter for the bit at index `idx`, counting from index 0 at the left like `BitArray::try_get`,
+    /// or an error if `idx` is not less than 8.
+    pub fn try_get(&self, idx: usize) -> Result<Bit, BitError> {
+        if idx < 8 {
+            Ok(self.get_bit_ordered(idx, BitOrder::Msb0))
+        } else {
+            Err(BitError::OutOfBounds { index: idx, len: 8 })
+        }
+    }
+
+    /// Setter for the bit at index `idx`, counting from index 0 at the left like `BitArray::try_set`,
+    /// or an error if `idx` is not less than 8.
+    pub fn try_set(&mut self, idx: usize, val: bool) -> Result<(), BitError> {
+        if idx < 8 {
+            self.set_bit_ordered(idx, val, BitOrder::Msb0);
+            Ok(())
+        } else {
+            Err(BitError::OutOfBounds { index: idx, len: 8 })
+        }
+    }
+
     /// Getter for the byte-representation of the internal bits being managed.
-    pub fn as_byte(&self) -> u8 {
+    pub const fn as_byte(&self) -> u8 {
         self.0
     }
+
+    /// Carry-less multiplication, treating both bytes as polynomials over GF(2).
+    ///
+    /// The bit with value `1 << k` is the coefficient of `x^k`, so the result's degree is at most 14.
+    /// `BitArray::clmul` gives the same product for arrays holding the coefficient of `x^k` at index `k`.
+    pub fn clmul(&self, other: Byte) -> u16 {
+        let rhs = u16::from(other.0);
+        (0..8)
+            .filter(|&k| self.get_bit(k).is_one())
+            .fold(0, |acc, k| acc ^ (rhs << k))
+    }
+
+    /// The number of positions at which the bits of the two bytes differ.
+    pub fn hamming_distance(&self, other: Byte) -> u32 {
+        (self.0 ^ other.0).count_ones()
+    }
+
+    /// Creates a byte from up to 8 bits, left-most first, padding any missing bits on the right with `0`.
+    ///
+    /// Returns an error if more than 8 bits are given.
+    pub fn from_bits_padded(bits: &[bool]) -> Result<Byte, BitError> {
+        if bits.len() > 8 {
+            return Err(BitError::InvalidLength {
+                len: bits.len(),
+                max: 8,
+            });
+        }
+        let mut padded = [false; 8];
+        padded[..bits.len()].copy_from_slice(bits);
+        Ok(Byte::from(padded))
+    }
+
+    /// The bits of the byte as booleans, left-most first, matching `From<[bool; 8]>`.
+    pub fn to_bools(&self) -> [bool; 8] {
+        core::array::from_fn(|i| self.0 & (0x80 >> i) != 0)
+    }
+
+    /// The bits of the byte, left-most first, matching `From<[Bit; 8]>`.
+    pub fn to_bits(&self) -> [Bit; 8] {
+        self.to_bools().map(Bit::from)
+    }
+
+    /// The bits of the byte as a vector of booleans, left-most first.
+    #[cfg(feature = "alloc")]
+    pub fn to_vec_bool(&self) -> alloc::vec::Vec<bool> {
+        self.to_bools().to_vec()
+    }
+
+    /// The reflected binary Gray code of the byte's value, `n ^ (n >> 1)`.
+    pub fn to_gray(&self) -> Byte {
+        Byte(self.0 ^ (self.0 >> 1))
+    }
+
+    /// Decodes a Gray code back to the value it encodes, the inverse of [`Byte::to_gray`].
+    pub fn from_gray(gray: Byte) -> Byte {
+        // Each bit of the value is the XOR of the bits of the code to its left.
+        let mut n = gray.0;
+        n ^= n >> 1;
+        n ^= n >> 2;
+        n ^= n >> 4;
+        Byte(n)
+    }
+
+    /// The XOR of every bit of the byte: `1` if an odd number of bits are set.
+    pub fn parity(&self) -> Bit {
+        Bit(self.0.count_ones() % 2 == 1)
+    }
+
+    /// The seven right-most bits (indices 1 to 7, counting from the left) as data, with the left-most
+    /// bit (index 0, value `0x80`) replaced by a parity bit that makes the number of `1` bits even.
+    pub fn with_even_parity_bit(&self) -> Byte {
+        let data = Byte(self.0 & 0x7F);
+        Byte(data.0 | (data.parity().as_u8() << 7))
+    }
+
+    /// As [`Byte::with_even_parity_bit`], but the parity bit makes the number of `1` bits odd.
+    pub fn with_odd_parity_bit(&self) -> Byte {
+        Byte(self.with_even_parity_bit().0 ^ 0x80)
+    }
+
+    /// True if the number of `1` bits in the byte, including any parity bit, is even.
+    pub fn check_even_parity(&self) -> bool {
+        self.parity().is_zero()
+    }
+
+    /// Interleaves the bits of two bytes, as for a Morton (z-order) index.
+    ///
+    /// Counting from the left, as in [`BitOrder::Msb0`], bit `i` of `self` becomes bit `2 * i` of the
+    /// result and bit `i` of `other` becomes bit `2 * i + 1`. So `self` fills the even positions,
+    /// starting with the most significant bit, and `other` the odd ones.
+    pub fn interleave(&self, other: Byte) -> u16 {
+        (spread_bits(self.0) << 1) | spread_bits(other.0)
+    }
+
+    /// Splits a word back into the two bytes that [`Byte::interleave`] combined, in the same order.
+    pub fn deinterleave(word: u16) -> (Byte, Byte) {
+        (Byte(compact_bits(word >> 1)), Byte(compact_bits(word)))
+    }
+
+    /// The left (high) and right (low) four bits of the byte, in that order.
+    pub fn nibbles(&self) -> (u8, u8) {
+        (self.0 >> 4, self.0 & 0x0F)
+    }
+}
+
+/// Moves bit `k` of `x` to bit `2 * k`, leaving the odd bits clear.
+fn spread_bits(x: u8) -> u16 {
+    let mut x = u16::from(x);
+    x = (x | (x << 4)) & 0x0F0F;
+    x = (x | (x << 2)) & 0x3333;
+    (x | (x << 1)) & 0x5555
+}
+
+/// Gathers the even bits of `x` into a byte; the inverse of [`spread_bits`].
+fn compact_bits(x: u16) -> u8 {
+    let mut x = x & 0x5555;
+    x = (x | (x >> 1)) & 0x3333;
+    x = (x | (x >> 2)) & 0x0F0F;
+    ((x | (x >> 4)) & 0x00FF) as u8
 }
 
 impl From<u8> for Byte {
@@ -187,6 +821,18 @@ impl From<u8> for Byte {
     }
 }
 
+impl PartialEq<u8> for Byte {
+    fn eq(&self, other: &u8) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<Byte> for u8 {
+    fn eq(&self, other: &Byte) -> bool {
+        *self == other.0
+    }
+}
+
 impl From<[bool; 8]> for Byte {
     fn from(bits: [bool; 8]) -> Self {
         let mut byte = Byte::default();
@@ -224,16 +870,76 @@ impl From<[Bit; 8]> for Byte {
     }
 }
 
+/// Succeeds only for slices of exactly 8 bits, read in the same order as `From<[bool; 8]>`.
+impl TryFrom<&[bool]> for Byte {
+    type Error = BitError;
+
+    fn try_from(bits: &[bool]) -> Result<Self, Self::Error> {
+        <[bool; 8]>::try_from(bits)
+            .map(Byte::from)
+            .map_err(|_| BitError::LengthMismatch {
+                left: bits.len(),
+                right: 8,
+            })
+    }
+}
+
+/// Succeeds only for slices of exactly 8 bits, read in the same order as `From<[Bit; 8]>`.
+impl TryFrom<&[Bit]> for Byte {
+    type Error = BitError;
+
+    fn try_from(bits: &[Bit]) -> Result<Self, Self::Error> {
+        <[Bit; 8]>::try_from(bits)
+            .map(Byte::from)
+            .map_err(|_| BitError::LengthMismatch {
+                left: bits.len(),
+                right: 8,
+            })
+    }
+}
+
+impl From<Byte> for [bool; 8] {
+    fn from(byte: Byte) -> Self {
+        byte.to_bools()
+    }
+}
+
+impl From<Byte> for [Bit; 8] {
+    fn from(byte: Byte) -> Self {
+        byte.to_bits()
+    }
+}
+
+/// Indexes in [`BitOrder::Msb0`] order, counting from index 0 at the left like [`Byte::iter_ones`]
+/// and the `Index` of `BitArray`; panics if `idx` is not less than 8.
+///
+/// `IndexMut` is not provided, since the bits are packed; use [`Byte::try_set`] or the positional
+/// setters to change a bit.
+impl Index<usize> for Byte {
+    type Output = Bit;
+
+    fn index(&self, idx: usize) -> &Self::Output {
+        self.get_bit(BitOrder::Msb0.lsb0_index(idx)).as_static()
+    }
+}
+
+impl IntoIterator for &Byte {
+    type Item = Bit;
+
+    type IntoIter = BitIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.bits()
+    }
+}
+
 impl IntoIterator for Byte {
     type Item = Bit;
 
     type IntoIter = BitIter;
 
     fn into_iter(self) -> Self::IntoIter {
-        Self::IntoIter {
-            byte: self.clone(),
-            idx: 0,
-        }
+        Self::IntoIter::from(self)
     }
 }
 
@@ -245,43 +951,463 @@ impl Deref for Byte {
     }
 }
 
-/// An iterator structure, wrapping a byte object and allowing for bit-level iteration.
-struct BitIter {
-    /// The byte-object being wrapped by this iterator.
-    byte: Byte,
-    /// The index of the next bit that will be dispatched by this iterator.
-    idx: usize,
-}
+/// Implements a binary bitwise operator for `Byte`, by value and by reference, plus its assigning form.
+macro_rules! impl_byte_op {
+    ($op:ident, $method:ident, $op_assign:ident, $method_assign:ident) => {
+        impl $op for Byte {
+            type Output = Byte;
 
-impl From<Byte> for BitIter {
-    fn from(byte: Byte) -> Self {
-        Self { byte, idx: 0 }
-    }
-}
+            fn $method(self, rhs: Byte) -> Self::Output {
+                Byte(self.0.$method(rhs.0))
+            }
+        }
 
-impl Iterator for BitIter {
-    type Item = Bit;
+        impl $op<&Byte> for &Byte {
+            type Output = Byte;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.idx < 8 {
-            let res = Some(self.byte.get_bit(self.idx));
-            self.idx += 1;
-            res
-        } else {
-            None
+            fn $method(self, rhs: &Byte) -> Self::Output {
+                Byte(self.0.$method(rhs.0))
+            }
         }
-    }
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (0, Some(8))
-    }
+        impl $op_assign for Byte {
+            fn $method_assign(&mut self, rhs: Byte) {
+                self.0.$method_assign(rhs.0);
+            }
+        }
+
+        impl $op_assign<&Byte> for Byte {
+            fn $method_assign(&mut self, rhs: &Byte) {
+                self.0.$method_assign(rhs.0);
+            }
+        }
+    };
 }
 
-impl ExactSizeIterator for BitIter {}
+impl_byte_op!(BitAnd, bitand, BitAndAssign, bitand_assign);
+impl_byte_op!(BitOr, bitor, BitOrAssign, bitor_assign);
+impl_byte_op!(BitXor, bitxor, BitXorAssign, bitxor_assign);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl Not for Byte {
+    type Output = Byte;
+
+    fn not(self) -> Self::Output {
+        Byte(!self.0)
+    }
+}
+
+impl Not for &Byte {
+    type Output = Byte;
+
+    fn not(self) -> Self::Output {
+        Byte(!self.0)
+    }
+}
+
+/// Shifts the bits towards the left-most bit, filling with zeros.
+///
+/// Unlike `u8`, shifting by 8 or more does not panic: every bit is shifted out, leaving zero.
+impl Shl<u32> for Byte {
+    type Output = Byte;
+
+    fn shl(self, rhs: u32) -> Self::Output {
+        self.checked_shl(rhs).unwrap_or_default()
+    }
+}
+
+/// Shifts the bits towards the right-most bit, filling with zeros.
+///
+/// Unlike `u8`, shifting by 8 or more does not panic: every bit is shifted out, leaving zero.
+impl Shr<u32> for Byte {
+    type Output = Byte;
+
+    fn shr(self, rhs: u32) -> Self::Output {
+        self.checked_shr(rhs).unwrap_or_default()
+    }
+}
+
+impl ShlAssign<u32> for Byte {
+    fn shl_assign(&mut self, rhs: u32) {
+        *self = *self << rhs;
+    }
+}
+
+impl ShrAssign<u32> for Byte {
+    fn shr_assign(&mut self, rhs: u32) {
+        *self = *self >> rhs;
+    }
+}
+
+impl Byte {
+    /// Shifts the bits towards the left-most bit, or `None` if `n` is 8 or more.
+    pub fn checked_shl(&self, n: u32) -> Option<Byte> {
+        self.0.checked_shl(n).map(Byte)
+    }
+
+    /// Shifts the bits towards the right-most bit, or `None` if `n` is 8 or more.
+    pub fn checked_shr(&self, n: u32) -> Option<Byte> {
+        self.0.checked_shr(n).map(Byte)
+    }
+
+    /// Rotates the bits towards the left-most bit by `n` places, modulo 8.
+    pub fn rotate_left(&self, n: u32) -> Byte {
+        Byte(self.0.rotate_left(n))
+    }
+
+    /// Rotates the bits towards the right-most bit by `n` places, modulo 8.
+    pub fn rotate_right(&self, n: u32) -> Byte {
+        Byte(self.0.rotate_right(n))
+    }
+
+    /// The byte with the order of its bits reversed, so the left-most bit becomes the right-most.
+    pub fn reverse_bits(&self) -> Byte {
+        Byte(self.0.reverse_bits())
+    }
+
+    /// Writes the bits of the byte as binary digits, left-most first, into `buf`.
+    fn write_digits(&self, buf: &mut [u8; 8]) {
+        for (idx, digit) in buf.iter_mut().enumerate() {
+            *digit = if self.get_bit(7 - idx).is_one() {
+                b'1'
+            } else {
+                b'0'
+            };
+        }
+    }
+}
+
+/// Formats the byte as a binary literal, `Byte(0b1100_1101)`, rather than as a decimal value.
+impl fmt::Debug for Byte {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Byte(0b{})", self)
+    }
+}
+
+/// Formats the byte as its eight binary digits, with an underscore between the nibbles (`1100_1101`).
+impl fmt::Display for Byte {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut digits = [0; 8];
+        self.write_digits(&mut digits);
+        let mut buf = [b'_'; 9];
+        buf[..4].copy_from_slice(&digits[..4]);
+        buf[5..].copy_from_slice(&digits[4..]);
+        f.pad(core::str::from_utf8(&buf).unwrap())
+    }
+}
+
+/// Formats the byte as its eight binary digits (`11001101`), honoring the width, fill and `#` flags.
+impl fmt::Binary for Byte {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut digits = [0; 8];
+        self.write_digits(&mut digits);
+        f.pad_integral(true, "0b", core::str::from_utf8(&digits).unwrap())
+    }
+}
+
+/// Formats the byte as an integer in the given radix, like the wrapped `u8`.
+macro_rules! impl_byte_radix_fmt {
+    ($($trait:ident),*) => {
+        $(
+            /// Delegates to the wrapped `u8`, honoring the width, fill and `#` flags.
+            impl fmt::$trait for Byte {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt::$trait::fmt(&self.0, f)
+                }
+            }
+        )*
+    };
+}
+
+impl_byte_radix_fmt!(LowerHex, UpperHex, Octal);
+
+/// The error returned when parsing a `Byte`, or converting a character to a `Bit`, fails.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ParseByteError {
+    /// The input contained a character that is not a digit of the radix being parsed or an underscore.
+    InvalidDigit(char),
+    /// The input did not contain exactly eight binary digits; holds the number found.
+    WrongLength(usize),
+    /// The input contained no digits.
+    Empty,
+    /// The value was greater than 255.
+    Overflow,
+}
+
+impl fmt::Display for ParseByteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseByteError::InvalidDigit(c) => write!(f, "invalid digit {:?}", c),
+            ParseByteError::WrongLength(len) => {
+                write!(f, "expected 8 binary digits, found {}", len)
+            }
+            ParseByteError::Empty => f.write_str("no digits to parse"),
+            ParseByteError::Overflow => f.write_str("value does not fit in a byte"),
+        }
+    }
+}
+
+impl core::error::Error for ParseByteError {}
+
+impl Byte {
+    /// Parses a byte from digits in the given radix, ignoring any underscores between them.
+    ///
+    /// Unlike `u8::from_str_radix`, no sign is accepted.
+    ///
+    /// Panics if `radix` is not between 2 and 36.
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<Byte, ParseByteError> {
+        assert!(
+            (2..=36).contains(&radix),
+            "radix {} is not between 2 and 36",
+            radix
+        );
+        let mut value = 0u32;
+        let mut digits = 0;
+        for c in s.chars().filter(|&c| c != '_') {
+            let digit = c.to_digit(radix).ok_or(ParseByteError::InvalidDigit(c))?;
+            value = value * radix + digit;
+            if value > u32::from(u8::MAX) {
+                return Err(ParseByteError::Overflow);
+            }
+            digits += 1;
+        }
+        if digits == 0 {
+            return Err(ParseByteError::Empty);
+        }
+        Ok(Byte(value as u8))
+    }
+
+    /// Parses a byte, choosing the radix from a `0x`, `0o` or `0b` prefix, or decimal without one.
+    ///
+    /// Prefixes may be either case, and underscores between digits are ignored, as in `"0b1010_0001"`.
+    pub fn parse(s: &str) -> Result<Byte, ParseByteError> {
+        let (digits, radix) = match s.get(..2) {
+            Some("0x") | Some("0X") => (&s[2..], 16),
+            Some("0o") | Some("0O") => (&s[2..], 8),
+            Some("0b") | Some("0B") => (&s[2..], 2),
+            _ => (s, 10),
+        };
+        Byte::from_str_radix(digits, radix)
+    }
+}
+
+/// Parses eight binary digits, left-most first, such as `"1100_1101"` or `"11001101"`.
+///
+/// Input with a `0x`, `0o` or `0b` prefix, such as `"0xCD"`, is instead parsed by [`Byte::parse`].
+/// Underscores between digits and surrounding whitespace are ignored.
+impl core::str::FromStr for Byte {
+    type Err = ParseByteError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some("0x" | "0X" | "0o" | "0O" | "0b" | "0B") = s.get(..2) {
+            return Byte::parse(s);
+        }
+        let mut byte = 0u8;
+        let mut len = 0;
+        for c in s.chars().filter(|&c| c != '_') {
+            let bit = match c {
+                '0' => 0,
+                '1' => 1,
+                _ => return Err(ParseByteError::InvalidDigit(c)),
+            };
+            byte = (byte << 1) | bit;
+            len += 1;
+        }
+        if len == 8 {
+            Ok(Byte(byte))
+        } else {
+            Err(ParseByteError::WrongLength(len))
+        }
+    }
+}
+
+/// An iterator structure, wrapping a byte object and allowing for bit-level iteration.
+#[derive(Debug, Clone)]
+pub struct BitIter {
+    /// The byte-object being wrapped by this iterator.
+    byte: Byte,
+    /// The index of the next bit that will be dispatched from the front of this iterator.
+    idx: usize,
+    /// One past the index of the next bit that will be dispatched from the back of this iterator.
+    end: usize,
+    /// The order in which the indices are counted.
+    order: BitOrder,
+}
+
+/// Iterates in [`BitOrder::Lsb0`] order.
+impl From<Byte> for BitIter {
+    fn from(byte: Byte) -> Self {
+        Self {
+            byte,
+            idx: 0,
+            end: 8,
+            order: BitOrder::Lsb0,
+        }
+    }
+}
+
+impl Iterator for BitIter {
+    type Item = Bit;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx < self.end {
+            let res = Some(self.byte.get_bit_ordered(self.idx, self.order));
+            self.idx += 1;
+            res
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.idx;
+        (remaining, Some(remaining))
+    }
+}
+
+impl DoubleEndedIterator for BitIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.idx < self.end {
+            self.end -= 1;
+            Some(self.byte.get_bit_ordered(self.end, self.order))
+        } else {
+            None
+        }
+    }
+}
+
+impl ExactSizeIterator for BitIter {}
+
+impl FusedIterator for BitIter {}
+
+/// An iterator over the indices of the `1` or `0` bits of a byte, created by [`Byte::iter_ones`]
+/// and [`Byte::iter_zeros`].
+#[derive(Debug, Clone)]
+pub struct ByteIndices {
+    /// The bits whose indices have not been dispatched yet.
+    bits: u8,
+}
+
+impl Iterator for ByteIndices {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bits == 0 {
+            return None;
+        }
+        let idx = self.bits.leading_zeros();
+        self.bits &= !(0x80 >> idx);
+        Some(idx as usize)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.bits.count_ones() as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl DoubleEndedIterator for ByteIndices {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.bits == 0 {
+            return None;
+        }
+        let shift = self.bits.trailing_zeros();
+        self.bits &= !(1 << shift);
+        Some(7 - shift as usize)
+    }
+}
+
+impl ExactSizeIterator for ByteIndices {}
+
+impl FusedIterator for ByteIndices {}
+
+/// An iterator over the runs of equal bits in a byte, created by [`Byte::runs`].
+#[derive(Debug, Clone)]
+pub struct ByteRuns {
+    /// The byte being iterated over.
+    byte: u8,
+    /// The index, counting from the left, at which the next run starts.
+    idx: u32,
+}
+
+impl Iterator for ByteRuns {
+    type Item = (Bit, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx == 8 {
+            return None;
+        }
+        let rest = self.byte << self.idx;
+        let one = rest & 0x80 != 0;
+        let run = if one {
+            rest.leading_ones()
+        } else {
+            rest.leading_zeros()
+        };
+        let run = run.min(8 - self.idx);
+        self.idx += run;
+        Some((Bit::from(one), run as usize))
+    }
+}
+
+impl FusedIterator for ByteRuns {}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::format;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const SYN_FLAG: Byte = Byte::with_bit_set(6);
+    const MASK: Byte =
+        Byte::from_bools_const([true, false, true, false, false, false, false, true]);
+
+    const _: () = assert!(SYN_FLAG.as_byte() == 0b0000_0010);
+    const _: () = assert!(MASK.as_byte() == Byte::new(0b1010_0001).as_byte());
+    const _: () =
+        assert!(MASK.get_7().is_one() && MASK.get_6().is_zero() && MASK.get_0().as_bool());
+    const _: () = assert!(MASK.count_ones() == 3 && MASK.count_zeros() == 5);
+    const _: () = assert!(Byte::new(0xFF).all() && MASK.any() && Byte::new(0).none());
+    const _: () = assert!(Bit::new(true).as_u8() == 1 && Bit::ONE.is_one());
+
+    #[test]
+    fn test_const_constructors() {
+        assert_eq!(SYN_FLAG, Byte::from(0x02));
+        assert_eq!(
+            MASK,
+            Byte::from([true, false, true, false, false, false, false, true])
+        );
+        for idx in 0..8 {
+            let byte = Byte::with_bit_set(idx);
+            assert_eq!(byte, Byte::from(0x80 >> idx));
+            assert_eq!(byte, Byte::from_fn(|i| i == idx));
+            assert_eq!(byte.first_one(), Some(idx));
+            assert!(byte[idx].is_one());
+        }
+        assert_eq!(Bit::new(false), Bit::ZERO);
+    }
+
+    #[test]
+    #[should_panic(expected = "bit index out of range for a byte")]
+    fn test_with_bit_set_out_of_range() {
+        let _ = Byte::with_bit_set(8);
+    }
+
+    #[test]
+    fn test_from_fn() {
+        let mut calls = Vec::new();
+        let byte = Byte::from_fn(|i| {
+            calls.push(i);
+            i % 3 == 0
+        });
+        assert_eq!(calls, [0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(byte, Byte::from(0b1001_0010));
+        assert_eq!(Byte::from_fn(|_| true), Byte::from(u8::MAX));
+    }
 
     #[test]
     fn test_getters() {
@@ -382,19 +1508,80 @@ mod tests {
         assert_ne!(zero, one);
     }
 
+    #[test]
+    #[allow(deprecated)]
+    fn test_bit_constants() {
+        assert!(Bit::ZERO.is_zero());
+        assert!(Bit::ONE.is_one());
+        assert_eq!(Bit::default(), Bit::ZERO);
+        assert_eq!(Bit::get_zero_bit(), Bit::ZERO);
+        assert_eq!(Bit::get_one_bit(), Bit::ONE);
+    }
+
+    #[test]
+    fn test_bit_from_char() {
+        assert_eq!(Bit::try_from('0'), Ok(Bit::ZERO));
+        assert_eq!(Bit::try_from('1'), Ok(Bit::ONE));
+        for c in ['2', 'a', ' ', '_'] {
+            assert_eq!(Bit::try_from(c), Err(ParseByteError::InvalidDigit(c)));
+        }
+    }
+
+    #[test]
+    fn test_ordering() {
+        assert!(Bit::ZERO < Bit::ONE);
+        let mut bytes: Vec<Byte> = [0x80u8, 0x01, 0xFF, 0x00]
+            .iter()
+            .map(|&b| Byte::from(b))
+            .collect();
+        bytes.sort();
+        assert_eq!(bytes, [0x00, 0x01, 0x80, 0xFF]);
+        assert_eq!(Byte::from(0x7F).max(Byte::from(0x80)), 0x80);
+    }
+
+    #[test]
+    fn test_bit_ops() {
+        for a in [false, true] {
+            let bit = Bit::from(a);
+            assert_eq!(!bit, !a);
+            assert_eq!(!!bit, bit);
+            for b in [false, true] {
+                let other = Bit::from(b);
+                assert_eq!(bit & other, a & b);
+                assert_eq!(bit | other, a | b);
+                assert_eq!(bit ^ other, a ^ b);
+                assert_eq!(bit & b, a & b);
+                assert_eq!(bit | b, a | b);
+                assert_eq!(bit ^ b, a ^ b);
+
+                let mut acc = bit;
+                acc ^= other;
+                assert_eq!(acc, a ^ b);
+                acc |= b;
+                assert_eq!(acc, (a ^ b) | b);
+                acc &= other;
+                assert_eq!(acc, ((a ^ b) | b) & b);
+            }
+        }
+
+        let byte = Byte::from(0b0110_1011);
+        let parity = byte.bits().fold(Bit::ZERO, |parity, bit| parity ^ bit);
+        assert_eq!(parity, Bit::ONE);
+    }
+
     #[test]
     fn test_new_byte() {
         let num = 0b1010_0001u8;
         let bools = [true, false, true, false, false, false, false, true];
         let bits = [
-            Bit::get_one_bit(),
-            Bit::get_zero_bit(),
-            Bit::get_one_bit(),
-            Bit::get_zero_bit(),
-            Bit::get_zero_bit(),
-            Bit::get_zero_bit(),
-            Bit::get_zero_bit(),
-            Bit::get_one_bit(),
+            Bit::ONE,
+            Bit::ZERO,
+            Bit::ONE,
+            Bit::ZERO,
+            Bit::ZERO,
+            Bit::ZERO,
+            Bit::ZERO,
+            Bit::ONE,
         ];
 
         assert_eq!(161, Byte::from(num).as_byte());
@@ -402,6 +1589,636 @@ mod tests {
         assert_eq!(161, Byte::from(bits).as_byte());
     }
 
+    #[test]
+    fn test_try_from_slice() {
+        let bools = [true, false, true, false, false, false, false, true, true];
+        assert_eq!(Byte::try_from(&bools[..8]), Ok(Byte::from(0b1010_0001)));
+        let bits: Vec<Bit> = bools.iter().map(|&b| Bit::from(b)).collect();
+        assert_eq!(Byte::try_from(&bits[..8]), Ok(Byte::from(0b1010_0001)));
+        for len in [0, 7, 9] {
+            let err = BitError::LengthMismatch {
+                left: len,
+                right: 8,
+            };
+            assert_eq!(Byte::try_from(&bools[..len]), Err(err.clone()));
+            assert_eq!(Byte::try_from(&bits[..len]), Err(err));
+        }
+    }
+
+    #[test]
+    fn test_from_bits_padded() {
+        let bools = [true, true, false, true, false, false, true, true, true];
+        assert_eq!(Byte::from_bits_padded(&bools[..0]), Ok(Byte::from(0)));
+        assert_eq!(
+            Byte::from_bits_padded(&bools[..3]),
+            Ok(Byte::from(0b1100_0000))
+        );
+        assert_eq!(
+            Byte::from_bits_padded(&bools[..7]),
+            Ok(Byte::from(0b1101_0010))
+        );
+        assert_eq!(
+            Byte::from_bits_padded(&bools[..8]),
+            Ok(Byte::from(0b1101_0011))
+        );
+        assert_eq!(
+            Byte::from_bits_padded(&bools),
+            Err(BitError::InvalidLength { len: 9, max: 8 })
+        );
+    }
+
+    #[test]
+    fn test_to_bool_array() {
+        let byte = Byte::from(0b1010_0001);
+        assert_eq!(
+            <[bool; 8]>::from(byte),
+            [true, false, true, false, false, false, false, true]
+        );
+        for value in 0..=u8::MAX {
+            let byte = Byte::from(value);
+            assert_eq!(Byte::from(<[bool; 8]>::from(byte)), byte);
+            assert_eq!(Byte::from(<[Bit; 8]>::from(byte)), byte);
+            assert!(byte
+                .to_bits()
+                .iter()
+                .copied()
+                .eq(byte.bits_ordered(BitOrder::Msb0)));
+            #[cfg(feature = "alloc")]
+            assert_eq!(byte.to_vec_bool(), byte.to_bools());
+        }
+    }
+
+    #[test]
+    fn test_bitwise_ops() {
+        let values = [0u8, 0b1010_1010, 0b1100_1101, 0b0011_0110, u8::MAX];
+        for &a in &values {
+            for &b in &values {
+                let (x, y) = (Byte::from(a), Byte::from(b));
+                let and = x & y;
+                let or = BitOr::bitor(&x, &y);
+                let xor = x ^ y;
+                let not = !x;
+                assert_eq!(and.as_byte(), a & b);
+                assert_eq!(or.as_byte(), a | b);
+                assert_eq!(xor.as_byte(), a ^ b);
+                assert_eq!((!&x).as_byte(), !a);
+                for idx in 0..8 {
+                    let (p, q) = (*x.get_bit(idx), *y.get_bit(idx));
+                    assert_eq!(*and.get_bit(idx), p && q);
+                    assert_eq!(*or.get_bit(idx), p || q);
+                    assert_eq!(*xor.get_bit(idx), p != q);
+                    assert_eq!(*not.get_bit(idx), !p);
+                }
+
+                let mut z = x;
+                z &= y;
+                assert_eq!(z, and);
+                let mut z = x;
+                z |= &y;
+                assert_eq!(z, or);
+                let mut z = x;
+                z ^= y;
+                assert_eq!(z, xor);
+            }
+        }
+    }
+
+    #[test]
+    fn test_try_get_set() {
+        let mut byte = Byte::from(0b0000_0100);
+        assert_eq!(byte.try_get(5), Ok(Bit::ONE));
+        assert_eq!(byte.try_get(0), Ok(Bit::ZERO));
+        assert_eq!(byte.try_set(0, true), Ok(()));
+        assert_eq!(byte.as_byte(), 0b1000_0100);
+        assert!(byte
+            .iter_ones()
+            .all(|idx| byte.try_get(idx) == Ok(Bit::ONE)));
+        #[cfg(feature = "alloc")]
+        {
+            let arr = BitArray::from_bytes(&[byte.as_byte()]);
+            assert!((0..8).all(|idx| byte.try_get(idx) == arr.try_get(idx)));
+        }
+
+        let err = BitError::OutOfBounds { index: 8, len: 8 };
+        assert_eq!(byte.try_get(8), Err(err.clone()));
+        assert_eq!(byte.try_set(8, true), Err(err.clone()));
+        assert_eq!(byte.as_byte(), 0b1000_0100);
+        assert_eq!(err.to_string(), "index 8 out of range for length 8");
+    }
+
+    #[test]
+    fn test_shift() {
+        let byte = Byte::from(0b1001_0110);
+        let shl = byte << 3;
+        assert!(shl.get_7().is_one());
+        assert!(shl.get_6().is_zero());
+        assert!(shl.get_5().is_one());
+        assert!(shl.get_4().is_one());
+        assert!(shl.get_3().is_zero());
+        assert!(shl.get_2().is_zero());
+        assert!(shl.get_1().is_zero());
+        assert!(shl.get_0().is_zero());
+
+        let shr = byte >> 3;
+        assert_eq!(shr.as_byte(), 0b0001_0010);
+        assert!(shr.get_7().is_zero());
+        assert!(shr.get_4().is_one());
+        assert!(shr.get_1().is_one());
+
+        for n in 0..8 {
+            assert_eq!((byte << n).as_byte(), byte.as_byte() << n);
+            assert_eq!((byte >> n).as_byte(), byte.as_byte() >> n);
+        }
+        assert_eq!(byte << 8, Byte::from(0));
+        assert_eq!(byte >> 100, Byte::from(0));
+        assert_eq!(byte.checked_shl(8), None);
+        assert_eq!(byte.checked_shr(7), Some(Byte::from(1)));
+
+        let mut assigned = byte;
+        assigned <<= 1;
+        assert_eq!(assigned.as_byte(), 0b0010_1100);
+        assigned >>= 2;
+        assert_eq!(assigned.as_byte(), 0b0000_1011);
+    }
+
+    #[test]
+    fn test_rotate() {
+        let byte = Byte::from(0b1001_0110);
+        assert_eq!(byte.rotate_left(8), byte);
+        assert_eq!(byte.rotate_right(8), byte);
+        assert_eq!(byte.rotate_left(1).as_byte(), 0b0010_1101);
+        assert_eq!(byte.rotate_right(1).as_byte(), 0b0100_1011);
+        assert_eq!(byte.rotate_left(11), byte.rotate_left(3));
+        assert!(byte.rotate_left(1).get_0().is_one());
+        for n in 0..16 {
+            assert_eq!(byte.rotate_left(n).rotate_right(n), byte);
+        }
+    }
+
+    #[test]
+    fn test_format() {
+        let byte = Byte::from(0b1100_1101);
+        assert_eq!(format!("{}", byte), "1100_1101");
+        assert_eq!(format!("{:b}", byte), "11001101");
+        assert_eq!(format!("{:#b}", byte), "0b11001101");
+        assert_eq!(format!("{:>10b}", byte), "  11001101");
+        assert_eq!(format!("{:*<12}", byte), "1100_1101***");
+        assert_eq!(format!("{:012b}", byte), "000011001101");
+        assert_eq!(format!("{}", Byte::from(0)), "0000_0000");
+        assert_eq!(format!("{:b}", Byte::from(1)), "00000001");
+        assert_eq!(format!("{}", Byte::from(u8::MAX)), "1111_1111");
+    }
+
+    #[test]
+    fn test_debug() {
+        assert_eq!(format!("{:?}", Byte::from(205)), "Byte(0b1100_1101)");
+        assert_eq!(format!("{:?}", Byte::from(1)), "Byte(0b0000_0001)");
+        assert_eq!(format!("{:#?}", Byte::from(0)), "Byte(0b0000_0000)");
+        assert_eq!(
+            format!("{:?}", Some(Byte::from(0xF0))),
+            "Some(Byte(0b1111_0000))"
+        );
+    }
+
+    #[test]
+    fn test_format_radix() {
+        let byte = Byte::from(0xAB);
+        assert_eq!(format!("{:x}", byte), "ab");
+        assert_eq!(format!("{:X}", byte), "AB");
+        assert_eq!(format!("{:o}", byte), "253");
+        assert_eq!(format!("{:#x}", byte), "0xab");
+        assert_eq!(format!("{:#o}", byte), "0o253");
+        assert_eq!(format!("{:02x}", Byte::from(0x0A)), "0a");
+        assert_eq!(format!("{:#06X}", Byte::from(0x0A)), "0x000A");
+        assert_eq!(format!("{:>5x}", byte), "   ab");
+        assert_eq!(format!("{:_<4o}", Byte::from(8)), "10__");
+        for val in [0u8, 1, 0x7F, 0xFF] {
+            assert_eq!(format!("{:#x}", Byte::from(val)), format!("{:#x}", val));
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        for value in [0u8, 1, 0b1100_1101, 0b1000_0000, u8::MAX] {
+            let byte = Byte::from(value);
+            assert_eq!(format!("{}", byte).parse::<Byte>(), Ok(byte));
+            assert_eq!(format!("{:b}", byte).parse::<Byte>(), Ok(byte));
+        }
+        assert_eq!(" 1100_1101\n".parse(), Ok(Byte::from(0b1100_1101)));
+        assert_eq!("0000_0000".parse(), Ok(Byte::from(0)));
+        assert_eq!("1111_1111".parse(), Ok(Byte::from(u8::MAX)));
+
+        assert_eq!("".parse::<Byte>(), Err(ParseByteError::WrongLength(0)));
+        assert_eq!(
+            "1100_110".parse::<Byte>(),
+            Err(ParseByteError::WrongLength(7))
+        );
+        assert_eq!(
+            "1_1001_1010".parse::<Byte>(),
+            Err(ParseByteError::WrongLength(9))
+        );
+        assert_eq!(
+            "1100_1201".parse::<Byte>(),
+            Err(ParseByteError::InvalidDigit('2'))
+        );
+        assert_eq!(
+            "1100 1101".parse::<Byte>(),
+            Err(ParseByteError::InvalidDigit(' '))
+        );
+        assert_eq!(
+            ParseByteError::WrongLength(7).to_string(),
+            "expected 8 binary digits, found 7"
+        );
+        assert_eq!(
+            ParseByteError::InvalidDigit('x').to_string(),
+            "invalid digit 'x'"
+        );
+    }
+
+    #[test]
+    fn test_parse_error_is_error() {
+        fn parse_boxed(s: &str) -> Result<Byte, std::boxed::Box<dyn core::error::Error>> {
+            Ok(s.parse::<Byte>()?)
+        }
+        assert_eq!(parse_boxed("1010_0101").unwrap(), Byte::from(0b1010_0101));
+        let err = parse_boxed("1010_0102").unwrap_err();
+        assert_eq!(err.to_string(), "invalid digit '2'");
+    }
+
+    #[test]
+    fn test_from_str_radix() {
+        let expected = Ok(Byte::from(0xAF));
+        assert_eq!(Byte::from_str_radix("1010_1111", 2), expected);
+        assert_eq!(Byte::from_str_radix("257", 8), expected);
+        assert_eq!(Byte::from_str_radix("175", 10), expected);
+        assert_eq!(Byte::from_str_radix("aF", 16), expected);
+        assert_eq!(Byte::from_str_radix("4V", 36), expected);
+        assert_eq!(Byte::from_str_radix("255", 10), Ok(Byte::from(u8::MAX)));
+
+        assert_eq!(
+            Byte::from_str_radix("256", 10),
+            Err(ParseByteError::Overflow)
+        );
+        assert_eq!(
+            Byte::from_str_radix("1_0000_0000", 2),
+            Err(ParseByteError::Overflow)
+        );
+        assert_eq!(
+            Byte::from_str_radix("18", 8),
+            Err(ParseByteError::InvalidDigit('8'))
+        );
+        assert_eq!(
+            Byte::from_str_radix("-1", 10),
+            Err(ParseByteError::InvalidDigit('-'))
+        );
+        assert_eq!(Byte::from_str_radix("__", 16), Err(ParseByteError::Empty));
+    }
+
+    #[test]
+    fn test_parse_prefixed() {
+        let expected = Ok(Byte::from(0xAF));
+        for s in [
+            "0xAF",
+            "0Xaf",
+            "0o257",
+            "0O2_57",
+            "0b1010_1111",
+            "175",
+            "1_75",
+        ] {
+            assert_eq!(Byte::parse(s), expected, "{}", s);
+        }
+        assert_eq!(Byte::parse("0x1FF"), Err(ParseByteError::Overflow));
+        assert_eq!(Byte::parse("0x"), Err(ParseByteError::Empty));
+        assert_eq!(Byte::parse(""), Err(ParseByteError::Empty));
+        assert_eq!(Byte::parse("0b102"), Err(ParseByteError::InvalidDigit('2')));
+
+        assert_eq!("0b1100_1101".parse(), Ok(Byte::from(0b1100_1101)));
+        assert_eq!(" 0xCD ".parse(), Ok(Byte::from(0xCD)));
+        assert_eq!("0x1FF".parse::<Byte>(), Err(ParseByteError::Overflow));
+    }
+
+    #[test]
+    #[should_panic(expected = "radix 37 is not between 2 and 36")]
+    fn test_from_str_radix_bad_radix() {
+        let _ = Byte::from_str_radix("1", 37);
+    }
+
+    #[test]
+    fn test_clmul() {
+        let x = Byte::from(0b10);
+        let x_plus_1 = Byte::from(0b11);
+        assert_eq!(x.clmul(x), 0b100);
+        // (x + 1)^2 == x^2 + 1 over GF(2).
+        assert_eq!(x_plus_1.clmul(x_plus_1), 0b101);
+        assert_eq!(
+            Byte::from(u8::MAX).clmul(Byte::from(u8::MAX)),
+            0b0101_0101_0101_0101
+        );
+        assert_eq!(Byte::from(0b1010_0001).clmul(Byte::from(0)), 0);
+        assert_eq!(Byte::from(0b1010_0001).clmul(Byte::from(1)), 0b1010_0001);
+
+        for a in [0u8, 1, 0x53, 0xCA, 0xFF] {
+            for b in [0u8, 3, 0x1B, 0x80, 0xF0] {
+                let (a, b) = (Byte::from(a), Byte::from(b));
+                assert_eq!(a.clmul(b), b.clmul(a));
+                let c = Byte::from(0x35);
+                let b_xor_c = Byte::from(b.as_byte() ^ c.as_byte());
+                assert_eq!(a.clmul(b_xor_c), a.clmul(b) ^ a.clmul(c));
+            }
+        }
+    }
+
+    #[test]
+    fn test_compare_primitives() {
+        let one = Bit::from(true);
+        let zero = Bit::ZERO;
+        assert_eq!(one, true);
+        assert_eq!(true, one);
+        assert_eq!(zero, false);
+        assert_eq!(false, zero);
+        assert_ne!(one, false);
+        assert_ne!(false, one);
+        assert!(one.as_bool());
+        assert_eq!(one.as_u8(), 1);
+        assert_eq!(zero.as_u8(), 0);
+
+        let byte = Byte::from(0b1010_1001);
+        assert_eq!(byte.get_3(), true);
+        assert_eq!(byte.get_1(), false);
+        assert_eq!(byte, 0b1010_1001u8);
+        assert_eq!(0b1010_1001u8, byte);
+        assert_eq!(byte, 0b1010_1001);
+        assert_ne!(byte, 0);
+        assert_ne!(0, byte);
+        // Comparisons between the wrappers themselves still infer.
+        assert_eq!(byte, Byte::from(0b1010_1001));
+        assert_eq!(byte.get_0(), Bit::from(1));
+    }
+
+    #[test]
+    fn test_get_set_bits() {
+        let mut byte = Byte::from(0b1011_0110);
+        assert_eq!(byte.get_bits(0..8), 0b1011_0110);
+        assert_eq!(byte.get_bits(0..3), 0b101);
+        assert_eq!(byte.get_bits(2..6), 0b1101);
+        assert_eq!(byte.get_bits(7..8), 0);
+        assert_eq!(byte.get_bits(4..4), 0);
+
+        byte.set_bits(2..6, 0b0010);
+        assert_eq!(byte, 0b1000_1010);
+        byte.set_bits(0..8, 0xFF);
+        assert_eq!(byte, 0xFF);
+        byte.set_bits(5..8, 0);
+        assert_eq!(byte, 0b1111_1000);
+        byte.set_bits(3..3, 0);
+        assert_eq!(byte, 0b1111_1000);
+    }
+
+    #[test]
+    #[should_panic(expected = "value 0x8 does not fit in 3 bits")]
+    fn test_set_bits_too_wide() {
+        Byte::default().set_bits(1..4, 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "range 6..9 out of range for a byte")]
+    fn test_get_bits_out_of_range() {
+        Byte::default().get_bits(6..9);
+    }
+
+    #[test]
+    fn test_index() {
+        let mut byte = Byte::from(0b0101_0000);
+        assert_eq!(&byte[3], &Bit::from(true));
+        assert!(*byte[1]);
+        assert!(!*byte[0]);
+        assert!((0..8).all(|idx| byte[idx] == byte.get_bit_ordered(idx, BitOrder::Msb0)));
+        // Indexing only borrows the byte shared, and `Bit` is `Copy`, so it can be changed afterwards.
+        let bit = byte[3];
+        byte.set_4(false);
+        assert!(bit.is_one());
+        assert!(byte[3].is_zero());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_index_agrees_with_array() {
+        for val in 0..=u8::MAX {
+            let byte = Byte::from(val);
+            let arr = BitArray::from_bytes(&[val]);
+            assert!((0..8).all(|idx| byte[idx] == arr[idx]));
+            assert!(byte.iter_ones().all(|idx| byte[idx].is_one()));
+            assert!(byte.iter_zeros().all(|idx| byte[idx].is_zero()));
+            assert!(byte.first_one().is_none_or(|idx| byte[idx].is_one()));
+        }
+        assert!(Byte::from(0b1000_0000)[0].is_one());
+    }
+
+    #[test]
+    #[should_panic(expected = "bit index 8 out of range for a byte")]
+    fn test_index_out_of_range() {
+        let _ = Byte::default()[8];
+    }
+
+    #[test]
+    fn test_toggle() {
+        let mut byte = Byte::from(0b1010_1010);
+        for idx in 0..8 {
+            let old = byte.get_bit_ordered(idx, BitOrder::Msb0);
+            assert_eq!(byte.toggle(idx), !*old);
+        }
+        assert_eq!(byte, 0b0101_0101);
+
+        for idx in 0..8 {
+            byte.toggle(idx);
+            byte.toggle(idx);
+        }
+        assert_eq!(byte, 0b0101_0101);
+
+        let mut byte = Byte::default();
+        assert_eq!(byte.toggle_0(), true);
+        assert_eq!(byte.toggle_7(), true);
+        assert_eq!(byte.toggle_3(), true);
+        assert_eq!(byte, 0b1000_1001);
+        assert_eq!(byte.toggle_3(), false);
+        byte.toggle_1();
+        byte.toggle_2();
+        byte.toggle_4();
+        byte.toggle_5();
+        byte.toggle_6();
+        assert_eq!(byte, 0b1111_0111);
+
+        let mut byte = Byte::default();
+        assert!(byte.toggle(2).is_one());
+        assert_eq!(byte.first_one(), Some(2));
+        assert_eq!(byte, 0b0010_0000);
+    }
+
+    #[test]
+    #[should_panic(expected = "bit index 8 out of range for a byte")]
+    fn test_toggle_out_of_range() {
+        Byte::default().toggle(8);
+    }
+
+    #[test]
+    fn test_set_clear_toggle_all() {
+        let mut byte = Byte::from(0b0110_0001);
+        byte.toggle_all();
+        assert_eq!(byte, 0b1001_1110);
+        byte.set_all();
+        assert_eq!(byte, 0xFF);
+        byte.clear_all();
+        assert_eq!(byte, 0);
+    }
+
+    #[test]
+    fn test_swap() {
+        let mut byte = Byte::from(0b1000_0001);
+        byte.swap(0, 7);
+        assert_eq!(byte, 0b1000_0001);
+        byte.swap(0, 1);
+        assert_eq!(byte, 0b0100_0001);
+        assert_eq!(byte.first_one(), Some(1));
+        byte.swap(7, 6);
+        assert_eq!(byte, 0b0100_0010);
+        assert!(byte.iter_ones().eq([1, 6].iter().copied()));
+        byte.swap(3, 3);
+        assert_eq!(byte, 0b0100_0010);
+    }
+
+    #[test]
+    fn test_reverse_bits() {
+        assert_eq!(Byte::from(0b1100_1101).reverse_bits(), 0b1011_0011);
+        for val in 0..=u8::MAX {
+            let byte = Byte::from(val);
+            assert_eq!(byte.reverse_bits(), val.reverse_bits());
+            assert_eq!(byte.reverse_bits().reverse_bits(), byte);
+            assert!(byte.reverse_bits().bits().eq(byte.bits().rev()));
+        }
+    }
+
+    #[test]
+    fn test_runs() {
+        let one = Bit::ONE;
+        let zero = Bit::ZERO;
+        assert_eq!(
+            Byte::from(0b1110_0101).runs().collect::<Vec<_>>(),
+            [(one, 3), (zero, 2), (one, 1), (zero, 1), (one, 1)]
+        );
+        assert_eq!(Byte::from(0).runs().collect::<Vec<_>>(), [(zero, 8)]);
+        assert_eq!(Byte::from(0xFF).runs().collect::<Vec<_>>(), [(one, 8)]);
+        assert_eq!(Byte::from(0b1010_1010).runs().count(), 8);
+        for val in 0..=u8::MAX {
+            let byte = Byte::from(val);
+            assert_eq!(byte.runs().map(|(_, len)| len).sum::<usize>(), 8);
+            let ones: usize = byte
+                .runs()
+                .filter(|(b, _)| b.is_one())
+                .map(|(_, len)| len)
+                .sum();
+            assert_eq!(ones, byte.count_ones() as usize);
+        }
+    }
+
+    #[test]
+    fn test_predicates() {
+        for (value, all, any) in [
+            (0x00u8, false, false),
+            (0xFF, true, true),
+            (0x80, false, true),
+        ] {
+            let byte = Byte::from(value);
+            assert_eq!(byte.all(), all);
+            assert_eq!(byte.any(), any);
+            assert_eq!(byte.none(), !any);
+        }
+    }
+
+    #[test]
+    fn test_gray() {
+        assert_eq!(Byte::from(0b0000_0010).to_gray(), 0b0000_0011);
+        assert_eq!(Byte::from(0b1000_0000).to_gray(), 0b1100_0000);
+        for value in 0..=u8::MAX {
+            let byte = Byte::from(value);
+            assert_eq!(Byte::from_gray(byte.to_gray()), byte);
+            let next = Byte::from(value.wrapping_add(1));
+            assert_eq!(byte.to_gray().hamming_distance(next.to_gray()), 1);
+        }
+    }
+
+    #[test]
+    fn test_parity() {
+        assert_eq!(Byte::from(0x00).parity(), Bit::ZERO);
+        assert_eq!(Byte::from(0xFF).parity(), Bit::ZERO);
+        assert_eq!(Byte::from(0x7F).parity(), Bit::ONE);
+
+        // 7-bit ASCII with the standard even and odd parity bits in the left-most position.
+        for (c, even, odd) in [
+            (b'A', 0x41, 0xC1),
+            (b'C', 0xC3, 0x43),
+            (b'0', 0x30, 0xB0),
+            (b'7', 0xB7, 0x37),
+            (0x00, 0x00, 0x80),
+            (0x7F, 0xFF, 0x7F),
+        ] {
+            let byte = Byte::from(c);
+            assert_eq!(byte.with_even_parity_bit(), even);
+            assert_eq!(byte.with_odd_parity_bit(), odd);
+            assert!(byte.with_even_parity_bit().check_even_parity());
+            assert!(!byte.with_odd_parity_bit().check_even_parity());
+            // Any existing left-most bit is replaced.
+            assert_eq!(Byte::from(c | 0x80).with_even_parity_bit(), even);
+        }
+    }
+
+    #[test]
+    fn test_interleave() {
+        assert_eq!(Byte::from(0xFF).interleave(Byte::from(0x00)), 0xAAAA);
+        assert_eq!(Byte::from(0x00).interleave(Byte::from(0xFF)), 0x5555);
+        assert_eq!(
+            Byte::from(0b1000_0001).interleave(Byte::from(0b0100_0000)),
+            0b1001_0000_0000_0010
+        );
+        for x in (0..=u8::MAX).step_by(7) {
+            for y in (0..=u8::MAX).step_by(11) {
+                let (x, y) = (Byte::from(x), Byte::from(y));
+                let word = x.interleave(y);
+                assert_eq!(Byte::deinterleave(word), (x, y));
+                for i in 0..8 {
+                    assert_eq!(
+                        word & (0x8000 >> (2 * i)) != 0,
+                        x.get_bit_ordered(i, BitOrder::Msb0).is_one()
+                    );
+                    assert_eq!(
+                        word & (0x8000 >> (2 * i + 1)) != 0,
+                        y.get_bit_ordered(i, BitOrder::Msb0).is_one()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_nibbles() {
+        assert_eq!(Byte::from(0xA7).nibbles(), (0xA, 0x7));
+        assert_eq!(Byte::from(0x0F).nibbles(), (0x0, 0xF));
+    }
+
+    #[test]
+    fn test_hamming_distance() {
+        let a = Byte::from(0b1100_1101);
+        assert_eq!(a.hamming_distance(a), 0);
+        assert_eq!(a.hamming_distance(!a), 8);
+        assert_eq!(a.hamming_distance(Byte::from(0b0100_1111)), 2);
+        for (x, y) in [(0u8, 0xFFu8), (0x53, 0xCA), (0x0F, 0x1E)] {
+            let (x, y) = (Byte::from(x), Byte::from(y));
+            let naive = x.bits().zip(y.bits()).filter(|(p, q)| p != q).count();
+            assert_eq!(x.hamming_distance(y) as usize, naive);
+            assert_eq!(x.hamming_distance(y), y.hamming_distance(x));
+        }
+    }
+
     #[test]
     fn test_iter() {
         let mut test_byte = Byte::from(0);
@@ -415,14 +2232,171 @@ mod tests {
         test_byte.set_4(true);
         test_byte.set_6(true);
         let mut iter = test_byte.into_iter();
-        assert_eq!(iter.next(), Some(Bit::get_zero_bit()));
-        assert_eq!(iter.next(), Some(Bit::get_one_bit()));
-        assert_eq!(iter.next(), Some(Bit::get_one_bit()));
-        assert_eq!(iter.next(), Some(Bit::get_one_bit()));
-        assert_eq!(iter.next(), Some(Bit::get_one_bit()));
-        assert_eq!(iter.next(), Some(Bit::get_zero_bit()));
-        assert_eq!(iter.next(), Some(Bit::get_one_bit()));
-        assert_eq!(iter.next(), Some(Bit::get_zero_bit()));
+        assert_eq!(iter.next(), Some(Bit::ZERO));
+        assert_eq!(iter.next(), Some(Bit::ONE));
+        assert_eq!(iter.next(), Some(Bit::ONE));
+        assert_eq!(iter.next(), Some(Bit::ONE));
+        assert_eq!(iter.next(), Some(Bit::ONE));
+        assert_eq!(iter.next(), Some(Bit::ZERO));
+        assert_eq!(iter.next(), Some(Bit::ONE));
+        assert_eq!(iter.next(), Some(Bit::ZERO));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_count() {
+        for value in (0..=u8::MAX).step_by(7) {
+            let byte = Byte::from(value);
+            let naive = byte.bits().filter(|b| b.is_one()).count() as u32;
+            assert_eq!(byte.count_ones(), naive);
+            assert_eq!(byte.count_zeros(), 8 - naive);
+        }
+        assert_eq!(Byte::from(0).count_ones(), 0);
+        assert_eq!(Byte::from(u8::MAX).count_zeros(), 0);
+    }
+
+    #[test]
+    fn test_scan() {
+        for value in 0..=u8::MAX {
+            let byte = Byte::from(value);
+            // Index 0 is the left-most bit.
+            let bits: Vec<bool> = (0..8).map(|idx| *byte.get_bit(7 - idx)).collect();
+            assert_eq!(byte.first_one(), bits.iter().position(|&b| b));
+            assert_eq!(byte.first_zero(), bits.iter().position(|&b| !b));
+            assert_eq!(byte.last_one(), bits.iter().rposition(|&b| b));
+            assert_eq!(
+                byte.leading_zeros() as usize,
+                bits.iter().take_while(|&&b| !b).count()
+            );
+            assert_eq!(
+                byte.trailing_zeros() as usize,
+                bits.iter().rev().take_while(|&&b| !b).count()
+            );
+        }
+        assert_eq!(Byte::from(0).leading_zeros(), 8);
+        assert_eq!(Byte::from(0).first_one(), None);
+        assert_eq!(Byte::from(u8::MAX).first_zero(), None);
+        assert_eq!(Byte::from(0b0010_0100).first_one(), Some(2));
+        assert_eq!(Byte::from(0b0010_0100).last_one(), Some(5));
+    }
+
+    #[test]
+    fn test_borrowed_iter() {
+        /// Counts the set bits of anything iterable by reference, without requiring `Copy`.
+        fn count_ones<T>(container: &T) -> usize
+        where
+            for<'a> &'a T: IntoIterator<Item = Bit>,
+        {
+            container.into_iter().filter(|b| b.is_one()).count()
+        }
+
+        let byte = Byte::from(0b1010_0110);
+        let ones: Vec<usize> = byte
+            .bits()
+            .enumerate()
+            .filter(|(_, b)| b.is_one())
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(ones, [1, 2, 5, 7]);
+        assert_eq!(count_ones(&byte), 4);
+        assert!((&byte).into_iter().eq(byte.bits()));
+
+        let mut seen = 0;
+        for bit in &byte {
+            seen += usize::from(*bit);
+        }
+        assert_eq!(seen, 4);
+    }
+
+    #[test]
+    fn test_bit_order() {
+        let byte = Byte::from(0b1000_0000);
+        assert!(byte.get_bit_ordered(0, BitOrder::Msb0).is_one());
+        assert!(byte.get_bit_ordered(7, BitOrder::Msb0).is_zero());
+        assert!(byte.get_bit_ordered(0, BitOrder::Lsb0).is_zero());
+        assert!(byte.get_bit_ordered(7, BitOrder::Lsb0).is_one());
+        // The legacy getters count in `Lsb0` order.
+        assert_eq!(byte.get_7(), byte.get_bit_ordered(7, BitOrder::Lsb0));
+
+        let msb: Vec<bool> = byte.bits_ordered(BitOrder::Msb0).map(|b| *b).collect();
+        assert_eq!(msb, [true, false, false, false, false, false, false, false]);
+        let lsb: Vec<bool> = byte.bits_ordered(BitOrder::Lsb0).map(|b| *b).collect();
+        assert_eq!(lsb, [false, false, false, false, false, false, false, true]);
+        assert!(byte.bits().eq(byte.bits_ordered(BitOrder::Lsb0)));
+        assert!(byte
+            .bits_ordered(BitOrder::Msb0)
+            .rev()
+            .eq(byte.bits_ordered(BitOrder::Lsb0)));
+
+        let mut byte = Byte::default();
+        byte.set_bit_ordered(1, true, BitOrder::Msb0);
+        byte.set_bit_ordered(1, true, BitOrder::Lsb0);
+        assert_eq!(byte.as_byte(), 0b0100_0010);
+    }
+
+    #[test]
+    #[should_panic(expected = "bit index 8 out of range for a byte")]
+    fn test_bit_order_out_of_range() {
+        Byte::default().get_bit_ordered(8, BitOrder::Msb0);
+    }
+
+    #[test]
+    fn test_iter_len() {
+        let mut iter = Byte::from(0b0101_1110).into_iter();
+        assert_eq!(iter.len(), 8);
+        iter.next();
+        iter.next();
+        assert_eq!(iter.len(), 6);
+        assert_eq!(iter.size_hint(), (6, Some(6)));
+        iter.next_back();
+        assert_eq!(iter.len(), 5);
+        assert_eq!(iter.by_ref().count(), 5);
+        assert_eq!(iter.len(), 0);
         assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn test_iter_rev() {
+        let byte = Byte::from(0b0101_1110);
+        let forward: Vec<Bit> = byte.into_iter().collect();
+        let mut backward: Vec<Bit> = byte.into_iter().rev().collect();
+        backward.reverse();
+        assert_eq!(forward, backward);
+        assert_eq!(byte.into_iter().next_back(), Some(Bit::ZERO));
+
+        // Interleaving never yields the same bit twice.
+        let mut iter = byte.into_iter();
+        let mut seen = Vec::new();
+        while let (Some(front), back) = (iter.next(), iter.next_back()) {
+            seen.push(front);
+            seen.extend(back);
+        }
+        assert_eq!(seen.len(), 8);
+        assert_eq!(seen.iter().filter(|b| b.is_one()).count(), 5);
+    }
+
+    #[test]
+    fn test_iter_indices() {
+        let byte = Byte::from(0b1001_0110);
+        assert_eq!(byte.iter_ones().collect::<Vec<_>>(), [0, 3, 5, 6]);
+        assert_eq!(byte.iter_zeros().collect::<Vec<_>>(), [1, 2, 4, 7]);
+        assert_eq!(byte.iter_ones().rev().collect::<Vec<_>>(), [6, 5, 3, 0]);
+        assert_eq!(byte.iter_ones().len(), 4);
+
+        let mut ones = byte.iter_ones();
+        assert_eq!(ones.next(), Some(0));
+        assert_eq!(ones.next_back(), Some(6));
+        assert_eq!(ones.next(), Some(3));
+        assert_eq!(ones.next_back(), Some(5));
+        assert_eq!(ones.next(), None);
+        assert_eq!(ones.next_back(), None);
+
+        for val in 0..=u8::MAX {
+            let byte = Byte::from(val);
+            let naive = (0..8).filter(|&idx| byte.get_bit_ordered(idx, BitOrder::Msb0).is_one());
+            assert!(byte.iter_ones().eq(naive));
+            assert_eq!(byte.iter_zeros().count(), byte.count_zeros() as usize);
+        }
     }
 }
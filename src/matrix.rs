@@ -0,0 +1,436 @@
+//! Matrices of bits, with linear algebra over GF(2).
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{Bit, BitArray, BitError};
+
+/// A matrix of bits, stored as one packed [`BitArray`] per row.
+///
+/// Arithmetic is over GF(2): addition is XOR and multiplication is AND. Row operations act on
+/// whole packed rows, 64 bits at a time.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct BitMatrix {
+    /// The rows of the matrix, each `cols` bits long.
+    rows: Vec<BitArray>,
+    /// The number of columns.
+    cols: usize,
+}
+
+impl BitMatrix {
+    /// Creates a matrix of `rows` by `cols` bits, all of which are `0`.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            rows: (0..rows).map(|_| BitArray::new(cols)).collect(),
+            cols,
+        }
+    }
+
+    /// Creates the `n` by `n` identity matrix.
+    pub fn identity(n: usize) -> Self {
+        let mut res = Self::new(n, n);
+        for (i, row) in res.rows.iter_mut().enumerate() {
+            row.set(i, true);
+        }
+        res
+    }
+
+    /// Creates a matrix from its rows, with as many columns as the first row has bits.
+    ///
+    /// Returns an error if the rows are not all the same length.
+    pub fn from_rows(rows: Vec<BitArray>) -> Result<Self, BitError> {
+        let cols = rows.first().map_or(0, BitArray::len);
+        if let Some(row) = rows.iter().find(|row| row.len() != cols) {
+            return Err(BitError::LengthMismatch {
+                left: cols,
+                right: row.len(),
+            });
+        }
+        Ok(Self { rows, cols })
+    }
+
+    /// The number of rows.
+    pub fn rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// The number of columns.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// The bits of row `r`.
+    ///
+    /// Panics if `r` is not less than the number of rows.
+    pub fn row(&self, r: usize) -> &BitArray {
+        &self.rows[r]
+    }
+
+    /// Getter for the bit in row `r` and column `c`.
+    ///
+    /// Panics if either index is out of range.
+    pub fn get(&self, r: usize, c: usize) -> Bit {
+        self.rows[r].get(c)
+    }
+
+    /// Setter for the bit in row `r` and column `c`.
+    ///
+    /// Panics if either index is out of range.
+    pub fn set(&mut self, r: usize, c: usize, val: bool) {
+        self.rows[r].set(c, val);
+    }
+
+    /// The product of the matrix with the column vector `v`: bit `i` is the parity of row `i`
+    /// ANDed with `v`.
+    ///
+    /// Returns an error if `v` does not have one bit per column.
+    pub fn mul_vec(&self, v: &BitArray) -> Result<BitArray, BitError> {
+        if v.len() != self.cols {
+            return Err(BitError::LengthMismatch {
+                left: self.cols,
+                right: v.len(),
+            });
+        }
+        Ok(self.rows.iter().map(|row| (row & v).parity()).collect())
+    }
+
+    /// The matrix product `self * other` over GF(2).
+    ///
+    /// Row `i` of the product is the XOR of the rows of `other` picked out by the `1` bits of
+    /// row `i` of `self`, so the work is whole packed rows rather than single bits. Returns an
+    /// error if `self` does not have as many columns as `other` has rows.
+    pub fn multiply(&self, other: &BitMatrix) -> Result<BitMatrix, BitError> {
+        if self.cols != other.rows() {
+            return Err(BitError::LengthMismatch {
+                left: self.cols,
+                right: other.rows(),
+            });
+        }
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| {
+                let mut acc = BitArray::new(other.cols);
+                for k in row.iter_ones() {
+                    acc ^= &other.rows[k];
+                }
+                acc
+            })
+            .collect();
+        Ok(BitMatrix {
+            rows,
+            cols: other.cols,
+        })
+    }
+
+    /// The matrix drawn with Unicode braille characters, each showing a block of 4 rows by 2
+    /// columns, one `1` bit to a raised dot.
+    ///
+    /// Each line of text covers four rows, and lines are separated by `\n` with none after the
+    /// last. Rows and columns past the edge of the matrix count as `0`, so an empty matrix gives
+    /// an empty string.
+    pub fn render_braille(&self) -> String {
+        // The dot raised for each row of a cell, in the left and right columns.
+        const DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+        let mut out = String::new();
+        for top in (0..self.rows()).step_by(4) {
+            if top > 0 {
+                out.push('\n');
+            }
+            for left in (0..self.cols).step_by(2) {
+                let mut dots = 0;
+                for (dr, row_dots) in DOTS.iter().enumerate() {
+                    for (dc, &dot) in row_dots.iter().enumerate() {
+                        let (r, c) = (top + dr, left + dc);
+                        if r < self.rows() && c < self.cols && self.get(r, c).is_one() {
+                            dots |= dot;
+                        }
+                    }
+                }
+                out.push(char::from_u32(0x2800 + dots).expect("braille patterns are characters"));
+            }
+        }
+        out
+    }
+
+    /// XORs row `src` into row `dst`, which must differ.
+    fn xor_row(&mut self, dst: usize, src: usize) {
+        let (dst, src) = if dst < src {
+            let (head, tail) = self.rows.split_at_mut(src);
+            (&mut head[dst], &tail[0])
+        } else {
+            let (head, tail) = self.rows.split_at_mut(dst);
+            (&mut tail[0], &head[src])
+        };
+        *dst ^= src;
+    }
+
+    /// The reduced row echelon form of the matrix, by Gauss-Jordan elimination, with the column
+    /// of each pivot in order.
+    ///
+    /// Every pivot is the only `1` in its column, and the rows without a pivot are all zero and
+    /// come last. Each elimination step XORs whole packed rows.
+    pub fn row_reduce(&self) -> (BitMatrix, Vec<usize>) {
+        let mut res = self.clone();
+        let mut pivots = Vec::new();
+        for col in 0..self.cols {
+            let next = pivots.len();
+            let Some(found) = (next..res.rows()).find(|&r| res.get(r, col).is_one()) else {
+                continue;
+            };
+            res.rows.swap(next, found);
+            for r in 0..res.rows() {
+                if r != next && res.get(r, col).is_one() {
+                    res.xor_row(r, next);
+                }
+            }
+            pivots.push(col);
+            if pivots.len() == res.rows() {
+                break;
+            }
+        }
+        (res, pivots)
+    }
+
+    /// The rank of the matrix over GF(2): the number of linearly independent rows.
+    pub fn rank(&self) -> usize {
+        self.row_reduce().1.len()
+    }
+
+    /// A solution `x` of `self * x = rhs`, or `None` if the system has no solution.
+    ///
+    /// When there are many solutions, every free variable is set to `0`.
+    /// Panics if `rhs` does not have one bit per row.
+    pub fn solve(&self, rhs: &BitArray) -> Option<BitArray> {
+        assert_eq!(
+            rhs.len(),
+            self.rows(),
+            "right-hand side of length {} for a matrix with {} rows",
+            rhs.len(),
+            self.rows()
+        );
+        // Reduce the matrix augmented with `rhs` as an extra column.
+        let augmented = BitMatrix {
+            rows: self
+                .rows
+                .iter()
+                .enumerate()
+                .map(|(r, row)| row.concat(&BitArray::from_fn(1, |_| *rhs.get(r))))
+                .collect(),
+            cols: self.cols + 1,
+        };
+        let (reduced, pivots) = augmented.row_reduce();
+        if pivots.last() == Some(&self.cols) {
+            // A row reads 0 = 1.
+            return None;
+        }
+        let mut x = BitArray::new(self.cols);
+        for (r, &col) in pivots.iter().enumerate() {
+            x.set(col, *reduced.get(r, self.cols));
+        }
+        Some(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Prbs, PrbsKind};
+    use alloc::vec;
+
+    fn matrix(rows: &[&str]) -> BitMatrix {
+        BitMatrix::from_rows(
+            rows.iter()
+                .map(|row| row.chars().map(|c| c == '1').collect())
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    fn random(prbs: &mut Prbs, rows: usize, cols: usize) -> BitMatrix {
+        BitMatrix::from_rows((0..rows).map(|_| prbs.take(cols).collect()).collect()).unwrap()
+    }
+
+    /// The rank of a matrix of bools, by elimination one bit at a time.
+    fn naive_rank(mut m: Vec<Vec<bool>>) -> usize {
+        let cols = m.first().map_or(0, Vec::len);
+        let mut rank = 0;
+        for col in 0..cols {
+            if let Some(p) = (rank..m.len()).find(|&r| m[r][col]) {
+                m.swap(rank, p);
+                let pivot = m[rank].clone();
+                for (r, row) in m.iter_mut().enumerate() {
+                    if r != rank && row[col] {
+                        row.iter_mut().zip(&pivot).for_each(|(x, &p)| *x ^= p);
+                    }
+                }
+                rank += 1;
+            }
+        }
+        rank
+    }
+
+    #[test]
+    fn test_new_and_access() {
+        let mut m = BitMatrix::new(3, 70);
+        assert_eq!((m.rows(), m.cols()), (3, 70));
+        m.set(2, 65, true);
+        assert_eq!(m.get(2, 65), Bit::ONE);
+        assert_eq!(m.row(2).count_ones(), 1);
+        assert_eq!(
+            BitMatrix::from_rows(vec![BitArray::new(3), BitArray::new(4)]),
+            Err(BitError::LengthMismatch { left: 3, right: 4 })
+        );
+        assert_eq!(BitMatrix::from_rows(Vec::new()), Ok(BitMatrix::new(0, 0)));
+    }
+
+    #[test]
+    fn test_identity_rank() {
+        for n in [0, 1, 5, 64, 100] {
+            assert_eq!(BitMatrix::identity(n).rank(), n);
+        }
+        assert_eq!(BitMatrix::new(4, 6).rank(), 0);
+    }
+
+    #[test]
+    fn test_row_reduce() {
+        let m = matrix(&["0110", "1101", "1011"]);
+        let (reduced, pivots) = m.row_reduce();
+        // The third row is the sum of the first two.
+        assert_eq!(pivots, [0, 1]);
+        assert_eq!(reduced, matrix(&["1011", "0110", "0000"]));
+    }
+
+    #[test]
+    fn test_singular_system() {
+        let m = matrix(&["110", "011", "101"]);
+        assert_eq!(m.rank(), 2);
+        let rhs: BitArray = [true, false, false].iter().copied().collect();
+        assert_eq!(m.solve(&rhs), None);
+    }
+
+    #[test]
+    fn test_solve() {
+        let mut prbs = Prbs::new(PrbsKind::Prbs15);
+        for (rows, cols) in [(1, 1), (5, 5), (8, 12), (70, 70), (100, 65)] {
+            let m = random(&mut prbs, rows, cols);
+            let x: BitArray = prbs.by_ref().take(cols).collect();
+            let rhs = m.mul_vec(&x).unwrap();
+            let solution = m.solve(&rhs).expect("the system was built to be solvable");
+            assert_eq!(m.mul_vec(&solution).unwrap(), rhs);
+        }
+    }
+
+    #[test]
+    fn test_rank_against_naive() {
+        let mut prbs = Prbs::new(PrbsKind::Prbs15);
+        for (rows, cols) in [(3, 3), (10, 4), (4, 10), (30, 30), (65, 70)] {
+            let m = random(&mut prbs, rows, cols);
+            let bools = (0..rows)
+                .map(|r| (0..cols).map(|c| *m.get(r, c)).collect())
+                .collect();
+            assert_eq!(m.rank(), naive_rank(bools));
+        }
+        // Duplicated rows add nothing to the rank.
+        let m = random(&mut prbs, 6, 40);
+        let mut rows: Vec<BitArray> = (0..6).map(|r| m.row(r).clone()).collect();
+        rows.extend_from_within(..);
+        assert_eq!(BitMatrix::from_rows(rows).unwrap().rank(), m.rank());
+    }
+
+    #[test]
+    fn test_multiply_identity() {
+        let mut prbs = Prbs::new(PrbsKind::Prbs15);
+        let m = random(&mut prbs, 7, 70);
+        assert_eq!(BitMatrix::identity(7).multiply(&m), Ok(m.clone()));
+        assert_eq!(m.multiply(&BitMatrix::identity(70)), Ok(m));
+    }
+
+    #[test]
+    fn test_multiply_known() {
+        let a = matrix(&["1011", "0110", "1100", "0001"]);
+        let b = matrix(&["1000", "1100", "0110", "1011"]);
+        let expected = matrix(&["0101", "1010", "0100", "1011"]);
+        assert_eq!(a.multiply(&b), Ok(expected));
+    }
+
+    #[test]
+    fn test_multiply_against_naive() {
+        let mut prbs = Prbs::new(PrbsKind::Prbs15);
+        for (n, k, m) in [(1, 1, 1), (3, 5, 2), (10, 64, 9), (20, 70, 130)] {
+            let (a, b) = (random(&mut prbs, n, k), random(&mut prbs, k, m));
+            let product = a.multiply(&b).unwrap();
+            assert_eq!((product.rows(), product.cols()), (n, m));
+            for i in 0..n {
+                for j in 0..m {
+                    let bit = (0..k).fold(false, |acc, x| acc ^ (*a.get(i, x) && *b.get(x, j)));
+                    assert_eq!(*product.get(i, j), bit);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_multiply_associative() {
+        let mut prbs = Prbs::new(PrbsKind::Prbs15);
+        for _ in 0..4 {
+            let a = random(&mut prbs, 9, 33);
+            let b = random(&mut prbs, 33, 65);
+            let c = random(&mut prbs, 65, 12);
+            let left = a.multiply(&b).unwrap().multiply(&c).unwrap();
+            let right = a.multiply(&b.multiply(&c).unwrap()).unwrap();
+            assert_eq!(left, right);
+        }
+    }
+
+    #[test]
+    fn test_multiply_mismatch() {
+        assert_eq!(
+            BitMatrix::new(2, 3).multiply(&BitMatrix::new(4, 2)),
+            Err(BitError::LengthMismatch { left: 3, right: 4 })
+        );
+    }
+
+    #[test]
+    fn test_render_braille() {
+        assert_eq!(BitMatrix::new(0, 0).render_braille(), "");
+        assert_eq!(BitMatrix::new(4, 2).render_braille(), "\u{2800}");
+        // Each dot on its own, left column top to bottom, then right.
+        let dots = [
+            (0, 0, 0x01),
+            (1, 0, 0x02),
+            (2, 0, 0x04),
+            (3, 0, 0x40),
+            (0, 1, 0x08),
+            (1, 1, 0x10),
+            (2, 1, 0x20),
+            (3, 1, 0x80),
+        ];
+        for &(r, c, dot) in &dots {
+            let mut m = BitMatrix::new(4, 2);
+            m.set(r, c, true);
+            assert_eq!(
+                m.render_braille().chars().next(),
+                char::from_u32(0x2800 + dot)
+            );
+        }
+
+        let mut checkerboard = BitMatrix::new(8, 6);
+        for r in 0..8 {
+            for c in 0..6 {
+                checkerboard.set(r, c, (r + c) % 2 == 0);
+            }
+        }
+        assert_eq!(checkerboard.render_braille(), "⢕⢕⢕\n⢕⢕⢕");
+
+        // A partial cell at the edge counts the missing rows and columns as zero.
+        let full = BitMatrix::from_rows(vec![BitArray::from_fn(3, |_| true); 5]).unwrap();
+        assert_eq!(full.render_braille(), "⣿⡇\n⠉⠁");
+    }
+
+    #[test]
+    #[should_panic(expected = "right-hand side of length 2 for a matrix with 3 rows")]
+    fn test_solve_length_mismatch() {
+        BitMatrix::identity(3).solve(&BitArray::new(2));
+    }
+}
@@ -0,0 +1,244 @@
+//! Conversions between sequences of booleans and packed bytes.
+//!
+//! Bits are packed following the crate's big-endian convention: the first bit is the left-most
+//! bit of the first byte. Unused bits of a partial final byte are zero.
+
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+use core::iter::{ExactSizeIterator, Iterator};
+
+use crate::BitSliceExt;
+#[cfg(feature = "alloc")]
+use crate::{BitArray, BitError};
+
+/// Packs a sequence of booleans into bytes, returning the bytes and the number of bits packed.
+#[cfg(feature = "alloc")]
+pub fn pack_bits<I: IntoIterator<Item = bool>>(iter: I) -> (Vec<u8>, usize) {
+    let iter = iter.into_iter();
+    let mut bytes = Vec::with_capacity(iter.size_hint().0.div_ceil(8));
+    let mut len = 0;
+    for bit in iter {
+        if len % 8 == 0 {
+            bytes.push(0);
+        }
+        if bit {
+            bytes[len / 8] |= 0x80 >> (len % 8);
+        }
+        len += 1;
+    }
+    (bytes, len)
+}
+
+/// Lazily unpacks the first `bit_len` bits of `bytes` into booleans.
+///
+/// Panics if `bit_len` is greater than the number of bits in `bytes`.
+pub fn unpack_bits(bytes: &[u8], bit_len: usize) -> impl ExactSizeIterator<Item = bool> + '_ {
+    assert!(
+        bit_len <= bytes.bit_len(),
+        "bit length {} exceeds the {} bits available",
+        bit_len,
+        bytes.bit_len()
+    );
+    bytes.iter_bits().take(bit_len).map(|bit| *bit)
+}
+
+/// Splits `data`, a run of packed records of `width` bits each, into `width` columns, where bit `i`
+/// of column `j` is bit `j` of record `i`.
+///
+/// Records and columns are handled in blocks of 64 by 64 bits, each read and written a word at a
+/// time and transposed in registers. Returns an error if the length of `data` is not a multiple of
+/// `width`. Panics if `width` is zero.
+///
+/// ```
+/// use bit_array_rs::{transpose_records, untranspose_records, BitArray};
+///
+/// // Three 2-bit records: 10, 11, 01.
+/// let data = BitArray::from_fn(6, |i| [true, false, true, true, false, true][i]);
+/// let columns = transpose_records(&data, 2)?;
+/// assert_eq!(columns[0], BitArray::from_fn(3, |i| i < 2));
+/// assert_eq!(columns[1], BitArray::from_fn(3, |i| i > 0));
+/// assert_eq!(untranspose_records(&columns), data);
+/// # Ok::<(), bit_array_rs::BitError>(())
+/// ```
+#[cfg(feature = "alloc")]
+pub fn transpose_records(data: &BitArray, width: usize) -> Result<Vec<BitArray>, BitError> {
+    assert!(width > 0, "record width must be non-zero");
+    if !data.len().is_multiple_of(width) {
+        return Err(BitError::NotMultipleOf {
+            len: data.len(),
+            width,
+        });
+    }
+    let records = data.len() / width;
+    let mut columns = vec![BitArray::new(records); width];
+    for r0 in (0..records).step_by(64) {
+        let rows = (records - r0).min(64);
+        for c0 in (0..width).step_by(64) {
+            let cols = (width - c0).min(64);
+            let mut block = [0; 64];
+            for (i, word) in block.iter_mut().take(rows).enumerate() {
+                let start = (r0 + i) * width + c0;
+                *word = data.get_bits(start..start + cols) << (64 - cols);
+            }
+            transpose64(&mut block);
+            for (column, &word) in columns[c0..c0 + cols].iter_mut().zip(&block) {
+                column.set_bits(r0..r0 + rows, word >> (64 - rows));
+            }
+        }
+    }
+    Ok(columns)
+}
+
+/// Packs `columns` back into records, the inverse of [`transpose_records`]: record `i` holds bit
+/// `i` of each column in turn.
+///
+/// Panics if the columns are not all the same length.
+#[cfg(feature = "alloc")]
+pub fn untranspose_records(columns: &[BitArray]) -> BitArray {
+    let width = columns.len();
+    let records = columns.first().map_or(0, BitArray::len);
+    assert!(
+        columns.iter().all(|column| column.len() == records),
+        "columns must all have the same length"
+    );
+    let mut data = BitArray::new(records * width);
+    for r0 in (0..records).step_by(64) {
+        let rows = (records - r0).min(64);
+        for c0 in (0..width).step_by(64) {
+            let cols = (width - c0).min(64);
+            let mut block = [0; 64];
+            for (word, column) in block.iter_mut().zip(&columns[c0..c0 + cols]) {
+                *word = column.get_bits(r0..r0 + rows) << (64 - rows);
+            }
+            transpose64(&mut block);
+            for (i, &word) in block.iter().take(rows).enumerate() {
+                let start = (r0 + i) * width + c0;
+                data.set_bits(start..start + cols, word >> (64 - cols));
+            }
+        }
+    }
+    data
+}
+
+/// Transposes a 64 by 64 bit matrix in place, where row `i` is `block[i]` and column 0 is the most
+/// significant bit, by swapping ever smaller off-diagonal blocks.
+#[cfg(feature = "alloc")]
+fn transpose64(block: &mut [u64; 64]) {
+    let mut j = 32;
+    let mut mask = 0x0000_0000_FFFF_FFFF_u64;
+    while j != 0 {
+        let mut k = 0;
+        while k < 64 {
+            let t = (block[k] ^ (block[k + j] >> j)) & mask;
+            block[k] ^= t;
+            block[k + j] ^= t << j;
+            k = (k + j + 1) & !j;
+        }
+        j >>= 1;
+        mask ^= mask << j;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An arbitrary, irregular pattern of bits.
+    #[cfg(feature = "alloc")]
+    fn pattern(len: usize) -> impl Iterator<Item = bool> {
+        (0..len).map(|i| (i * i + i / 3) % 5 < 2)
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_round_trip() {
+        for len in [0, 1, 7, 8, 9, 15, 16, 17, 100] {
+            let (bytes, bit_len) = pack_bits(pattern(len));
+            assert_eq!(bit_len, len);
+            assert_eq!(bytes.len(), len.div_ceil(8));
+            assert!(unpack_bits(&bytes, bit_len).eq(pattern(len)));
+            // Padding bits are zero.
+            assert!(bytes.iter_bits().skip(len).all(|b| b.is_zero()));
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_pack_order() {
+        let (bytes, len) = pack_bits([true, false, true, false, false, false, false, true, true]);
+        assert_eq!(bytes, [0b1010_0001, 0b1000_0000]);
+        assert_eq!(len, 9);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_transpose_records() {
+        use alloc::string::ToString;
+
+        // Two 3-bit records, 101 and 011.
+        let data = BitArray::from_fn(6, |i| [true, false, true, false, true, true][i]);
+        let columns = transpose_records(&data, 3).unwrap();
+        let expected = [[true, false], [false, true], [true, true]];
+        for (column, bools) in columns.iter().zip(&expected) {
+            assert_eq!(column, &BitArray::from(&bools[..]));
+        }
+        assert_eq!(untranspose_records(&columns), data);
+
+        assert_eq!(
+            transpose_records(&BitArray::new(10), 3),
+            Err(BitError::NotMultipleOf { len: 10, width: 3 })
+        );
+        assert_eq!(
+            BitError::NotMultipleOf { len: 10, width: 3 }.to_string(),
+            "length 10 is not a multiple of 3"
+        );
+        let empty = transpose_records(&BitArray::new(0), 4).unwrap();
+        assert_eq!(empty, vec![BitArray::new(0); 4]);
+        assert_eq!(untranspose_records(&empty), BitArray::new(0));
+        assert_eq!(untranspose_records(&[]), BitArray::new(0));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_transpose_matches_naive() {
+        let bits: Vec<bool> = pattern(200 * 130).collect();
+        for width in [1, 7, 32, 64, 65, 130] {
+            for records in [0, 1, 63, 64, 65, 200] {
+                let data = BitArray::from(&bits[..records * width]);
+                let columns = transpose_records(&data, width).unwrap();
+                assert_eq!(columns.len(), width);
+                for (j, column) in columns.iter().enumerate() {
+                    let naive = BitArray::from_fn(records, |i| bits[i * width + j]);
+                    assert_eq!(column, &naive);
+                }
+                assert_eq!(untranspose_records(&columns), data);
+            }
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[should_panic(expected = "columns must all have the same length")]
+    fn test_untranspose_ragged() {
+        untranspose_records(&[BitArray::new(3), BitArray::new(4)]);
+    }
+
+    #[test]
+    fn test_unpack_exact() {
+        let bytes = [0b1100_1101u8, 0b1010_0000];
+        let mut bits = unpack_bits(&bytes, 11);
+        assert_eq!(bits.len(), 11);
+        assert_eq!(bits.next(), Some(true));
+        assert_eq!(bits.next(), Some(true));
+        assert_eq!(bits.next(), Some(false));
+        assert_eq!(bits.len(), 8);
+        assert_eq!(bits.filter(|&b| b).count(), 5);
+        assert_eq!(unpack_bits(&bytes, 0).next(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "bit length 17 exceeds the 16 bits available")]
+    fn test_unpack_too_long() {
+        let _ = unpack_bits(&[0, 0], 17);
+    }
+}
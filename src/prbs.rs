@@ -0,0 +1,353 @@
+//! Standard pseudo-random binary sequences, for bit-error-rate testing.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::iter::{FusedIterator, IntoIterator, Iterator};
+
+#[cfg(feature = "alloc")]
+use crate::BitArray;
+use crate::{Bit, Lfsr, LfsrKind};
+
+/// The standardized PRBS polynomials (ITU-T O.150).
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PrbsKind {
+    /// `x^7 + x^6 + 1`
+    Prbs7,
+    /// `x^9 + x^5 + 1`
+    Prbs9,
+    /// `x^15 + x^14 + 1`
+    Prbs15,
+    /// `x^23 + x^18 + 1`
+    Prbs23,
+    /// `x^31 + x^28 + 1`
+    Prbs31,
+}
+
+impl PrbsKind {
+    /// The degree of the polynomial, which is also the width of the generating register.
+    pub fn width(self) -> u32 {
+        self.exponents().0
+    }
+
+    /// The non-constant exponents of the polynomial, highest first.
+    fn exponents(self) -> (u32, u32) {
+        match self {
+            PrbsKind::Prbs7 => (7, 6),
+            PrbsKind::Prbs9 => (9, 5),
+            PrbsKind::Prbs15 => (15, 14),
+            PrbsKind::Prbs23 => (23, 18),
+            PrbsKind::Prbs31 => (31, 28),
+        }
+    }
+
+    /// A register producing this sequence, whose next outputs are the bits of `seed`.
+    ///
+    /// The register holds upcoming outputs, so `x^n + x^m + 1` taps bits `0` and `n - m`.
+    fn lfsr(self, seed: u64) -> Lfsr {
+        let (n, m) = self.exponents();
+        Lfsr::new(LfsrKind::Fibonacci, n, 1 | (1 << (n - m)), seed)
+    }
+
+    /// A mask covering every bit of the register.
+    fn mask(self) -> u64 {
+        (1 << self.width()) - 1
+    }
+}
+
+/// A generator for one of the standard PRBS patterns.
+///
+/// The output matches the usual shift-left generator seeded with all ones:
+/// each bit is the XOR of the bits `n` and `m` positions before it.
+#[derive(Debug, Clone)]
+pub struct Prbs {
+    /// The register producing the sequence.
+    lfsr: Lfsr,
+}
+
+impl Prbs {
+    /// Creates a generator for the given pattern.
+    pub fn new(kind: PrbsKind) -> Self {
+        let mut lfsr = kind.lfsr(kind.mask());
+        // Skip over the seed, so the first output is the first generated bit.
+        lfsr.step_n(kind.width() as usize);
+        Self { lfsr }
+    }
+
+    /// Produces the next `n` bits of the pattern.
+    #[cfg(feature = "alloc")]
+    pub fn generate(&mut self, n: usize) -> BitArray {
+        self.lfsr.generate(n)
+    }
+}
+
+impl Iterator for Prbs {
+    type Item = Bit;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.lfsr.step())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl FusedIterator for Prbs {}
+
+/// The number of most recent compared bits considered when deciding whether lock was lost.
+const LOCK_WINDOW: u32 = 64;
+
+/// The number of errors within the window at which the checker re-synchronizes.
+const LOCK_THRESHOLD: u32 = LOCK_WINDOW / 4;
+
+/// Checks an incoming bit stream against one of the standard PRBS patterns.
+///
+/// The checker synchronizes by loading the first `width` received bits into its register,
+/// then runs freely and compares every further bit against its own prediction,
+/// so a single flipped bit is counted as exactly one error.
+/// If a quarter of the last 64 compared bits are errors (e.g. after a slip), lock is dropped
+/// and the checker synchronizes again; errors counted before that point are kept.
+#[derive(Debug, Clone)]
+pub struct PrbsChecker {
+    /// The pattern being checked against.
+    kind: PrbsKind,
+    /// The register predicting the incoming bits, once synchronized.
+    lfsr: Option<Lfsr>,
+    /// The bits received while synchronizing.
+    sync: u64,
+    /// The number of bits received while synchronizing.
+    sync_len: u32,
+    /// The outcome of the most recent comparisons, with a `1` for each error.
+    history: u64,
+    /// The number of bits received so far.
+    position: usize,
+    /// The number of errors found so far.
+    error_count: usize,
+    /// The positions of the errors found so far.
+    #[cfg(feature = "alloc")]
+    errors: Vec<usize>,
+}
+
+impl PrbsChecker {
+    /// Creates a checker for the given pattern.
+    pub fn new(kind: PrbsKind) -> Self {
+        Self {
+            kind,
+            lfsr: None,
+            sync: 0,
+            sync_len: 0,
+            history: 0,
+            position: 0,
+            error_count: 0,
+            #[cfg(feature = "alloc")]
+            errors: Vec::new(),
+        }
+    }
+
+    /// True if the checker is synchronized with the incoming stream.
+    pub fn is_locked(&self) -> bool {
+        self.lfsr.is_some()
+    }
+
+    /// The number of bits received so far.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// The number of errors found so far.
+    pub fn error_count(&self) -> usize {
+        self.error_count
+    }
+
+    /// The positions in the incoming stream of the errors found so far.
+    #[cfg(feature = "alloc")]
+    pub fn errors(&self) -> &[usize] {
+        &self.errors
+    }
+
+    /// Feeds one received bit to the checker.
+    ///
+    /// Returns `None` while synchronizing, otherwise whether the bit was as expected.
+    pub fn push(&mut self, bit: Bit) -> Option<bool> {
+        let res = match &mut self.lfsr {
+            None => {
+                self.sync |= u64::from(bit.is_one()) << self.sync_len;
+                self.sync_len += 1;
+                if self.sync_len == self.kind.width() {
+                    // An all-zero register would never leave zero; keep looking.
+                    if self.sync != 0 {
+                        let mut lfsr = self.kind.lfsr(self.sync);
+                        lfsr.step_n(self.sync_len as usize);
+                        self.lfsr = Some(lfsr);
+                        self.history = 0;
+                    }
+                    self.sync = 0;
+                    self.sync_len = 0;
+                }
+                None
+            }
+            Some(lfsr) => {
+                let ok = lfsr.step() == bit;
+                self.history = (self.history << 1) | u64::from(!ok);
+                if !ok {
+                    self.error_count += 1;
+                    #[cfg(feature = "alloc")]
+                    self.errors.push(self.position);
+                }
+                if self.history.count_ones() >= LOCK_THRESHOLD {
+                    self.lfsr = None;
+                }
+                Some(ok)
+            }
+        };
+        self.position += 1;
+        res
+    }
+
+    /// Feeds every bit of `bits` to the checker.
+    pub fn check<I: IntoIterator<Item = Bit>>(&mut self, bits: I) {
+        for bit in bits {
+            self.push(bit);
+        }
+    }
+
+    /// Feeds every bit of `bits` to the checker, in order.
+    #[cfg(feature = "alloc")]
+    pub fn check_bits(&mut self, bits: &BitArray) {
+        self.check(bits.iter());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KINDS: [PrbsKind; 5] = [
+        PrbsKind::Prbs7,
+        PrbsKind::Prbs9,
+        PrbsKind::Prbs15,
+        PrbsKind::Prbs23,
+        PrbsKind::Prbs31,
+    ];
+
+    /// The textbook shift-left generator, seeded with all ones.
+    fn reference(kind: PrbsKind) -> impl Iterator<Item = Bit> {
+        let (n, m) = kind.exponents();
+        let mut state: u64 = (1 << n) - 1;
+        core::iter::repeat_with(move || {
+            let bit = ((state >> (n - 1)) ^ (state >> (m - 1))) & 1;
+            state = ((state << 1) | bit) & ((1 << n) - 1);
+            Bit::from(bit == 1)
+        })
+    }
+
+    /// Packs bits into bytes, most-significant first.
+    fn bytes<I: Iterator<Item = Bit>>(mut bits: I) -> impl Iterator<Item = u8> {
+        core::iter::repeat_with(move || {
+            (0..8).fold(0, |acc, _| {
+                (acc << 1) | u8::from(bits.next().unwrap().is_one())
+            })
+        })
+    }
+
+    #[test]
+    fn test_matches_reference() {
+        for kind in KINDS {
+            assert!(Prbs::new(kind).take(5000).eq(reference(kind).take(5000)));
+        }
+        let prbs7 = [0x02, 0x0C, 0x28, 0xF2, 0x2C, 0xEA, 0x7D, 0x0E];
+        assert!(bytes(Prbs::new(PrbsKind::Prbs7))
+            .take(8)
+            .eq(prbs7.iter().copied()));
+    }
+
+    #[test]
+    fn test_period() {
+        let seq: [Bit; 254] = {
+            let mut seq = [Bit::ZERO; 254];
+            Prbs::new(PrbsKind::Prbs7)
+                .zip(seq.iter_mut())
+                .for_each(|(b, s)| *s = b);
+            seq
+        };
+        assert_eq!(seq[..127], seq[127..]);
+        assert_eq!(seq[..127].iter().filter(|b| b.is_one()).count(), 64);
+    }
+
+    #[test]
+    fn test_clean_loopback() {
+        for kind in KINDS {
+            let mut checker = PrbsChecker::new(kind);
+            checker.check(Prbs::new(kind).take(10_000));
+            assert!(checker.is_locked());
+            assert_eq!(checker.error_count(), 0);
+            assert_eq!(checker.position(), 10_000);
+        }
+    }
+
+    #[test]
+    fn test_injected_errors() {
+        let injected = [100, 101, 500, 1234, 4000];
+        let mut checker = PrbsChecker::new(PrbsKind::Prbs15);
+        checker.check(
+            Prbs::new(PrbsKind::Prbs15)
+                .take(5000)
+                .enumerate()
+                .map(|(i, b)| if injected.contains(&i) { !*b } else { *b })
+                .map(Bit::from),
+        );
+        assert!(checker.is_locked());
+        assert_eq!(checker.error_count(), injected.len());
+        #[cfg(feature = "alloc")]
+        assert_eq!(checker.errors(), injected);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_generate() {
+        for kind in KINDS {
+            let mut prbs = Prbs::new(kind);
+            let first = prbs.generate(1000);
+            let second = prbs.generate(24);
+            assert!(first.iter().eq(Prbs::new(kind).take(1000)));
+            assert!(second.iter().eq(Prbs::new(kind).skip(1000).take(24)));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_check_bits() {
+        let mut bits = Prbs::new(PrbsKind::Prbs23).generate(5000);
+        let mut checker = PrbsChecker::new(PrbsKind::Prbs23);
+        checker.check_bits(&bits);
+        assert!(checker.is_locked());
+        assert_eq!(checker.error_count(), 0);
+
+        bits.toggle(700);
+        bits.toggle(3210);
+        let mut checker = PrbsChecker::new(PrbsKind::Prbs23);
+        checker.check_bits(&bits);
+        assert_eq!(checker.position(), 5000);
+        assert_eq!(checker.errors(), [700, 3210]);
+    }
+
+    #[test]
+    fn test_relock_after_slip() {
+        let mut checker = PrbsChecker::new(PrbsKind::Prbs9);
+        // Drop a single bit from the stream.
+        checker.check(
+            Prbs::new(PrbsKind::Prbs9)
+                .take(3000)
+                .enumerate()
+                .filter(|&(i, _)| i != 1000)
+                .map(|(_, b)| b),
+        );
+        assert!(checker.is_locked());
+        let after_slip = checker.error_count();
+        assert!(after_slip > 0);
+
+        checker.check(Prbs::new(PrbsKind::Prbs9).skip(3000).take(2000));
+        assert!(checker.is_locked());
+        assert_eq!(checker.error_count(), after_slip);
+    }
+}
@@ -0,0 +1,167 @@
+//! Sequential unpacking of bits from a byte stream.
+
+use std::io;
+
+use crate::{Bit, Byte};
+
+/// Reads bits, one field at a time, from any [`io::Read`] source.
+///
+/// Bits are read in the crate's big-endian order, starting from the left-most bit of each byte,
+/// so data produced by [`BitWriter`](crate::BitWriter) reads back exactly.
+/// One byte is buffered at a time.
+#[derive(Debug)]
+pub struct BitReader<R> {
+    /// The source of the bytes.
+    inner: R,
+    /// The byte currently being read.
+    byte: Byte,
+    /// The number of bits of the current byte not yet read.
+    remaining: usize,
+}
+
+impl<R: io::Read> BitReader<R> {
+    /// Creates a reader over the bytes of `inner`.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            byte: Byte::default(),
+            remaining: 0,
+        }
+    }
+
+    /// Consumes the reader, returning the underlying source.
+    ///
+    /// Any unread bits of the buffered byte are lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Reads the next whole byte from the source, or `None` at the end of the stream.
+    fn next_byte(&mut self) -> io::Result<Option<Byte>> {
+        let mut buf = [0];
+        loop {
+            match self.inner.read(&mut buf) {
+                Ok(0) => return Ok(None),
+                Ok(_) => return Ok(Some(Byte::from(buf[0]))),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Reads the next bit, or `None` if the stream has ended.
+    pub fn read_bit(&mut self) -> io::Result<Option<Bit>> {
+        if self.remaining == 0 {
+            match self.next_byte()? {
+                Some(byte) => {
+                    self.byte = byte;
+                    self.remaining = 8;
+                }
+                None => return Ok(None),
+            }
+        }
+        self.remaining -= 1;
+        Ok(Some(self.byte.get_bit(self.remaining)))
+    }
+
+    /// Reads `count` bits, returning them as the low bits of an integer, the first bit read most significant.
+    ///
+    /// Fails with `InvalidInput` if `count` exceeds 64, and with `UnexpectedEof` if the stream
+    /// ends before `count` bits have been read.
+    pub fn read_bits(&mut self, count: u32) -> io::Result<u64> {
+        if count > 64 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("cannot read {} bits into a u64", count),
+            ));
+        }
+        let mut value = 0;
+        for read in 0..count {
+            match self.read_bit()? {
+                Some(bit) => value = (value << 1) | u64::from(bit.is_one()),
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        format!("stream ended after {} of {} bits", read, count),
+                    ))
+                }
+            }
+        }
+        Ok(value)
+    }
+
+    /// Discards the unread bits of the current byte, then reads the next whole byte.
+    ///
+    /// Returns `None` if the stream has ended.
+    pub fn read_byte_aligned(&mut self) -> io::Result<Option<Byte>> {
+        self.remaining = 0;
+        self.next_byte()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BitWriter;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_read_bits() {
+        let mut reader = BitReader::new(Cursor::new(vec![0b1010_1100, 0b1101_0000]));
+        assert_eq!(reader.read_bit().unwrap(), Some(Bit::ONE));
+        assert_eq!(reader.read_bits(3).unwrap(), 0b010);
+        assert_eq!(reader.read_bits(8).unwrap(), 0b1100_1101);
+        assert_eq!(reader.read_bits(0).unwrap(), 0);
+        assert_eq!(reader.read_bits(4).unwrap(), 0);
+        assert_eq!(reader.read_bit().unwrap(), None);
+    }
+
+    #[test]
+    fn test_unexpected_eof() {
+        let mut reader = BitReader::new(Cursor::new(vec![0xFF]));
+        reader.read_bits(5).unwrap();
+        let err = reader.read_bits(4).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        assert_eq!(err.to_string(), "stream ended after 3 of 4 bits");
+
+        let mut reader = BitReader::new(Cursor::new(vec![0xFF]));
+        let err = reader.read_bits(65).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_read_byte_aligned() {
+        let mut reader = BitReader::new(Cursor::new(vec![0b1000_0000, 0xAB, 0xCD]));
+        assert_eq!(reader.read_bit().unwrap(), Some(Bit::ONE));
+        assert_eq!(reader.read_byte_aligned().unwrap(), Some(Byte::from(0xAB)));
+        assert_eq!(reader.read_bits(4).unwrap(), 0xC);
+        assert_eq!(reader.read_byte_aligned().unwrap(), None);
+        assert_eq!(reader.read_bit().unwrap(), None);
+    }
+
+    #[test]
+    fn test_writer_round_trip() {
+        let fields: [(u64, u32); 6] = [
+            (1, 1),
+            (0b010, 3),
+            (0xDEAD, 16),
+            (0, 7),
+            (u64::MAX, 64),
+            (5, 3),
+        ];
+        let mut writer = BitWriter::new();
+        for &(value, count) in &fields {
+            writer.write_bits(value, count).unwrap();
+        }
+        let (bytes, len) = writer.finish();
+
+        let mut reader = BitReader::new(Cursor::new(bytes));
+        for &(value, count) in &fields {
+            assert_eq!(reader.read_bits(count).unwrap(), value);
+        }
+        // Only the zero padding of the final byte is left.
+        let padding = 8 - len % 8;
+        assert_eq!(reader.read_bits(padding as u32).unwrap(), 0);
+        assert_eq!(reader.read_bit().unwrap(), None);
+    }
+}
@@ -0,0 +1,335 @@
+//! Borrowed views of a range of bits within a [`BitArray`].
+
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::{DoubleEndedIterator, ExactSizeIterator, FusedIterator, Iterator};
+use core::ops::Range;
+
+use crate::{Bit, BitArray};
+
+/// A read-only view of a range of bits within a [`BitArray`], created by [`BitArray::slice`].
+///
+/// Index 0 of the slice is the first bit of the range, which need not fall on a byte boundary.
+/// Slices compare equal when they hold the same bits, whichever arrays they view, and hash the same
+/// as a [`BitArray`] holding those bits. [`BitArray::from`] copies a slice into a new array.
+#[derive(Clone, Copy)]
+pub struct BitSlice<'a> {
+    /// The array being viewed.
+    arr: &'a BitArray,
+    /// The index in the array of the first bit of the slice.
+    start: usize,
+    /// The number of bits in the slice.
+    len: usize,
+}
+
+impl<'a> BitSlice<'a> {
+    /// Creates a view of the bits of `arr` in `range`.
+    ///
+    /// Panics if the range is decreasing or extends past the end of the array.
+    pub(crate) fn new(arr: &'a BitArray, range: Range<usize>) -> Self {
+        assert!(
+            range.start <= range.end && range.end <= arr.len(),
+            "range {}..{} out of range for bit array of length {}",
+            range.start,
+            range.end,
+            arr.len()
+        );
+        Self {
+            arr,
+            start: range.start,
+            len: range.end - range.start,
+        }
+    }
+
+    /// The number of bits in the slice.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// True if the slice holds no bits.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Getter for the bit at index `idx` of the slice.
+    ///
+    /// Panics if `idx` is not less than the length of the slice.
+    pub fn get(&self, idx: usize) -> Bit {
+        assert!(
+            idx < self.len,
+            "index {} out of range for bit slice of length {}",
+            idx,
+            self.len
+        );
+        self.arr.get(self.start + idx)
+    }
+
+    /// A view of the bits of this slice in `range`, indexed relative to this slice.
+    ///
+    /// Panics if the range is decreasing or extends past the end of the slice.
+    pub fn slice(&self, range: Range<usize>) -> BitSlice<'a> {
+        assert!(
+            range.start <= range.end && range.end <= self.len,
+            "range {}..{} out of range for bit slice of length {}",
+            range.start,
+            range.end,
+            self.len
+        );
+        BitSlice {
+            arr: self.arr,
+            start: self.start + range.start,
+            len: range.end - range.start,
+        }
+    }
+
+    /// Iterates over every bit of the slice, in index order.
+    pub fn iter(&self) -> SliceIter<'a> {
+        SliceIter {
+            slice: *self,
+            idx: 0,
+            end: self.len,
+        }
+    }
+
+    /// The ranges of at most 64 bits, relative to the array, that together cover the slice.
+    fn chunks(&self) -> impl Iterator<Item = Range<usize>> {
+        let (start, end) = (self.start, self.start + self.len);
+        (start..end)
+            .step_by(64)
+            .map(move |idx| idx..(idx + 64).min(end))
+    }
+
+    /// The number of bits in the slice with a value of `1`.
+    pub fn count_ones(&self) -> usize {
+        self.chunks()
+            .map(|range| self.arr.get_bits(range).count_ones() as usize)
+            .sum()
+    }
+
+    /// Copies the bits of the slice into a new array.
+    pub fn to_bit_array(&self) -> BitArray {
+        let mut arr = BitArray::new(self.len);
+        for range in self.chunks() {
+            let value = self.arr.get_bits(range.clone());
+            arr.set_bits(range.start - self.start..range.end - self.start, value);
+        }
+        arr
+    }
+}
+
+impl PartialEq for BitSlice<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len
+            && self
+                .chunks()
+                .zip(other.chunks())
+                .all(|(a, b)| self.arr.get_bits(a) == other.arr.get_bits(b))
+    }
+}
+
+impl Eq for BitSlice<'_> {}
+
+impl PartialEq<BitArray> for BitSlice<'_> {
+    fn eq(&self, other: &BitArray) -> bool {
+        *self == other.slice(0..other.len())
+    }
+}
+
+impl PartialEq<BitSlice<'_>> for BitArray {
+    fn eq(&self, other: &BitSlice<'_>) -> bool {
+        other == self
+    }
+}
+
+/// Hashes the length, then the bits 64 at a time from the start of the slice, so equal slices
+/// hash the same wherever they start.
+impl Hash for BitSlice<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len);
+        for range in self.chunks() {
+            state.write_u64(self.arr.get_bits(range));
+        }
+    }
+}
+
+impl From<BitSlice<'_>> for BitArray {
+    fn from(slice: BitSlice<'_>) -> Self {
+        slice.to_bit_array()
+    }
+}
+
+impl fmt::Debug for BitSlice<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BitSlice(")?;
+        for bit in self.iter() {
+            f.write_str(if bit.is_one() { "1" } else { "0" })?;
+        }
+        f.write_str(")")
+    }
+}
+
+/// An iterator over the bits of a [`BitSlice`], created by [`BitSlice::iter`].
+#[derive(Debug, Clone)]
+pub struct SliceIter<'a> {
+    /// The slice being iterated over.
+    slice: BitSlice<'a>,
+    /// The index of the next bit dispatched from the front.
+    idx: usize,
+    /// One past the index of the next bit dispatched from the back.
+    end: usize,
+}
+
+impl Iterator for SliceIter<'_> {
+    type Item = Bit;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx < self.end {
+            self.idx += 1;
+            // SAFETY: the index is below `end`, which is at most the length of the slice, and the
+            // slice lies within its array.
+            Some(unsafe {
+                self.slice
+                    .arr
+                    .get_unchecked(self.slice.start + self.idx - 1)
+            })
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.idx;
+        (remaining, Some(remaining))
+    }
+}
+
+impl DoubleEndedIterator for SliceIter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.idx < self.end {
+            self.end -= 1;
+            // SAFETY: as for `next`.
+            Some(unsafe { self.slice.arr.get_unchecked(self.slice.start + self.end) })
+        } else {
+            None
+        }
+    }
+}
+
+impl ExactSizeIterator for SliceIter<'_> {}
+
+impl FusedIterator for SliceIter<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Prbs, PrbsKind};
+    use alloc::format;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_slice() {
+        let arr: BitArray = Prbs::new(PrbsKind::Prbs15).take(200).collect();
+        let bools: Vec<bool> = arr.iter().map(|b| *b).collect();
+        for (start, end) in [(0, 200), (3, 13), (7, 150), (64, 64), (199, 200)] {
+            let slice = arr.slice(start..end);
+            assert_eq!(slice.len(), end - start);
+            assert_eq!(slice.is_empty(), start == end);
+            assert!(slice
+                .iter()
+                .map(|b| *b)
+                .eq(bools[start..end].iter().copied()));
+            assert!(slice
+                .iter()
+                .rev()
+                .map(|b| *b)
+                .eq(bools[start..end].iter().rev().copied()));
+            let ones = bools[start..end].iter().filter(|&&b| b).count();
+            assert_eq!(slice.count_ones(), ones);
+            assert_eq!(slice.to_bit_array(), BitArray::from(&bools[start..end]));
+        }
+    }
+
+    #[test]
+    fn test_nested_slice() {
+        let arr: BitArray = Prbs::new(PrbsKind::Prbs15).take(100).collect();
+        let outer = arr.slice(5..90);
+        let inner = outer.slice(3..70);
+        let innermost = inner.slice(11..12);
+        assert_eq!(inner, arr.slice(8..75));
+        assert_eq!(innermost.get(0), arr.get(19));
+        assert_eq!(inner.get(66), arr.get(74));
+        assert_eq!(format!("{:?}", arr.slice(0..0)), "BitSlice()");
+    }
+
+    #[test]
+    fn test_eq_across_arrays() {
+        let a = BitArray::from_bytes(&[0b1011_0011, 0b1000_0000]);
+        let b = BitArray::from_bytes(&[0b0001_0110, 0b0111_0000]);
+        // The same nine bits, at offsets 0 and 3.
+        assert_eq!(a.slice(0..9), b.slice(3..12));
+        assert_ne!(a.slice(0..9), b.slice(4..13));
+        assert_ne!(a.slice(0..9), b.slice(3..11));
+        assert_eq!(format!("{:?}", a.slice(2..7)), "BitSlice(11001)");
+    }
+
+    #[test]
+    fn test_eq_and_from_array() {
+        let arr: BitArray = Prbs::new(PrbsKind::Prbs15).take(150).collect();
+        for (start, end) in [(0, 150), (3, 13), (7, 140), (64, 64)] {
+            let owned = BitArray::from(arr.slice(start..end));
+            assert_eq!(owned, arr.slice(start..end));
+            assert_eq!(arr.slice(start..end), owned);
+            assert_eq!(owned.len(), end - start);
+        }
+        let mut flipped = BitArray::from(arr.slice(20..30));
+        flipped.toggle(4);
+        assert_ne!(arr.slice(20..30), flipped);
+        assert_ne!(BitArray::new(9), BitArray::new(10).slice(0..9).slice(0..8));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_hash_matches_array() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::HashMap;
+
+        fn hash_of<T: Hash + ?Sized>(value: &T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let arr: BitArray = Prbs::new(PrbsKind::Prbs15).take(300).collect();
+        for (start, end) in [(0, 300), (5, 69), (64, 200), (131, 131)] {
+            let slice = arr.slice(start..end);
+            assert_eq!(hash_of(&slice), hash_of(&slice.to_bit_array()));
+        }
+        assert_eq!(hash_of(&arr), hash_of(&arr.slice(0..300)));
+
+        // Keys built from unaligned slices find those built from the same bits elsewhere.
+        let mut counts: HashMap<BitArray, usize> = HashMap::new();
+        for start in (0..280).step_by(7) {
+            *counts
+                .entry(arr.slice(start..start + 4).into())
+                .or_default() += 1;
+        }
+        let total: usize = (0..16u64)
+            .map(|value| BitArray::from_fn(4, |i| value & (8 >> i) != 0))
+            .filter_map(|key| counts.get(&key))
+            .sum();
+        assert_eq!(total, 40);
+    }
+
+    #[test]
+    #[should_panic(expected = "range 2..7 out of range for bit slice of length 5")]
+    fn test_slice_out_of_range() {
+        let arr = BitArray::new(20);
+        arr.slice(10..15).slice(2..7);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 5 out of range for bit slice of length 5")]
+    fn test_get_out_of_range() {
+        BitArray::new(20).slice(10..15).get(5);
+    }
+}
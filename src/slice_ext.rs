@@ -0,0 +1,150 @@
+//! Bit-level access to plain byte slices, without wrapping them in a container.
+
+use core::iter::{ExactSizeIterator, FusedIterator, Iterator};
+
+use crate::Bit;
+
+/// Extension trait adding bit operations directly to `[u8]`.
+///
+/// Bits are indexed across the whole slice, following the crate's big-endian convention:
+/// index 0 is the left-most bit of the first byte, index 8 the left-most bit of the second.
+pub trait BitSliceExt {
+    /// The number of bits in the slice.
+    fn bit_len(&self) -> usize;
+
+    /// Getter for the bit at index `idx`, or `None` if it is past the end of the slice.
+    fn get_bit(&self, idx: usize) -> Option<Bit>;
+
+    /// Setter for the bit at index `idx`.
+    ///
+    /// Panics if `idx` is past the end of the slice.
+    fn set_bit(&mut self, idx: usize, val: bool);
+
+    /// The number of bits in the slice with a value of `1`.
+    fn count_ones(&self) -> usize;
+
+    /// Iterates over every bit in the slice, in index order.
+    fn iter_bits(&self) -> SliceBitIter<'_>;
+}
+
+/// The mask selecting bit `idx` within its byte.
+fn mask(idx: usize) -> u8 {
+    0x80 >> (idx % 8)
+}
+
+impl BitSliceExt for [u8] {
+    fn bit_len(&self) -> usize {
+        self.len() * 8
+    }
+
+    fn get_bit(&self, idx: usize) -> Option<Bit> {
+        self.get(idx / 8)
+            .map(|byte| Bit::from(byte & mask(idx) != 0))
+    }
+
+    fn set_bit(&mut self, idx: usize, val: bool) {
+        let len = self.bit_len();
+        let byte = self
+            .get_mut(idx / 8)
+            .unwrap_or_else(|| panic!("bit index {} out of range for length {}", idx, len));
+        if val {
+            *byte |= mask(idx);
+        } else {
+            *byte &= !mask(idx);
+        }
+    }
+
+    fn count_ones(&self) -> usize {
+        self.iter().map(|byte| byte.count_ones() as usize).sum()
+    }
+
+    fn iter_bits(&self) -> SliceBitIter<'_> {
+        SliceBitIter {
+            bytes: self,
+            idx: 0,
+        }
+    }
+}
+
+/// An iterator over the bits of a byte slice, created by [`BitSliceExt::iter_bits`].
+pub struct SliceBitIter<'a> {
+    /// The bytes being iterated over.
+    bytes: &'a [u8],
+    /// The index of the next bit that will be dispatched by this iterator.
+    idx: usize,
+}
+
+impl Iterator for SliceBitIter<'_> {
+    type Item = Bit;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let res = self.bytes.get_bit(self.idx);
+        if res.is_some() {
+            self.idx += 1;
+        }
+        res
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.bytes.bit_len() - self.idx;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for SliceBitIter<'_> {}
+
+impl FusedIterator for SliceBitIter<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Byte;
+
+    #[test]
+    fn test_agrees_with_byte() {
+        let bytes = [0b1100_1101u8, 0x00, 0xFF, 0b0101_0011];
+        for (i, &b) in bytes.iter().enumerate() {
+            let byte = Byte::from(b);
+            for j in 0..8 {
+                // The byte getters count from the right-most bit.
+                assert_eq!(bytes.get_bit(i * 8 + j), Some(byte.get_bit(7 - j)));
+            }
+        }
+        assert_eq!(bytes.get_bit(32), None);
+        assert_eq!(bytes.count_ones(), 5 + 8 + 4);
+    }
+
+    #[test]
+    fn test_set_bit() {
+        let mut bytes = [0u8; 2];
+        bytes.set_bit(0, true);
+        bytes.set_bit(9, true);
+        bytes.set_bit(15, true);
+        assert_eq!(bytes, [0b1000_0000, 0b0100_0001]);
+
+        bytes.set_bit(15, false);
+        assert_eq!(bytes, [0b1000_0000, 0b0100_0000]);
+        assert_eq!(bytes.count_ones(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "bit index 16 out of range for length 16")]
+    fn test_set_bit_out_of_range() {
+        let mut bytes = [0u8; 2];
+        bytes.set_bit(16, true);
+    }
+
+    #[test]
+    fn test_iter_bits() {
+        let bytes = [0b1010_0001u8, 0b1000_0000];
+        let mut iter = bytes.iter_bits();
+        assert_eq!(iter.len(), 16);
+        assert_eq!(iter.next(), Some(Bit::ONE));
+        assert_eq!(iter.next(), Some(Bit::ZERO));
+        assert_eq!(iter.len(), 14);
+        assert_eq!(iter.filter(|b| b.is_one()).count(), 3);
+
+        let empty: [u8; 0] = [];
+        assert_eq!(empty.iter_bits().len(), 0);
+    }
+}
@@ -0,0 +1,821 @@
+//! A growable sequence of bits.
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::iter::{
+    DoubleEndedIterator, ExactSizeIterator, Extend, FromIterator, FusedIterator, IntoIterator,
+    Iterator,
+};
+use core::ops::{Bound, Deref, DerefMut, Range, RangeBounds};
+
+use crate::{Bit, BitArray, Byte};
+
+/// A growable sequence of bits, packed into bytes.
+///
+/// Dereferences to [`BitArray`] for indexed access; this type adds the operations that change
+/// the number of bits held.
+#[derive(Clone, Default, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct BitVec {
+    /// The bits held by the vector.
+    inner: BitArray,
+}
+
+impl BitVec {
+    /// Creates an empty vector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty vector with room for at least `bits` bits before it reallocates.
+    pub fn with_capacity(bits: usize) -> Self {
+        Self {
+            inner: BitArray {
+                blocks: Vec::with_capacity(bits.div_ceil(64)),
+                len: 0,
+            },
+        }
+    }
+
+    /// The number of bits the vector can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.inner.blocks.capacity().saturating_mul(64)
+    }
+
+    /// Appends a bit to the end of the vector.
+    pub fn push(&mut self, val: bool) {
+        if self.inner.len.is_multiple_of(64) {
+            self.inner.blocks.push(0);
+        }
+        self.inner.len += 1;
+        // SAFETY: the new last index is less than the new length, and a block was added if needed.
+        unsafe { self.inner.set_unchecked(self.inner.len - 1, val) };
+    }
+
+    /// Appends a bit to the end of the vector.
+    pub fn push_bit(&mut self, bit: Bit) {
+        self.push(*bit);
+    }
+
+    /// Removes the last bit of the vector and returns it, or `None` if the vector is empty.
+    pub fn pop(&mut self) -> Option<Bit> {
+        if self.inner.is_empty() {
+            return None;
+        }
+        let idx = self.inner.len - 1;
+        let bit = self.inner.get(idx);
+        // Keep the unused bits of the final block at zero.
+        self.inner.set(idx, false);
+        self.inner.len = idx;
+        if idx.is_multiple_of(64) {
+            self.inner.blocks.pop();
+        }
+        self.inner.debug_check_padding();
+        Some(bit)
+    }
+
+    /// Inserts a bit at index `idx`, shifting every later bit up by one place.
+    ///
+    /// Panics if `idx` is greater than the length of the vector.
+    pub fn insert(&mut self, idx: usize, val: bool) {
+        assert!(
+            idx <= self.inner.len,
+            "insertion index {} out of range for bit vector of length {}",
+            idx,
+            self.inner.len
+        );
+        self.push(false);
+        let (first, offset) = (idx / 8, idx % 8);
+        let keep = !(u8::MAX >> offset);
+        let mut carry = 0;
+        for (i, byte) in self.inner.bytes_mut()[first..].iter_mut().enumerate() {
+            let old = byte.0;
+            byte.0 = if i == 0 {
+                (old & keep) | ((old & !keep) >> 1)
+            } else {
+                (old >> 1) | (carry << 7)
+            };
+            carry = old & 1;
+        }
+        self.inner.set(idx, val);
+    }
+
+    /// Removes the bit at index `idx` and returns it, shifting every later bit down by one place.
+    ///
+    /// Panics if `idx` is not less than the length of the vector.
+    pub fn remove(&mut self, idx: usize) -> Bit {
+        let bit = self.inner.get(idx);
+        let (first, offset) = (idx / 8, idx % 8);
+        let keep = !(u8::MAX >> offset);
+        let bytes = self.inner.bytes_mut();
+        for i in first..bytes.len() {
+            let old = bytes[i].0;
+            let next = bytes.get(i + 1).map_or(0, |b| b.0 >> 7);
+            let shifted = (old << 1) | next;
+            bytes[i].0 = if i == first {
+                (old & keep) | (shifted & !keep)
+            } else {
+                shifted
+            };
+        }
+        self.truncate(self.inner.len - 1);
+        bit
+    }
+
+    /// Appends the eight bits of `byte` to the end of the vector, left-most first.
+    fn push_byte(&mut self, byte: u8) {
+        let start = self.inner.len;
+        self.inner.resize_len(start + 8);
+        let bytes = self.inner.bytes_mut();
+        match start % 8 {
+            0 => bytes[start / 8] = Byte(byte),
+            offset => {
+                // The byte straddles the partial final byte and a new one.
+                bytes[start / 8] |= Byte(byte >> offset);
+                bytes[start / 8 + 1] = Byte(byte << (8 - offset));
+            }
+        }
+        self.inner.debug_check_padding();
+    }
+
+    /// Moves every bit of `other` onto the end of the vector, leaving `other` empty.
+    pub fn append(&mut self, other: &mut BitVec) {
+        let len = self.inner.len + other.inner.len;
+        self.reserve(other.inner.len);
+        for &byte in other.inner.as_bytes() {
+            self.push_byte(byte);
+        }
+        // The padding of `other` was copied as zeros; drop any bits holding only padding.
+        self.truncate(len);
+        other.clear();
+    }
+
+    /// Appends the bits of `src` in `range` to the end of the vector, a byte at a time.
+    fn extend_from_range(&mut self, src: &BitArray, range: Range<usize>) {
+        let mut idx = range.start;
+        while range.end - idx >= 8 {
+            self.push_byte(src.get_bits(idx..idx + 8) as u8);
+            idx += 8;
+        }
+        for idx in idx..range.end {
+            self.push(*src.get(idx));
+        }
+    }
+
+    /// The number of blocks needed to hold `additional` more bits than the vector has room for now.
+    fn blocks_needed(&self, additional: usize) -> usize {
+        let blocks = self.inner.len.saturating_add(additional).div_ceil(64);
+        blocks.saturating_sub(self.inner.blocks.len())
+    }
+
+    /// Reserves storage for at least `additional` more bits, so that pushing them does not reallocate.
+    ///
+    /// May reserve more than requested, to avoid frequent reallocation.
+    pub fn reserve(&mut self, additional: usize) {
+        let blocks = self.blocks_needed(additional);
+        self.inner.blocks.reserve(blocks);
+    }
+
+    /// Reserves storage for at least `additional` more bits, rounding up only to a whole block.
+    pub fn reserve_exact(&mut self, additional: usize) {
+        let blocks = self.blocks_needed(additional);
+        self.inner.blocks.reserve_exact(blocks);
+    }
+
+    /// Shrinks the storage of the vector as close as possible to the blocks its bits occupy.
+    pub fn shrink_to_fit(&mut self) {
+        self.inner.blocks.shrink_to_fit();
+    }
+
+    /// Shortens the vector to `len` bits, clearing the bits dropped from its final block.
+    ///
+    /// Does nothing if the vector is already no longer than `len`.
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.inner.len {
+            self.inner.resize_len(len);
+        }
+    }
+
+    /// Changes the length of the vector to `len`, filling any new bits with `val`.
+    pub fn resize(&mut self, len: usize, val: bool) {
+        let old = self.inner.len;
+        if len <= old {
+            self.truncate(len);
+            return;
+        }
+        self.reserve(len - old);
+        self.inner.resize_len(len);
+        if val {
+            self.inner
+                .fill(old..len, true)
+                .expect("the range was just added");
+        }
+    }
+
+    /// Splits the vector in two at index `at`, returning the bits from `at` onwards.
+    ///
+    /// The returned vector starts at bit 0 whatever the alignment of `at`, and `self` keeps the bits
+    /// before `at`. Panics if `at` is greater than the length of the vector.
+    pub fn split_off(&mut self, at: usize) -> BitVec {
+        assert!(
+            at <= self.inner.len,
+            "split index {} out of range for bit vector of length {}",
+            at,
+            self.inner.len
+        );
+        let mut tail = BitVec::with_capacity(self.inner.len - at);
+        tail.extend_from_range(&self.inner, at..self.inner.len);
+        self.truncate(at);
+        tail
+    }
+
+    /// Keeps only the bits for which `f` returns true, given each bit's original index and value,
+    /// closing the gaps so the survivors stay in order.
+    ///
+    /// This is done in a single pass, reading a byte at a time and writing each byte of survivors
+    /// over bytes already read. `f` is called exactly once for each bit, in ascending order.
+    pub fn retain<F: FnMut(usize, Bit) -> bool>(&mut self, mut f: F) {
+        let len = self.inner.len;
+        let bytes = self.inner.bytes_mut();
+        let (mut kept, mut acc) = (0, 0u8);
+        for read in 0..bytes.len() {
+            let byte = bytes[read].0;
+            for offset in 0..(len - read * 8).min(8) {
+                let val = byte & (0x80 >> offset) != 0;
+                if f(read * 8 + offset, Bit::from(val)) {
+                    acc |= u8::from(val) << (7 - kept % 8);
+                    kept += 1;
+                    if kept % 8 == 0 {
+                        // At least `kept` bits have been read, so this byte has been too.
+                        bytes[kept / 8 - 1] = Byte(acc);
+                        acc = 0;
+                    }
+                }
+            }
+        }
+        if kept % 8 != 0 {
+            bytes[kept / 8] = Byte(acc);
+        }
+        self.truncate(kept);
+    }
+
+    /// Removes the bits in `range` from the vector, returning them as an iterator.
+    ///
+    /// The remaining bits are shifted down to close the gap when the iterator is dropped, whether or
+    /// not it was fully consumed. If the iterator is leaked, the vector keeps only the bits before
+    /// the range. Panics if the range is decreasing or extends past the end of the vector.
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_> {
+        let range = self.resolve_range(range);
+        let mut drained = BitVec::new();
+        drained.extend_from_range(&self.inner, range.clone());
+        let mut tail = BitVec::new();
+        tail.extend_from_range(&self.inner, range.end..self.inner.len);
+        self.truncate(range.start);
+        Drain {
+            end: drained.len(),
+            drained,
+            idx: 0,
+            tail,
+            vec: self,
+        }
+    }
+
+    /// Converts any range of indices into a `Range`.
+    ///
+    /// Panics if the range is decreasing or extends past the end of the vector.
+    fn resolve_range<R: RangeBounds<usize>>(&self, range: R) -> Range<usize> {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end + 1,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.inner.len,
+        };
+        assert!(
+            start <= end && end <= self.inner.len,
+            "range {}..{} out of range for bit vector of length {}",
+            start,
+            end,
+            self.inner.len
+        );
+        start..end
+    }
+
+    /// Removes every bit from the vector, keeping the allocated storage.
+    pub fn clear(&mut self) {
+        self.inner.blocks.clear();
+        self.inner.len = 0;
+    }
+}
+
+/// Packs the bits into bytes as they arrive, reserving space up front when the length is known.
+impl FromIterator<bool> for BitVec {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut bits = BitVec::new();
+        bits.extend(iter);
+        bits
+    }
+}
+
+/// Packs the bits into bytes as they arrive, reserving space up front when the length is known.
+impl FromIterator<Bit> for BitVec {
+    fn from_iter<I: IntoIterator<Item = Bit>>(iter: I) -> Self {
+        iter.into_iter().map(|bit| *bit).collect()
+    }
+}
+
+impl Extend<bool> for BitVec {
+    fn extend<I: IntoIterator<Item = bool>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for val in iter {
+            self.push(val);
+        }
+    }
+}
+
+impl Extend<Bit> for BitVec {
+    fn extend<I: IntoIterator<Item = Bit>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().map(|bit| *bit));
+    }
+}
+
+/// Appends every bit of each byte, left-most first.
+impl Extend<u8> for BitVec {
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0.saturating_mul(8));
+        for byte in iter {
+            self.push_byte(byte);
+        }
+    }
+}
+
+impl From<&[bool]> for BitVec {
+    fn from(bits: &[bool]) -> Self {
+        bits.iter().copied().collect()
+    }
+}
+
+impl From<Vec<bool>> for BitVec {
+    fn from(bits: Vec<bool>) -> Self {
+        bits.into_iter().collect()
+    }
+}
+
+impl From<BitVec> for BitArray {
+    fn from(bits: BitVec) -> Self {
+        bits.inner
+    }
+}
+
+/// An iterator over the bits removed from a [`BitVec`], created by [`BitVec::drain`].
+///
+/// Dropping the iterator moves the bits after the drained range down to close the gap.
+#[derive(Debug)]
+pub struct Drain<'a> {
+    /// The vector the bits were removed from, holding only the bits before the range.
+    vec: &'a mut BitVec,
+    /// The removed bits.
+    drained: BitVec,
+    /// The index of the next removed bit dispatched from the front.
+    idx: usize,
+    /// One past the index of the next removed bit dispatched from the back.
+    end: usize,
+    /// The bits after the range, to be moved back onto the vector.
+    tail: BitVec,
+}
+
+impl Iterator for Drain<'_> {
+    type Item = Bit;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx < self.end {
+            self.idx += 1;
+            // SAFETY: the index is below `end`, the length of `drained`.
+            Some(unsafe { self.drained.get_unchecked(self.idx - 1) })
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.idx;
+        (remaining, Some(remaining))
+    }
+}
+
+impl DoubleEndedIterator for Drain<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.idx < self.end {
+            self.end -= 1;
+            // SAFETY: as for `next`.
+            Some(unsafe { self.drained.get_unchecked(self.end) })
+        } else {
+            None
+        }
+    }
+}
+
+impl ExactSizeIterator for Drain<'_> {}
+
+impl FusedIterator for Drain<'_> {}
+
+impl Drop for Drain<'_> {
+    fn drop(&mut self) {
+        self.vec.append(&mut self.tail);
+    }
+}
+
+impl Deref for BitVec {
+    type Target = BitArray;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl DerefMut for BitVec {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl fmt::Debug for BitVec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt_named("BitVec", f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Prbs, PrbsKind};
+    use alloc::format;
+    use alloc::vec;
+
+    #[test]
+    fn test_push_pop() {
+        let reference: Vec<bool> = (0..300).map(|i| i % 3 == 0 || i % 7 == 2).collect();
+        let mut bits = BitVec::new();
+        for &val in &reference {
+            bits.push(val);
+        }
+        assert_eq!(bits.len(), 300);
+        assert_eq!(bits.as_bytes().len(), 38);
+        assert!(reference
+            .iter()
+            .enumerate()
+            .all(|(idx, &val)| bits.get(idx) == Bit::from(val)));
+
+        for &val in reference.iter().rev() {
+            assert_eq!(bits.pop(), Some(Bit::from(val)));
+        }
+        assert_eq!(bits.pop(), None);
+        assert!(bits.is_empty());
+        assert!(bits.as_bytes().is_empty());
+    }
+
+    #[test]
+    fn test_pop_clears_padding() {
+        let mut bits = BitVec::new();
+        bits.push_bit(Bit::ONE);
+        bits.push(true);
+        bits.push(true);
+        bits.pop();
+        let mut expected = BitVec::new();
+        expected.push(true);
+        expected.push(true);
+        assert_eq!(bits, expected);
+        assert_eq!(format!("{:?}", bits), "BitVec(len=2, 11)");
+    }
+
+    #[test]
+    fn test_count() {
+        let mut bits = BitVec::new();
+        for idx in 0..21 {
+            bits.push(idx % 3 == 0);
+        }
+        assert_eq!(bits.count_ones(), 7);
+        assert_eq!(bits.count_zeros(), 14);
+        bits.pop();
+        assert_eq!(bits.count_zeros(), 13);
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut bits = BitVec::new();
+        (0..20).for_each(|_| bits.push(true));
+        bits.clear();
+        assert!(bits.is_empty());
+        assert_eq!(bits.pop(), None);
+        bits.push(false);
+        assert_eq!(bits.len(), 1);
+    }
+
+    #[test]
+    fn test_set_all_then_push() {
+        let mut bits: BitVec = [false; 13].iter().copied().collect();
+        bits.set_all();
+        // Padding stays zero, so pushed bits are not already set.
+        bits.push(false);
+        bits.push(false);
+        assert_eq!(bits.count_ones(), 13);
+        bits.toggle_all();
+        assert_eq!(bits.count_ones(), 2);
+    }
+
+    #[test]
+    fn test_capacity() {
+        let mut bits = BitVec::with_capacity(1000);
+        assert!(bits.capacity() >= 1000);
+        bits.push(true);
+        let ptr = bits.as_bytes().as_ptr();
+        (1..1000).for_each(|_| bits.push(false));
+        assert_eq!(bits.as_bytes().as_ptr(), ptr);
+
+        bits.reserve(5000);
+        assert!(bits.capacity() >= 6000);
+        let ptr = bits.as_bytes().as_ptr();
+        (0..5000).for_each(|i| bits.push(i % 3 == 0));
+        assert_eq!(bits.as_bytes().as_ptr(), ptr);
+
+        bits.reserve_exact(64);
+        assert!(bits.capacity() >= 6064);
+
+        let before = bits.capacity();
+        while bits.len() > 10 {
+            bits.pop();
+        }
+        bits.shrink_to_fit();
+        assert!(bits.capacity() < before);
+        assert!(bits.capacity() >= 10);
+        assert_eq!(bits.count_ones(), 1);
+        assert_eq!(BitVec::new().capacity(), 0);
+    }
+
+    #[test]
+    fn test_resize_truncate() {
+        let model: Vec<bool> = Prbs::new(PrbsKind::Prbs15)
+            .take(77)
+            .map(|bit| *bit)
+            .collect();
+        for (len, val) in [
+            (77, true),
+            (80, true),
+            (130, false),
+            (200, true),
+            (64, false),
+            (3, true),
+            (0, false),
+        ] {
+            let mut bits = BitVec::from(&model[..]);
+            let mut expected = model.clone();
+            bits.resize(len, val);
+            expected.resize(len, val);
+            assert_eq!(bits, BitVec::from(expected.clone()));
+
+            bits.truncate(len + 5);
+            assert_eq!(bits.len(), len);
+            bits.truncate(len / 2);
+            expected.truncate(len / 2);
+            assert_eq!(bits, BitVec::from(expected));
+            assert_eq!(
+                bits.count_ones(),
+                bits.iter().filter(|bit| bit.is_one()).count()
+            );
+        }
+    }
+
+    #[test]
+    fn test_split_off() {
+        let model: Vec<bool> = Prbs::new(PrbsKind::Prbs15)
+            .take(150)
+            .map(|bit| *bit)
+            .collect();
+        for at in [0, 1, 8, 13, 64, 67, 149, 150] {
+            let mut head = BitVec::from(&model[..]);
+            let tail = head.split_off(at);
+            assert_eq!(head, BitVec::from(&model[..at]));
+            assert_eq!(tail, BitVec::from(&model[at..]));
+            assert_eq!(
+                head.count_ones() + tail.count_ones(),
+                model.iter().filter(|&&b| b).count()
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "split index 11 out of range for bit vector of length 10")]
+    fn test_split_off_out_of_range() {
+        BitVec::from(&[true; 10][..]).split_off(11);
+    }
+
+    #[test]
+    fn test_from_iter() {
+        for len in [0, 1, 8, 9, 1000] {
+            let bools: Vec<bool> = Prbs::new(PrbsKind::Prbs15).take(len).map(|b| *b).collect();
+            let bits: BitVec = bools.iter().copied().collect();
+            assert_eq!(bits.len(), len);
+            assert_eq!(bits.as_bytes().len(), len.div_ceil(8));
+            assert!((0..len).all(|idx| *bits.get(idx) == bools[idx]));
+
+            assert_eq!(bits.iter().collect::<BitVec>(), bits);
+            assert_eq!(BitVec::from(&bools[..]), bits);
+            assert_eq!(BitVec::from(bools), bits);
+        }
+
+        let bits: BitVec = (0..20).map(|i| i % 3 == 0).collect();
+        assert!(bits.blocks.capacity() >= 1);
+        assert_eq!(BitArray::from(bits.clone()), *bits);
+    }
+
+    #[test]
+    fn test_extend() {
+        let mut bits = BitVec::new();
+        bits.extend([true, false, true]);
+        bits.extend([Bit::ONE]);
+        bits.extend([0xF0u8, 0x0F]);
+        assert_eq!(bits.len(), 20);
+        assert_eq!(bits.as_bytes(), [0b1011_1111, 0b0000_0000, 0b1111_0000]);
+        bits.extend(core::iter::empty::<u8>());
+        assert_eq!(bits.len(), 20);
+
+        let mut aligned = BitVec::new();
+        aligned.extend([0xDEu8, 0xAD]);
+        assert_eq!(aligned.as_bytes(), [0xDE, 0xAD]);
+    }
+
+    #[test]
+    fn test_append() {
+        let mut prbs = Prbs::new(PrbsKind::Prbs15);
+        for len_a in [0, 1, 5, 8, 13] {
+            for len_b in [0, 1, 3, 8, 12, 100] {
+                let a: Vec<bool> = prbs.by_ref().take(len_a).map(|b| *b).collect();
+                let b: Vec<bool> = prbs.by_ref().take(len_b).map(|b| *b).collect();
+                let mut bits_a = BitVec::from(&a[..]);
+                let mut bits_b = BitVec::from(&b[..]);
+                bits_a.append(&mut bits_b);
+
+                let expected: Vec<bool> = a.iter().chain(&b).copied().collect();
+                assert_eq!(bits_a, BitVec::from(expected));
+                assert!(bits_b.is_empty());
+                assert_eq!(bits_b, BitVec::new());
+            }
+        }
+    }
+
+    #[test]
+    fn test_retain() {
+        let model: Vec<bool> = Prbs::new(PrbsKind::Prbs15)
+            .take(1000)
+            .map(|bit| *bit)
+            .collect();
+        let mut bits: BitVec = model.iter().copied().collect();
+        let mut calls = 0;
+        bits.retain(|idx, bit| {
+            assert_eq!(idx, calls);
+            assert_eq!(*bit, model[idx]);
+            calls += 1;
+            idx % 5 != 4
+        });
+        assert_eq!(calls, 1000);
+        let expected: Vec<bool> = model
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| idx % 5 != 4)
+            .map(|(_, &val)| val)
+            .collect();
+        assert_eq!(bits.len(), 800);
+        assert!(bits.iter().map(|bit| *bit).eq(expected.iter().copied()));
+
+        // Keeping only the ones leaves 1s up to the new length and 0s after it.
+        let ones = model.iter().filter(|&&val| val).count();
+        let mut bits: BitVec = model.iter().copied().collect();
+        bits.retain(|_, bit| bit.is_one());
+        assert_eq!(bits.len(), ones);
+        assert!(bits.all());
+        assert_eq!(bits.count_ones(), ones);
+        bits.inner.debug_check_padding();
+        bits.push(false);
+        assert!(bits.get(ones).is_zero());
+    }
+
+    #[test]
+    fn test_retain_all_or_none() {
+        let model: Vec<bool> = Prbs::new(PrbsKind::Prbs15)
+            .take(77)
+            .map(|bit| *bit)
+            .collect();
+        let mut bits: BitVec = model.iter().copied().collect();
+        let before = bits.clone();
+        bits.retain(|_, _| true);
+        assert_eq!(bits, before);
+        bits.retain(|_, _| false);
+        assert!(bits.is_empty());
+        assert_eq!(bits, BitVec::new());
+        bits.retain(|_, _| unreachable!());
+        assert!(bits.is_empty());
+    }
+
+    #[test]
+    fn test_drain() {
+        let model: Vec<bool> = Prbs::new(PrbsKind::Prbs15).take(100).map(|b| *b).collect();
+        for (start, end) in [
+            (0, 30),
+            (0, 8),
+            (13, 61),
+            (40, 41),
+            (70, 100),
+            (0, 100),
+            (50, 50),
+        ] {
+            let mut bits = BitVec::from(&model[..]);
+            let drained: Vec<bool> = bits.drain(start..end).map(|b| *b).collect();
+            assert_eq!(drained, model[start..end]);
+
+            let mut survivors = model.clone();
+            survivors.drain(start..end);
+            assert_eq!(bits, BitVec::from(survivors));
+        }
+
+        let mut bits = BitVec::from(&model[..]);
+        let mut drain = bits.drain(10..=20);
+        assert_eq!(drain.len(), 11);
+        assert_eq!(drain.next_back(), Some(Bit::from(model[20])));
+        assert_eq!(drain.next(), Some(Bit::from(model[10])));
+        drop(drain);
+        assert_eq!(bits.len(), 89);
+        assert_eq!(bits.drain(..).count(), 89);
+        assert!(bits.is_empty());
+    }
+
+    #[test]
+    fn test_drain_leak() {
+        let model: Vec<bool> = Prbs::new(PrbsKind::Prbs15).take(100).map(|b| *b).collect();
+        let mut bits = BitVec::from(&model[..]);
+        core::mem::forget(bits.drain(37..90));
+        assert_eq!(bits, BitVec::from(&model[..37]));
+        bits.push(true);
+        assert_eq!(bits.len(), 38);
+    }
+
+    #[test]
+    #[should_panic(expected = "range 90..101 out of range for bit vector of length 100")]
+    fn test_drain_out_of_range() {
+        let mut bits = BitVec::from(vec![false; 100]);
+        bits.drain(90..101);
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut prbs = Prbs::new(PrbsKind::Prbs15);
+        let mut next = |n: usize| {
+            (0..16).fold(0, |acc, _| {
+                (acc << 1) | usize::from(prbs.next().unwrap().is_one())
+            }) % n
+        };
+        let mut model: Vec<bool> = Vec::new();
+        let mut bits = BitVec::new();
+        for _ in 0..3000 {
+            // Favour inserts, so the vector grows across many bytes.
+            if model.is_empty() || next(5) < 3 {
+                let idx = next(model.len() + 1);
+                let val = next(2) == 1;
+                model.insert(idx, val);
+                bits.insert(idx, val);
+            } else {
+                let idx = next(model.len());
+                assert_eq!(bits.remove(idx), Bit::from(model.remove(idx)));
+            }
+        }
+        assert!(model.len() > 100);
+        assert_eq!(bits, BitVec::from(model));
+    }
+
+    #[test]
+    fn test_insert_remove_edges() {
+        let mut bits = BitVec::from(vec![true; 8]);
+        bits.insert(8, false);
+        assert_eq!(bits.as_bytes(), [0xFF, 0]);
+        bits.insert(0, false);
+        assert_eq!(bits.as_bytes(), [0x7F, 0x80]);
+        assert_eq!(bits.remove(9), Bit::from(false));
+        assert_eq!(bits.remove(0), Bit::from(false));
+        assert_eq!(bits, BitVec::from(vec![true; 8]));
+    }
+
+    #[test]
+    #[should_panic(expected = "insertion index 4 out of range for bit vector of length 3")]
+    fn test_insert_out_of_range() {
+        BitVec::from(vec![true; 3]).insert(4, true);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 3 out of range for bit array of length 3")]
+    fn test_remove_out_of_range() {
+        BitVec::from(vec![true; 3]).remove(3);
+    }
+}
@@ -0,0 +1,135 @@
+//! Sequential packing of bits into a byte buffer.
+
+use alloc::vec::Vec;
+
+use crate::{BitError, Byte};
+
+/// Packs bits, written one field at a time, into a byte buffer.
+///
+/// Bits are packed in the crate's big-endian order: the first bit written becomes the left-most
+/// bit of the first byte.
+#[derive(Debug, Clone, Default)]
+pub struct BitWriter {
+    /// The bytes written so far; any unused bits of the final byte are zero.
+    bytes: Vec<u8>,
+    /// The number of bits written so far.
+    len: usize,
+}
+
+impl BitWriter {
+    /// Creates a writer with an empty buffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of bits written so far.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// True if no bits have been written.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Appends a single bit.
+    pub fn write_bit(&mut self, val: bool) {
+        if self.len.is_multiple_of(8) {
+            self.bytes.push(0);
+        }
+        if val {
+            self.bytes[self.len / 8] |= 0x80 >> (self.len % 8);
+        }
+        self.len += 1;
+    }
+
+    /// Appends the low `count` bits of `value`, the most-significant of them first.
+    ///
+    /// Returns an error, writing nothing, if `count` exceeds 64 or `value` does not fit in `count` bits.
+    pub fn write_bits(&mut self, value: u64, count: u32) -> Result<(), BitError> {
+        if count > 64 {
+            return Err(BitError::InvalidLength {
+                len: count as usize,
+                max: 64,
+            });
+        }
+        if count < 64 && value >> count != 0 {
+            return Err(BitError::ValueTooWide {
+                value,
+                width: count,
+            });
+        }
+        for idx in (0..count).rev() {
+            self.write_bit(value & (1 << idx) != 0);
+        }
+        Ok(())
+    }
+
+    /// Appends the eight bits of `byte`, left-most first.
+    pub fn write_byte(&mut self, byte: Byte) {
+        for idx in (0..8).rev() {
+            self.write_bit(byte.get_bit(idx).is_one());
+        }
+    }
+
+    /// Consumes the writer, returning the packed bytes and the number of bits written.
+    ///
+    /// A partial final byte is padded with zeros on the right.
+    pub fn finish(self) -> (Vec<u8>, usize) {
+        (self.bytes, self.len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_bitstring() {
+        let mut writer = BitWriter::new();
+        writer.write_bit(true);
+        writer.write_bits(0b010, 3).unwrap();
+        writer.write_byte(Byte::from(0b1100_1101));
+        writer.write_bits(0x3, 6).unwrap();
+        writer.write_bit(true);
+        assert_eq!(writer.len(), 19);
+        // 1 010 11001101 000011 1
+        let (bytes, len) = writer.finish();
+        assert_eq!(bytes, [0b1010_1100, 0b1101_0000, 0b1110_0000]);
+        assert_eq!(len, 19);
+    }
+
+    #[test]
+    fn test_aligned() {
+        let mut writer = BitWriter::new();
+        writer.write_bits(0xDEAD_BEEF, 32).unwrap();
+        writer.write_bits(u64::MAX, 64).unwrap();
+        writer.write_bits(0, 0).unwrap();
+        let (bytes, len) = writer.finish();
+        assert_eq!(len, 96);
+        assert_eq!(bytes[..4], [0xDE, 0xAD, 0xBE, 0xEF]);
+        assert!(bytes[4..].iter().all(|&b| b == 0xFF));
+        assert_eq!(BitWriter::new().finish(), (Vec::new(), 0));
+    }
+
+    #[test]
+    fn test_write_bits_errors() {
+        let mut writer = BitWriter::new();
+        assert_eq!(
+            writer.write_bits(0, 65),
+            Err(BitError::InvalidLength { len: 65, max: 64 })
+        );
+        assert_eq!(
+            writer.write_bits(0b1000, 3),
+            Err(BitError::ValueTooWide {
+                value: 0b1000,
+                width: 3
+            })
+        );
+        assert_eq!(
+            writer.write_bits(1, 0),
+            Err(BitError::ValueTooWide { value: 1, width: 0 })
+        );
+        assert!(writer.is_empty());
+    }
+}